@@ -0,0 +1,301 @@
+//! Interpolation functions
+
+/// Interpolate table `xs`, `ys` linearly at points `at`.
+///
+/// The values in `xs` must be in ascending order. Points outside of the
+/// table are clamped to its first or last value.
+/// Returns an empty vector if `xs` and `ys` differ in length or are empty.
+///
+/// # Arguments
+///
+/// * `xs` - ascending table positions
+/// * `ys` - table values
+/// * `at` - points to interpolate at
+///
+/// # Example
+///
+/// ```
+/// use math::interpolate;
+///
+/// let xs = [0., 1., 3.];
+/// let ys = [0., 10., 30.];
+/// assert_eq!(interpolate::linear_table(&xs, &ys, &[0.5, 2., 4.]), vec![5., 20., 30.]);
+/// ```
+pub fn linear_table(xs: &[f64], ys: &[f64], at: &[f64]) -> Vec<f64> {
+	if xs.len() != ys.len() || xs.is_empty() {
+		return vec![];
+	}
+	let last = xs.len() - 1;
+	at.iter().map(|&x| {
+		let i = xs.partition_point(|&a| a <= x);
+		match i {
+			0 => ys[0],
+			_ if i > last => ys[last],
+			_ => ys[i - 1] + (ys[i] - ys[i - 1]) * (x - xs[i - 1]) / (xs[i] - xs[i - 1]),
+		}
+	}).collect()
+}
+
+/// Generate `n` evenly spaced values from `start` to `end`.
+///
+/// Both `start` and `end` are included. A single value is `start`.
+///
+/// # Arguments
+///
+/// * `start` - first value
+/// * `end` - last value
+/// * `n` - number of values
+///
+/// # Example
+///
+/// ```
+/// use math::interpolate;
+///
+/// assert_eq!(interpolate::linspace(0., 1., 5), vec![0., 0.25, 0.5, 0.75, 1.]);
+/// ```
+pub fn linspace(start: f64, end: f64, n: usize) -> Vec<f64> {
+	match n {
+		0 => vec![],
+		1 => vec![start],
+		_ => {
+			let step = (end - start) / (n - 1) as f64;
+			(0..n).map(|i| match i == n - 1 {
+				true => end,
+				false => start + step * i as f64,
+			}).collect()
+		},
+	}
+}
+
+/// Resample `samples` to length `new_len`.
+///
+/// The samples are mapped onto `[0, 1]` and interpolated linearly at
+/// `new_len` evenly spaced points, which works for both upsampling and
+/// downsampling. A single sample is repeated and an empty input gives an
+/// empty result.
+///
+/// # Arguments
+///
+/// * `samples` - evenly spaced samples
+/// * `new_len` - length of the result
+///
+/// # Example
+///
+/// ```
+/// use math::interpolate;
+///
+/// let samples = [0., 1., 2.];
+/// assert_eq!(interpolate::resample(&samples, 5), vec![0., 0.5, 1., 1.5, 2.]);
+/// ```
+pub fn resample(samples: &[f64], new_len: usize) -> Vec<f64> {
+	match samples.len() {
+		0 => vec![],
+		1 => vec![samples[0]; new_len],
+		n if n == new_len => samples.to_vec(),
+		n => linear_table(&linspace(0., 1., n), samples, &linspace(0., 1., new_len)),
+	}
+}
+
+/// Akima spline.
+///
+/// Piecewise cubic interpolant through a table of points whose slope at
+/// every point is a weighted average of the neighbouring secant slopes,
+/// computed from a five-point stencil. The weights favour the flatter
+/// side, so unlike a natural cubic spline an abrupt change in the data
+/// does not cause oscillation over the whole table. Points outside of the
+/// table are clamped to its first or last value.
+///
+/// # Example
+///
+/// ```
+/// use math::interpolate::Akima;
+///
+/// let xs = [0., 1., 2., 3., 4.];
+/// let ys = [0., 2., 4., 6., 8.];
+/// let spline = Akima::new(&xs, &ys).unwrap();
+/// assert_eq!(spline.eval(2.5), 5.);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Akima {
+	xs: Vec<f64>,
+	ys: Vec<f64>,
+	slopes: Vec<f64>,
+}
+
+impl Akima {
+	/// Create spline through table `xs`, `ys`.
+	///
+	/// Returns `None` if `xs` and `ys` differ in length, there are fewer
+	/// than five points or `xs` is not strictly increasing.
+	///
+	/// # Arguments
+	///
+	/// * `xs` - strictly ascending table positions
+	/// * `ys` - table values
+	pub fn new(xs: &[f64], ys: &[f64]) -> Option<Akima> {
+		let n = xs.len();
+		if ys.len() != n || n < 5 || !xs.windows(2).all(|pair| pair[0] < pair[1]) {
+			return None;
+		}
+		// secant slopes shifted by two, extended by two on either end
+		let mut m = vec![0.; n + 3];
+		for i in 0..n - 1 {
+			m[i + 2] = (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]);
+		}
+		m[1] = 2. * m[2] - m[3];
+		m[0] = 3. * m[2] - 2. * m[3];
+		m[n + 1] = 2. * m[n] - m[n - 1];
+		m[n + 2] = 3. * m[n] - 2. * m[n - 1];
+		let slopes = (0..n).map(|i| {
+			let w1 = (m[i + 3] - m[i + 2]).abs();
+			let w2 = (m[i + 1] - m[i]).abs();
+			match w1 + w2 == 0. {
+				true => (m[i + 1] + m[i + 2]) / 2.,
+				false => (w1 * m[i + 1] + w2 * m[i + 2]) / (w1 + w2),
+			}
+		}).collect();
+		Some(Akima { xs: xs.to_vec(), ys: ys.to_vec(), slopes })
+	}
+
+	/// Evaluate spline at `x`.
+	///
+	/// # Arguments
+	///
+	/// * `x` - point to evaluate at
+	pub fn eval(&self, x: f64) -> f64 {
+		let last = self.xs.len() - 1;
+		if x.is_nan() {
+			return f64::NAN;
+		}
+		if x <= self.xs[0] {
+			return self.ys[0];
+		}
+		if x >= self.xs[last] {
+			return self.ys[last];
+		}
+		let i = self.xs.partition_point(|&a| a <= x) - 1;
+		let h = self.xs[i + 1] - self.xs[i];
+		let d = x - self.xs[i];
+		let m = (self.ys[i + 1] - self.ys[i]) / h;
+		let (t0, t1) = (self.slopes[i], self.slopes[i + 1]);
+		let c2 = (3. * m - 2. * t0 - t1) / h;
+		let c3 = (t0 + t1 - 2. * m) / (h * h);
+		self.ys[i] + d * (t0 + d * (c2 + d * c3))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	fn natural_spline(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+		// second derivatives from the tridiagonal system of a natural spline
+		let n = xs.len();
+		let h: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+		let mut diagonal = vec![1.; n];
+		let mut rhs = vec![0.; n];
+		let mut upper = vec![0.; n];
+		for i in 1..n - 1 {
+			let lower = h[i - 1];
+			diagonal[i] = 2. * (h[i - 1] + h[i]) - lower * upper[i - 1];
+			upper[i] = h[i] / diagonal[i];
+			let r = 6. * ((ys[i + 1] - ys[i]) / h[i] - (ys[i] - ys[i - 1]) / h[i - 1]);
+			rhs[i] = (r - lower * rhs[i - 1]) / diagonal[i];
+		}
+		let mut second = vec![0.; n];
+		for i in (1..n - 1).rev() {
+			second[i] = rhs[i] - upper[i] * second[i + 1];
+		}
+		let i = (xs.partition_point(|&a| a <= x).max(1) - 1).min(n - 2);
+		let (a, b) = ((xs[i + 1] - x) / h[i], (x - xs[i]) / h[i]);
+		a * ys[i] + b * ys[i + 1] + ((a.powi(3) - a) * second[i] + (b.powi(3) - b) * second[i + 1]) * h[i] * h[i] / 6.
+	}
+
+	#[test]
+	fn akima_knots() {
+		let xs = [0., 1., 2.5, 3., 4.5, 6., 7.];
+		let ys = [1., -2., 0.5, 4., 3., 3.5, -1.];
+		let spline = super::Akima::new(&xs, &ys).unwrap();
+		for (x, y) in xs.iter().zip(ys.iter()) {
+			assert!((spline.eval(*x) - y).abs() < 1e-12);
+		}
+		assert_eq!(spline.eval(-5.), 1.);
+		assert_eq!(spline.eval(10.), -1.);
+		assert!(spline.eval(f64::NAN).is_nan());
+	}
+
+	#[test]
+	fn akima_linear() {
+		let xs = [0., 1., 3., 4., 7., 8.];
+		let ys: Vec<f64> = xs.iter().map(|x| 2. * x - 1.).collect();
+		let spline = super::Akima::new(&xs, &ys).unwrap();
+		for x in super::linspace(0., 8., 33) {
+			assert!((spline.eval(x) - (2. * x - 1.)).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn akima_overshoot() {
+		let xs = [0., 1., 2., 3., 4., 5., 6., 7.];
+		let ys = [0., 0., 0., 0., 1., 1., 1., 1.];
+		let spline = super::Akima::new(&xs, &ys).unwrap();
+		let overshoot = |f: &dyn Fn(f64) -> f64| super::linspace(0., 7., 701).iter()
+			.fold(0f64, |a, &x| a.max(f(x) - 1.).max(-f(x)));
+		let akima = overshoot(&|x| spline.eval(x));
+		let natural = overshoot(&|x| natural_spline(&xs, &ys, x));
+		assert!(akima < 1e-12);
+		assert!(natural > 0.05);
+	}
+
+	#[test]
+	fn akima_invalid() {
+		let xs = [0., 1., 2., 3., 4.];
+		let ys = [0., 1., 4., 9., 16.];
+		assert!(super::Akima::new(&xs, &ys).is_some());
+		assert_eq!(super::Akima::new(&xs[..4], &ys[..4]), None);
+		assert_eq!(super::Akima::new(&xs, &ys[..4]), None);
+		assert_eq!(super::Akima::new(&[0., 1., 1., 3., 4.], &ys), None);
+		assert_eq!(super::Akima::new(&[0., 2., 1., 3., 4.], &ys), None);
+		assert_eq!(super::Akima::new(&[0., 1., 2., 3., f64::NAN], &ys), None);
+		assert_eq!(super::Akima::new(&[f64::NAN, 1., 2., 3., 4.], &ys), None);
+	}
+
+	#[test]
+	fn linear_table() {
+		let xs = [0., 1., 3.];
+		let ys = [0., 10., 30.];
+		let at = [-1., 0., 0.5, 1., 2., 3., 4.];
+		assert_eq!(super::linear_table(&xs, &ys, &at), vec![0., 0., 5., 10., 20., 30., 30.]);
+		assert_eq!(super::linear_table(&xs, &ys[..2], &at), vec![]);
+		assert_eq!(super::linear_table(&[], &[], &at), vec![]);
+	}
+
+	#[test]
+	fn linspace() {
+		assert_eq!(super::linspace(0., 1., 5), vec![0., 0.25, 0.5, 0.75, 1.]);
+		assert_eq!(super::linspace(2., -2., 3), vec![2., 0., -2.]);
+		assert_eq!(super::linspace(3., 7., 1), vec![3.]);
+		assert_eq!(super::linspace(3., 7., 0), vec![]);
+		assert_eq!(*super::linspace(0., 0.3, 7).last().unwrap(), 0.3);
+	}
+
+	#[test]
+	fn resample_up() {
+		assert_eq!(super::resample(&[0., 1., 2.], 5), vec![0., 0.5, 1., 1.5, 2.]);
+		assert_eq!(super::resample(&[0., 4.], 5), vec![0., 1., 2., 3., 4.]);
+	}
+
+	#[test]
+	fn resample_down() {
+		let samples = [0., 1., 2., 3., 4., 5., 6., 7., 8.];
+		assert_eq!(super::resample(&samples, 3), vec![0., 4., 8.]);
+		assert_eq!(super::resample(&samples, 5), vec![0., 2., 4., 6., 8.]);
+	}
+
+	#[test]
+	fn resample_edge() {
+		let samples = [3., 1., 4., 1., 5.];
+		assert_eq!(super::resample(&samples, 5), samples.to_vec());
+		assert_eq!(super::resample(&samples, 0), vec![]);
+		assert_eq!(super::resample(&[7.], 3), vec![7., 7., 7.]);
+		assert_eq!(super::resample(&[], 3), vec![]);
+	}
+}
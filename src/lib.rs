@@ -1,6 +1,39 @@
 //! Mathematical utilities
+//!
+//! With the default `std` feature disabled the crate is `no_std` and only
+//! provides the rounding functions of the `round` module that neither
+//! allocate nor need randomness. The `libm` feature then has to be enabled
+//! for the float functions missing from `core`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the std or the libm feature has to be enabled");
+
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "std")]
+pub mod distributions;
+#[cfg(feature = "std")]
+pub mod interpolate;
+#[cfg(feature = "std")]
+pub mod matrix;
+#[cfg(feature = "std")]
 pub mod mean;
+#[cfg(feature = "std")]
+pub mod num_theory;
 pub mod round;
+#[cfg(feature = "std")]
+pub mod sequence;
+#[cfg(feature = "std")]
+pub mod special;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod variance;
+#[cfg(feature = "std")]
+pub mod vector;
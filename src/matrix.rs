@@ -0,0 +1,465 @@
+//! Matrix type
+use std::ops::{ Index, IndexMut };
+
+/// Dense matrix of `f64` values.
+///
+/// Elements are stored in row-major order and accessed by
+/// `(row, column)` index.
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+///
+/// let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 4.]]).unwrap();
+/// assert_eq!(m[(1, 0)], 3.);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+	rows: usize,
+	cols: usize,
+	data: Vec<f64>,
+}
+
+impl Matrix {
+	/// Create matrix from `rows`.
+	///
+	/// Returns `None` if the rows differ in length.
+	///
+	/// # Arguments
+	///
+	/// * `rows` - collection of rows
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::from_rows(&[vec![1., 2., 3.], vec![4., 5., 6.]]).unwrap();
+	/// assert_eq!((m.rows(), m.cols()), (2, 3));
+	/// assert_eq!(Matrix::from_rows(&[vec![1., 2.], vec![3.]]), None);
+	/// ```
+	pub fn from_rows(rows: &[Vec<f64>]) -> Option<Matrix> {
+		let cols = rows.first().map_or(0, |row| row.len());
+		if rows.iter().any(|row| row.len() != cols) {
+			return None;
+		}
+		Some(Matrix {
+			rows: rows.len(),
+			cols,
+			data: rows.iter().flat_map(|row| row.iter().cloned()).collect(),
+		})
+	}
+
+	/// Create identity matrix of dimension `n`.
+	///
+	/// # Arguments
+	///
+	/// * `n` - number of rows and columns
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::identity(2);
+	/// assert_eq!(m, Matrix::from_rows(&[vec![1., 0.], vec![0., 1.]]).unwrap());
+	/// ```
+	pub fn identity(n: usize) -> Matrix {
+		let mut m = Matrix::zeros(n, n);
+		for i in 0..n {
+			m[(i, i)] = 1.;
+		}
+		m
+	}
+
+	/// Create matrix of zeros with `rows` rows and `cols` columns.
+	///
+	/// # Arguments
+	///
+	/// * `rows` - number of rows
+	/// * `cols` - number of columns
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::zeros(2, 3);
+	/// assert_eq!(m[(1, 2)], 0.);
+	/// ```
+	pub fn zeros(rows: usize, cols: usize) -> Matrix {
+		Matrix { rows, cols, data: vec![0.; rows * cols] }
+	}
+
+	/// Calculate Cholesky decomposition of matrix.
+	///
+	/// Returns the lower-triangular matrix `L` with `L * Lᵀ` equal to the
+	/// matrix. Symmetry is checked with a relative tolerance of `1e-10` to
+	/// allow for floating point noise.
+	/// Returns `None` if the matrix is not square, symmetric or positive
+	/// definite.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let a = Matrix::from_rows(&[vec![4., 2.], vec![2., 10.]]).unwrap();
+	/// let l = Matrix::from_rows(&[vec![2., 0.], vec![1., 3.]]).unwrap();
+	/// assert_eq!(a.cholesky(), Some(l));
+	/// ```
+	pub fn cholesky(&self) -> Option<Matrix> {
+		let n = self.rows;
+		if self.cols != n {
+			return None;
+		}
+		for i in 0..n {
+			for j in 0..i {
+				let (a, b) = (self[(i, j)], self[(j, i)]);
+				if (a - b).abs() > 1e-10 * a.abs().max(b.abs()) {
+					return None;
+				}
+			}
+		}
+		let mut l = Matrix::zeros(n, n);
+		for j in 0..n {
+			let diagonal = self[(j, j)] - (0..j).fold(0., |a, k| a + l[(j, k)] * l[(j, k)]);
+			if diagonal.is_nan() || diagonal <= 0. {
+				return None;
+			}
+			l[(j, j)] = diagonal.sqrt();
+			for i in j + 1..n {
+				let sum = (0..j).fold(0., |a, k| a + l[(i, k)] * l[(j, k)]);
+				l[(i, j)] = (self[(i, j)] - sum) / l[(j, j)];
+			}
+		}
+		Some(l)
+	}
+
+	/// Number of columns.
+	pub fn cols(&self) -> usize {
+		self.cols
+	}
+
+	/// Copy column `col` of matrix.
+	///
+	/// # Arguments
+	///
+	/// * `col` - column index
+	///
+	/// # Panics
+	///
+	/// Panics if `col` is out of bounds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 4.]]).unwrap();
+	/// assert_eq!(m.column(1), vec![2., 4.]);
+	/// ```
+	pub fn column(&self, col: usize) -> Vec<f64> {
+		(0..self.rows).map(|row| self[(row, col)]).collect()
+	}
+
+	/// Multiply matrix by `other`.
+	///
+	/// Returns `None` if the number of columns does not match the number of
+	/// rows in `other`.
+	///
+	/// # Arguments
+	///
+	/// * `other` - right-hand side matrix
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let a = Matrix::from_rows(&[vec![1., 2.], vec![3., 4.]]).unwrap();
+	/// let b = Matrix::from_rows(&[vec![5.], vec![6.]]).unwrap();
+	/// assert_eq!(a.multiply(&b), Matrix::from_rows(&[vec![17.], vec![39.]]));
+	/// ```
+	pub fn multiply(&self, other: &Matrix) -> Option<Matrix> {
+		if self.cols != other.rows {
+			return None;
+		}
+		let mut m = Matrix::zeros(self.rows, other.cols);
+		for i in 0..self.rows {
+			for j in 0..other.cols {
+				m[(i, j)] = (0..self.cols).fold(0., |a, k| a + self[(i, k)] * other[(k, j)]);
+			}
+		}
+		Some(m)
+	}
+
+	/// Raise matrix to power `exp`.
+	///
+	/// Uses exponentiation by squaring. A matrix raised to power zero is the
+	/// identity matrix.
+	/// Returns `None` if the matrix is not square.
+	///
+	/// # Arguments
+	///
+	/// * `exp` - exponent
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::from_rows(&[vec![1., 1.], vec![1., 0.]]).unwrap();
+	/// assert_eq!(m.pow(10).unwrap()[(0, 1)], 55.);
+	/// ```
+	pub fn pow(&self, exp: u32) -> Option<Matrix> {
+		if self.rows != self.cols {
+			return None;
+		}
+		let mut result = Matrix::identity(self.rows);
+		let mut base = self.clone();
+		let mut exp = exp;
+		while exp > 0 {
+			if exp & 1 == 1 {
+				result = result.multiply(&base)?;
+			}
+			exp >>= 1;
+			if exp > 0 {
+				base = base.multiply(&base)?;
+			}
+		}
+		Some(result)
+	}
+
+	/// Row `row` of matrix.
+	///
+	/// # Arguments
+	///
+	/// * `row` - row index
+	///
+	/// # Panics
+	///
+	/// Panics if `row` is out of bounds.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 4.]]).unwrap();
+	/// assert_eq!(m.row(1), &[3., 4.]);
+	/// ```
+	pub fn row(&self, row: usize) -> &[f64] {
+		assert!(row < self.rows, "matrix index out of bounds");
+		&self.data[row * self.cols..(row + 1) * self.cols]
+	}
+
+	/// Number of rows.
+	pub fn rows(&self) -> usize {
+		self.rows
+	}
+
+	/// Calculate trace of matrix.
+	///
+	/// The trace is the sum of the diagonal elements.
+	/// Returns `None` if the matrix is not square.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 4.]]).unwrap();
+	/// assert_eq!(m.trace(), Some(5.));
+	/// ```
+	pub fn trace(&self) -> Option<f64> {
+		match self.rows == self.cols {
+			true => Some((0..self.rows).fold(0., |a, i| a + self[(i, i)])),
+			false => None,
+		}
+	}
+
+	/// Transpose matrix.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use math::matrix::Matrix;
+	///
+	/// let m = Matrix::from_rows(&[vec![1., 2.]]).unwrap();
+	/// assert_eq!(m.transpose(), Matrix::from_rows(&[vec![1.], vec![2.]]).unwrap());
+	/// ```
+	pub fn transpose(&self) -> Matrix {
+		let mut m = Matrix::zeros(self.cols, self.rows);
+		for i in 0..self.rows {
+			for j in 0..self.cols {
+				m[(j, i)] = self[(i, j)];
+			}
+		}
+		m
+	}
+}
+
+impl Index<(usize, usize)> for Matrix {
+	type Output = f64;
+
+	fn index(&self, (row, col): (usize, usize)) -> &f64 {
+		assert!(row < self.rows && col < self.cols, "matrix index out of bounds");
+		&self.data[row * self.cols + col]
+	}
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+	fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+		assert!(row < self.rows && col < self.cols, "matrix index out of bounds");
+		&mut self.data[row * self.cols + col]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Matrix;
+
+	#[test]
+	fn from_rows() {
+		let m = Matrix::from_rows(&[vec![1., 2., 3.], vec![4., 5., 6.]]).unwrap();
+		assert_eq!((m.rows(), m.cols()), (2, 3));
+		assert_eq!(m[(0, 2)], 3.);
+		assert_eq!(m[(1, 0)], 4.);
+		assert_eq!(Matrix::from_rows(&[vec![1., 2.], vec![3.]]), None);
+		assert_eq!(Matrix::from_rows(&[]), Some(Matrix::zeros(0, 0)));
+	}
+
+	#[test]
+	fn cholesky() {
+		let a = Matrix::from_rows(&[vec![25., 15., -5.], vec![15., 18., 0.], vec![-5., 0., 11.]]).unwrap();
+		let l = a.cholesky().unwrap();
+		let expected = Matrix::from_rows(&[vec![5., 0., 0.], vec![3., 3., 0.], vec![-1., 1., 3.]]).unwrap();
+		assert_eq!(l, expected);
+		for i in 0..3 {
+			for j in i + 1..3 {
+				assert_eq!(l[(i, j)], 0.);
+			}
+		}
+	}
+
+	#[test]
+	fn cholesky_reconstruct() {
+		let a = Matrix::from_rows(&[
+			vec![4.2, 1.1, -0.7, 0.3],
+			vec![1.1, 3.5, 0.4, -1.2],
+			vec![-0.7, 0.4, 2.9, 0.8],
+			vec![0.3, -1.2, 0.8, 5.1],
+		]).unwrap();
+		let l = a.cholesky().unwrap();
+		let product = l.multiply(&l.transpose()).unwrap();
+		for i in 0..4 {
+			for j in 0..4 {
+				assert!((product[(i, j)] - a[(i, j)]).abs() < 1e-12);
+			}
+		}
+		assert_eq!(Matrix::identity(5).cholesky(), Some(Matrix::identity(5)));
+		assert_eq!(Matrix::zeros(0, 0).cholesky(), Some(Matrix::zeros(0, 0)));
+	}
+
+	#[test]
+	fn cholesky_invalid() {
+		let indefinite = Matrix::from_rows(&[vec![1., 2.], vec![2., 1.]]).unwrap();
+		let singular = Matrix::from_rows(&[vec![1., 1.], vec![1., 1.]]).unwrap();
+		let asymmetric = Matrix::from_rows(&[vec![4., 1.], vec![2., 4.]]).unwrap();
+		assert_eq!(indefinite.cholesky(), None);
+		assert_eq!(singular.cholesky(), None);
+		assert_eq!(asymmetric.cholesky(), None);
+		assert_eq!(Matrix::zeros(2, 3).cholesky(), None);
+	}
+
+	#[test]
+	fn column_and_row() {
+		let m = Matrix::from_rows(&[vec![1., 2., 3.], vec![4., 5., 6.]]).unwrap();
+		assert_eq!(m.column(0), vec![1., 4.]);
+		assert_eq!(m.column(2), vec![3., 6.]);
+		assert_eq!(m.row(0), &[1., 2., 3.]);
+		assert_eq!(m.row(1), &[4., 5., 6.]);
+	}
+
+	#[test]
+	#[should_panic]
+	fn row_out_of_bounds() {
+		Matrix::zeros(2, 2).row(2);
+	}
+
+	#[test]
+	fn identity() {
+		let m = Matrix::identity(3);
+		for i in 0..3 {
+			for j in 0..3 {
+				assert_eq!(m[(i, j)], if i == j { 1. } else { 0. });
+			}
+		}
+	}
+
+	#[test]
+	fn multiply() {
+		let a = Matrix::from_rows(&[vec![1., 2., 3.], vec![4., 5., 6.]]).unwrap();
+		let b = Matrix::from_rows(&[vec![7., 8.], vec![9., 10.], vec![11., 12.]]).unwrap();
+		let expected = Matrix::from_rows(&[vec![58., 64.], vec![139., 154.]]);
+		assert_eq!(a.multiply(&b), expected);
+		assert_eq!(a.multiply(&Matrix::identity(3)), Some(a.clone()));
+		assert_eq!(a.multiply(&a), None);
+	}
+
+	#[test]
+	fn pow() {
+		let m = Matrix::from_rows(&[vec![2., 1.], vec![0., 3.]]).unwrap();
+		assert_eq!(m.pow(0), Some(Matrix::identity(2)));
+		assert_eq!(m.pow(1), Some(m.clone()));
+		assert_eq!(m.pow(3), m.multiply(&m).and_then(|m2| m2.multiply(&m)));
+		for n in 0..5 {
+			assert_eq!(Matrix::identity(3).pow(n), Some(Matrix::identity(3)));
+		}
+	}
+
+	#[test]
+	fn pow_transition() {
+		let m = Matrix::from_rows(&[vec![0.9, 0.1], vec![0.5, 0.5]]).unwrap();
+		let p = m.pow(2).unwrap();
+		let expected = [[0.86, 0.14], [0.7, 0.3]];
+		for i in 0..2 {
+			for j in 0..2 {
+				assert!((p[(i, j)] - expected[i][j]).abs() < 1e-12);
+			}
+		}
+		let p = m.pow(50).unwrap();
+		assert!((p[(0, 0)] - 5. / 6.).abs() < 1e-12);
+		assert!((p[(1, 1)] - 1. / 6.).abs() < 1e-12);
+	}
+
+	#[test]
+	fn pow_non_square() {
+		assert_eq!(Matrix::zeros(2, 3).pow(2), None);
+		assert_eq!(Matrix::zeros(2, 3).pow(0), None);
+	}
+
+	#[test]
+	fn trace() {
+		assert_eq!(Matrix::identity(4).trace(), Some(4.));
+		let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 4.]]).unwrap();
+		assert_eq!(m.trace(), Some(5.));
+		assert_eq!(Matrix::zeros(2, 3).trace(), None);
+	}
+
+	#[test]
+	fn transpose() {
+		let a = Matrix::from_rows(&[vec![1., 2., 3.], vec![4., 5., 6.]]).unwrap();
+		let expected = Matrix::from_rows(&[vec![1., 4.], vec![2., 5.], vec![3., 6.]]).unwrap();
+		assert_eq!(a.transpose(), expected);
+		assert_eq!(a.transpose().transpose(), a);
+	}
+
+	#[test]
+	#[should_panic]
+	fn index_out_of_bounds() {
+		assert_eq!(Matrix::zeros(2, 2)[(0, 2)], 0.);
+	}
+}
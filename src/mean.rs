@@ -1,8 +1,12 @@
 //! Functions for calculating mean
-use std::f64::NAN;
+use stats;
+use std::collections::HashMap;
 
 /// Calculates arithmetic mean (AM) of data set `slice`.
 ///
+/// The values are added with compensated summation, so that small values
+/// are not lost next to large ones.
+///
 /// # Arguments
 ///
 /// * `slice` - collection of values
@@ -16,12 +20,89 @@ use std::f64::NAN;
 /// assert_eq!(mean::arithmetic(&slice), 12.);
 /// ```
 pub fn arithmetic(slice: &[f64]) -> f64 {
-	slice.iter().fold(0., |a, b| a + b) / slice.len() as f64
+	sum(slice) / slice.len() as f64
+}
+
+/// Calculate arithmetic mean of data set `slice` ignoring `NAN` values.
+///
+/// Same as `arithmetic` for the values of `slice` that are not `NAN`, so that
+/// missing samples marked as `NAN` do not poison the result. Infinite
+/// values are included.
+/// Returns `NAN` if `slice` has no values other than `NAN`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::arithmetic_nan_ignore(&[8., f64::NAN, 16.]), 12.);
+/// ```
+pub fn arithmetic_nan_ignore(slice: &[f64]) -> f64 {
+	nan_ignore(slice, arithmetic)
+}
+
+/// Calculate contraharmonic mean of data set `slice`.
+///
+/// Returns `Σx² / Σx`. For positive values the contraharmonic mean is
+/// greater than or equal to the arithmetic mean.
+/// Returns `NAN` if `slice` is empty or its values sum to zero.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [1., 3.];
+/// assert_eq!(mean::contraharmonic(&slice), 2.5);
+/// ```
+pub fn contraharmonic(slice: &[f64]) -> f64 {
+	let total = slice.iter().fold(0., |a, b| a + b);
+	match total == 0. {
+		true => f64::NAN,
+		false => slice.iter().fold(0., |a, b| a + b * b) / total,
+	}
+}
+
+/// Update exponentially weighted moving average (EWMA) `previous`.
+///
+/// Returns `alpha * new_value + (1 - alpha) * previous`.
+/// If `alpha` is outside of `(0, 1]`, function returns `NAN`.
+///
+/// # Arguments
+///
+/// * `previous` - previous average
+/// * `new_value` - value to add
+/// * `alpha` - smoothing factor
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::ewma_update(10., 20., 0.25), 12.5);
+/// ```
+pub fn ewma_update(previous: f64, new_value: f64, alpha: f64) -> f64 {
+	match alpha > 0. && alpha <= 1. {
+		true => alpha * new_value + (1. - alpha) * previous,
+		false => f64::NAN,
+	}
 }
 
 /// Calculate geometric mean (GM) of data set `slice`.
 ///
+/// The mean is calculated as the exponential of the mean of the logarithms
+/// of the magnitudes, so that it does not overflow or underflow when the
+/// product of the values would. Returns 0 if any value is 0.
 /// If the result would be imaginary, function returns `NAN`.
+/// Returns `NAN` if `slice` is empty.
 ///
 /// # Arguments
 ///
@@ -36,13 +117,43 @@ pub fn arithmetic(slice: &[f64]) -> f64 {
 /// assert_eq!(mean::geometric(&slice), 12.);
 /// ```
 pub fn geometric(slice: &[f64]) -> f64 {
-	let product = slice.iter().fold(1., |a, b| a * b);
-	match product < 0. {
-		true => NAN,
-		false => product.powf(1. / slice.len() as f64),
+	if slice.iter().any(|x| x.is_nan()) {
+		return f64::NAN;
+	}
+	if slice.contains(&0.) {
+		// the product of zero and infinity is undefined
+		return match slice.iter().any(|x| x.is_infinite()) {
+			true => f64::NAN,
+			false => 0.,
+		};
+	}
+	// the product is negative if an odd number of values is negative
+	match slice.iter().filter(|x| **x < 0.).count() % 2 == 1 {
+		true => f64::NAN,
+		false => arithmetic(&slice.iter().map(|x| x.abs().ln()).collect::<Vec<f64>>()).exp(),
 	}
 }
 
+/// Calculate geometric mean of data set `slice` ignoring `NAN` values.
+///
+/// Same as `geometric` for the values of `slice` that are not `NAN`, see
+/// `arithmetic_nan_ignore`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::geometric_nan_ignore(&[9., f64::NAN, 16.]), 12.);
+/// ```
+pub fn geometric_nan_ignore(slice: &[f64]) -> f64 {
+	nan_ignore(slice, geometric)
+}
+
 /// Calculate harmonic mean (HM) of data set `slice`.
 ///
 /// # Arguments
@@ -61,10 +172,707 @@ pub fn harmonic(slice: &[f64]) -> f64 {
 	slice.len() as f64 / slice.iter().fold(0., |a, b| a + 1. / b)
 }
 
+/// Calculate harmonic mean of data set `slice` ignoring `NAN` values.
+///
+/// Same as `harmonic` for the values of `slice` that are not `NAN`, see
+/// `arithmetic_nan_ignore`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::harmonic_nan_ignore(&[1., f64::NAN, 7.]), 1.75);
+/// ```
+pub fn harmonic_nan_ignore(slice: &[f64]) -> f64 {
+	nan_ignore(slice, harmonic)
+}
+
+/// Calculate Heronian mean of `a` and `b`.
+///
+/// Returns `(a + √(ab) + b) / 3`, which lies between the geometric and the
+/// arithmetic mean.
+/// Returns `NAN` if either value is negative.
+///
+/// # Arguments
+///
+/// * `a` - first value
+/// * `b` - second value
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::heronian(1., 4.), 7. / 3.);
+/// ```
+pub fn heronian(a: f64, b: f64) -> f64 {
+	match a < 0. || b < 0. {
+		true => f64::NAN,
+		false => (a + a.sqrt() * b.sqrt() + b) / 3.,
+	}
+}
+
+/// Calculate Lehmer mean of data set `slice`.
+///
+/// Returns `Σx^p / Σx^(p - 1)`. The Lehmer mean is the harmonic mean for
+/// `p = 0`, the geometric mean of two values for `p = 0.5`, the arithmetic
+/// mean for `p = 1` and the contraharmonic mean for `p = 2`.
+/// Returns `NAN` if `slice` is empty or the denominator is zero.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `p` - exponent
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [1., 3.];
+/// assert_eq!(mean::lehmer(&slice, 1.), 2.);
+/// assert_eq!(mean::lehmer(&slice, 2.), 2.5);
+/// ```
+pub fn lehmer(slice: &[f64], p: f64) -> f64 {
+	let denominator = slice.iter().fold(0., |a, x| a + x.powf(p - 1.));
+	match denominator == 0. {
+		true => f64::NAN,
+		false => slice.iter().fold(0., |a, x| a + x.powf(p)) / denominator,
+	}
+}
+
+/// Calculate logarithmic mean of `a` and `b`.
+///
+/// Returns `(b - a) / (ln b - ln a)`, or `a` if the values are equal, which
+/// is the limit of the quotient. The logarithmic mean lies between the
+/// geometric and the arithmetic mean.
+/// Returns `NAN` if either value is not positive.
+///
+/// # Arguments
+///
+/// * `a` - first value
+/// * `b` - second value
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::logarithmic(5., 5.), 5.);
+/// assert!((mean::logarithmic(1., std::f64::consts::E) - (std::f64::consts::E - 1.)).abs() < 1e-15);
+/// ```
+pub fn logarithmic(a: f64, b: f64) -> f64 {
+	if a <= 0. || b <= 0. || a.is_nan() || b.is_nan() {
+		return f64::NAN;
+	}
+	if a == b {
+		return a;
+	}
+	// the quotient is more accurate but can overflow or underflow
+	let ratio = b / a;
+	match ratio == 0. || ratio.is_infinite() {
+		true => (b - a) / (b.ln() - a.ln()),
+		false => (b - a) / ratio.ln(),
+	}
+}
+
+/// Calculate median of data set `slice`.
+///
+/// Returns the middle value of sorted `slice`, or the arithmetic mean of
+/// the two middle values if the length of `slice` is even. Values are
+/// sorted with `total_cmp`, which puts `NAN` after every other value.
+/// Same as `stats::median`.
+/// Returns `NAN` if `slice` is empty.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [7., 1., 4., 2.];
+/// assert_eq!(mean::median(&slice), 3.);
+/// ```
+pub fn median(slice: &[f64]) -> f64 {
+	stats::median(slice)
+}
+
+/// Calculate midrange of data set `slice`.
+///
+/// Returns `(min + max) / 2`, finding both extremes in a single pass.
+/// Returns `NAN` if `slice` is empty or contains `NAN`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [3., -1., 7., 2.];
+/// assert_eq!(mean::midrange(&slice), 3.);
+/// ```
+pub fn midrange(slice: &[f64]) -> f64 {
+	if slice.is_empty() {
+		return f64::NAN;
+	}
+	// f64::min and f64::max skip NAN, so it is carried separately
+	let (min, max, nan) = slice.iter().fold((f64::INFINITY, f64::NEG_INFINITY, false), |a, x| {
+		(a.0.min(*x), a.1.max(*x), a.2 || x.is_nan())
+	});
+	if nan {
+		return f64::NAN;
+	}
+	// halving first avoids overflow for extremes of the same sign but loses
+	// the lowest bit of subnormal values, so it is only done on overflow
+	match (min + max).is_finite() {
+		true => (min + max) / 2.,
+		false => min / 2. + max / 2.,
+	}
+}
+
+/// Find most frequent values of data set `slice`.
+///
+/// Returns every value that occurs the highest number of times, in
+/// ascending order, so that multimodal data returns several values and
+/// data without repeated values returns all of them. Values are compared
+/// by their bit patterns, so `0.1 + 0.2` does not match `0.3` and `-0.`
+/// does not match `0.`.
+/// Returns an empty vector if `slice` is empty.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [1., 3., 2., 3., 1., 4.];
+/// assert_eq!(mean::mode(&slice), vec![1., 3.]);
+/// ```
+pub fn mode(slice: &[f64]) -> Vec<f64> {
+	let mut counts: HashMap<u64, usize> = HashMap::new();
+	for x in slice.iter() {
+		*counts.entry(x.to_bits()).or_insert(0) += 1;
+	}
+	let max = counts.values().cloned().max().unwrap_or(0);
+	let mut modes: Vec<f64> = counts.iter()
+		.filter(|&(_, count)| *count == max)
+		.map(|(bits, _)| f64::from_bits(*bits))
+		.collect();
+	modes.sort_by(|a, b| a.total_cmp(b));
+	modes
+}
+
+/// Calculate power mean of data set `slice` with exponent `p`.
+///
+/// Returns `(Σx^p / n)^(1 / p)`, the generalized mean that is the
+/// harmonic mean for `p = -1`, the arithmetic mean for `p = 1` and the
+/// quadratic mean for `p = 2`. For `p = 0` the geometric mean, which is the
+/// limit of the power mean, is returned.
+/// Returns `NAN` if `slice` is empty.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `p` - exponent
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [1., 8.];
+/// assert_eq!(mean::power(&slice, 1.), 4.5);
+/// assert_eq!(mean::power(&slice, 0.), mean::geometric(&slice));
+/// ```
+pub fn power(slice: &[f64], p: f64) -> f64 {
+	if slice.is_empty() {
+		return f64::NAN;
+	}
+	match p == 0. {
+		true => geometric(slice),
+		false => (slice.iter().fold(0., |a, b| a + b.powf(p)) / slice.len() as f64).powf(1. / p),
+	}
+}
+
+/// Calculate quadratic mean (QM) of data set `slice`.
+///
+/// The quadratic mean, also known as root mean square (RMS), is the square
+/// root of the arithmetic mean of the squared values.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [1., 7.];
+/// assert_eq!(mean::quadratic(&slice), 5.);
+/// ```
+pub fn quadratic(slice: &[f64]) -> f64 {
+	(slice.iter().fold(0., |a, b| a + b * b) / slice.len() as f64).sqrt()
+}
+
+/// Calculate trimmed mean of data set `slice`.
+///
+/// Sorts `slice`, removes `floor(n * proportion)` values from each end and
+/// returns the arithmetic mean of the remaining values, so that outliers
+/// do not distort the result.
+/// Returns `NAN` if `slice` is empty or `proportion` is outside of
+/// `[0, 0.5)`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `proportion` - proportion of values to remove from each end
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let slice = [3., 1., 1000., 2., -500.];
+/// assert_eq!(mean::trimmed(&slice, 0.2), 2.);
+/// ```
+pub fn trimmed(slice: &[f64], proportion: f64) -> f64 {
+	if !(0. ..0.5).contains(&proportion) {
+		return f64::NAN;
+	}
+	let mut sorted = slice.to_vec();
+	sorted.sort_by(|a, b| a.total_cmp(b));
+	let trim = (slice.len() as f64 * proportion) as usize;
+	arithmetic(&sorted[trim..slice.len() - trim])
+}
+
+/// Calculate arithmetic mean of data set `slice` if it is not empty.
+///
+/// Same as `arithmetic` but returns `None` if `slice` is empty, so that no data
+/// can be told apart from a `NAN` result.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::try_arithmetic(&[8., 16.]), Some(12.));
+/// assert_eq!(mean::try_arithmetic(&[]), None);
+/// ```
+pub fn try_arithmetic(slice: &[f64]) -> Option<f64> {
+	match slice.is_empty() {
+		true => None,
+		false => Some(arithmetic(slice)),
+	}
+}
+
+/// Calculate geometric mean of data set `slice` if it is not empty.
+///
+/// Same as `geometric` but returns `None` if `slice` is empty, see
+/// `try_arithmetic`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::try_geometric(&[9., 16.]), Some(12.));
+/// assert_eq!(mean::try_geometric(&[]), None);
+/// ```
+pub fn try_geometric(slice: &[f64]) -> Option<f64> {
+	match slice.is_empty() {
+		true => None,
+		false => Some(geometric(slice)),
+	}
+}
+
+/// Calculate harmonic mean of data set `slice` if it is not empty.
+///
+/// Same as `harmonic` but returns `None` if `slice` is empty, see
+/// `try_arithmetic`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// assert_eq!(mean::try_harmonic(&[1., 7.]), Some(1.75));
+/// assert_eq!(mean::try_harmonic(&[]), None);
+/// ```
+pub fn try_harmonic(slice: &[f64]) -> Option<f64> {
+	match slice.is_empty() {
+		true => None,
+		false => Some(harmonic(slice)),
+	}
+}
+
+/// Calculate weighted arithmetic mean of data set `values`.
+///
+/// Returns `Σ(w * x) / Σw` where `weights` holds the weight of each value.
+/// Returns `NAN` if the slices differ in length or the weights sum to zero.
+///
+/// # Arguments
+///
+/// * `values` - collection of values
+/// * `weights` - weight of each value
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let values = [80., 90.];
+/// let weights = [3., 1.];
+/// assert_eq!(mean::weighted_arithmetic(&values, &weights), 82.5);
+/// ```
+pub fn weighted_arithmetic(values: &[f64], weights: &[f64]) -> f64 {
+	let total = weights.iter().fold(0., |a, b| a + b);
+	match values.len() != weights.len() || total == 0. {
+		true => f64::NAN,
+		false => values.iter().zip(weights).fold(0., |a, (x, w)| a + w * x) / total,
+	}
+}
+
+/// Calculate weighted geometric mean of data set `values`.
+///
+/// Returns `exp(Σ(w * ln x) / Σw)` where `weights` holds the weight of each
+/// value. Returns `NAN` if the slices differ in length, the weights sum to
+/// zero or any value is not positive.
+///
+/// # Arguments
+///
+/// * `values` - collection of values
+/// * `weights` - weight of each value
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let values = [1., 8.];
+/// let weights = [2., 1.];
+/// assert_eq!(mean::weighted_geometric(&values, &weights), 2.);
+/// ```
+pub fn weighted_geometric(values: &[f64], weights: &[f64]) -> f64 {
+	match values.iter().all(|x| *x > 0.) {
+		true => weighted_arithmetic(&values.iter().map(|x| x.ln()).collect::<Vec<f64>>(), weights).exp(),
+		false => f64::NAN,
+	}
+}
+
+/// Calculate weighted harmonic mean of data set `values`.
+///
+/// Returns `Σw / Σ(w / x)` where `weights` holds the weight of each value.
+/// Returns `NAN` if the slices differ in length or the weights sum to zero.
+///
+/// # Arguments
+///
+/// * `values` - collection of values
+/// * `weights` - weight of each value
+///
+/// # Example
+///
+/// ```
+/// use math::mean;
+///
+/// let values = [60., 30.];
+/// let weights = [1., 2.];
+/// assert_eq!(mean::weighted_harmonic(&values, &weights), 36.);
+/// ```
+pub fn weighted_harmonic(values: &[f64], weights: &[f64]) -> f64 {
+	let total = weights.iter().fold(0., |a, b| a + b);
+	match values.len() != weights.len() || total == 0. {
+		true => f64::NAN,
+		false => total / values.iter().zip(weights).fold(0., |a, (x, w)| a + w / x),
+	}
+}
+
+/// Exponentially weighted moving average (EWMA).
+///
+/// The average is seeded with the first value pushed and updated with
+/// `ewma_update` for every value after that.
+///
+/// # Example
+///
+/// ```
+/// use math::mean::Ewma;
+///
+/// let mut ewma = Ewma::new(0.5);
+/// ewma.push(8.);
+/// ewma.push(16.);
+/// assert_eq!(ewma.value(), 12.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ewma {
+	alpha: f64,
+	value: Option<f64>,
+}
+
+impl Ewma {
+	/// Create average with smoothing factor `alpha`.
+	///
+	/// If `alpha` is outside of `(0, 1]`, the average is always `NAN`.
+	///
+	/// # Arguments
+	///
+	/// * `alpha` - smoothing factor
+	pub fn new(alpha: f64) -> Ewma {
+		Ewma { alpha, value: None }
+	}
+
+	/// Add `value` to the average.
+	///
+	/// # Arguments
+	///
+	/// * `value` - value to add
+	pub fn push(&mut self, value: f64) {
+		self.value = Some(match self.value {
+			Some(previous) => ewma_update(previous, value, self.alpha),
+			None => match self.alpha > 0. && self.alpha <= 1. {
+				true => value,
+				false => f64::NAN,
+			},
+		});
+	}
+
+	/// Current average, `NAN` if no values have been pushed.
+	pub fn value(&self) -> f64 {
+		self.value.unwrap_or(f64::NAN)
+	}
+}
+
+/// Running arithmetic mean.
+///
+/// Updates the mean with every value pushed using Welford's algorithm,
+/// which stays accurate for long streams and never holds a growing sum.
+///
+/// # Example
+///
+/// ```
+/// use math::mean::Running;
+///
+/// let mut running = Running::new();
+/// running.push(8.);
+/// running.push(16.);
+/// assert_eq!(running.count(), 2);
+/// assert_eq!(running.mean(), 12.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Running {
+	count: usize,
+	mean: f64,
+}
+
+impl Running {
+	/// Create empty mean.
+	pub fn new() -> Running {
+		Running { count: 0, mean: 0. }
+	}
+
+	/// Number of values pushed.
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	/// Current mean, `NAN` if no values have been pushed.
+	pub fn mean(&self) -> f64 {
+		match self.count {
+			0 => f64::NAN,
+			_ => self.mean,
+		}
+	}
+
+	/// Add `x` to the mean.
+	///
+	/// As with `arithmetic`, an infinite value makes the mean infinite and
+	/// infinities of both signs or a `NAN` make it `NAN`.
+	///
+	/// # Arguments
+	///
+	/// * `x` - value to add
+	pub fn push(&mut self, x: f64) {
+		self.count += 1;
+		let n = self.count as f64;
+		self.mean = match (self.mean.is_finite(), x.is_finite()) {
+			(true, true) => match (x - self.mean).is_finite() {
+				true => self.mean + (x - self.mean) / n,
+				// the difference of values of opposite sign can overflow
+				false => self.mean + (x / n - self.mean / n),
+			},
+			(true, false) => x,
+			(false, false) => self.mean + x,
+			(false, true) => self.mean,
+		};
+	}
+}
+
+/// Running geometric mean.
+///
+/// Accumulates the sum of the logarithms of the values pushed and takes
+/// the exponential of their mean when queried. Once a value which is not
+/// positive has been pushed the mean is `NAN`.
+///
+/// # Example
+///
+/// ```
+/// use math::mean::RunningGeometric;
+///
+/// let mut running = RunningGeometric::new();
+/// running.push(2.);
+/// running.push(8.);
+/// assert_eq!(running.count(), 2);
+/// assert_eq!(running.mean(), 4.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RunningGeometric {
+	count: usize,
+	log_sum: f64,
+}
+
+impl RunningGeometric {
+	/// Create empty mean.
+	pub fn new() -> RunningGeometric {
+		RunningGeometric { count: 0, log_sum: 0. }
+	}
+
+	/// Number of values pushed.
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	/// Current mean, `NAN` if no values have been pushed.
+	pub fn mean(&self) -> f64 {
+		match self.count {
+			0 => f64::NAN,
+			_ => (self.log_sum / self.count as f64).exp(),
+		}
+	}
+
+	/// Add `x` to the mean.
+	///
+	/// # Arguments
+	///
+	/// * `x` - value to add
+	pub fn push(&mut self, x: f64) {
+		self.count += 1;
+		self.log_sum += match x > 0. {
+			true => x.ln(),
+			false => f64::NAN,
+		};
+	}
+}
+
+/// Running harmonic mean.
+///
+/// Accumulates the sum of the reciprocals of the values pushed and divides
+/// the count by it when queried.
+///
+/// # Example
+///
+/// ```
+/// use math::mean::RunningHarmonic;
+///
+/// let mut running = RunningHarmonic::new();
+/// running.push(1.);
+/// running.push(4.);
+/// running.push(4.);
+/// assert_eq!(running.count(), 3);
+/// assert_eq!(running.mean(), 2.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RunningHarmonic {
+	count: usize,
+	reciprocal_sum: f64,
+}
+
+impl RunningHarmonic {
+	/// Create empty mean.
+	pub fn new() -> RunningHarmonic {
+		RunningHarmonic { count: 0, reciprocal_sum: 0. }
+	}
+
+	/// Number of values pushed.
+	pub fn count(&self) -> usize {
+		self.count
+	}
+
+	/// Current mean, `NAN` if no values have been pushed.
+	pub fn mean(&self) -> f64 {
+		match self.count {
+			0 => f64::NAN,
+			_ => self.count as f64 / self.reciprocal_sum,
+		}
+	}
+
+	/// Add `x` to the mean.
+	///
+	/// # Arguments
+	///
+	/// * `x` - value to add
+	pub fn push(&mut self, x: f64) {
+		self.count += 1;
+		self.reciprocal_sum += 1. / x;
+	}
+}
+
+fn nan_ignore(slice: &[f64], mean: fn(&[f64]) -> f64) -> f64 {
+	let present: Vec<f64> = slice.iter().cloned().filter(|x| !x.is_nan()).collect();
+	match present.is_empty() {
+		true => f64::NAN,
+		false => mean(&present),
+	}
+}
+
+fn sum(slice: &[f64]) -> f64 {
+	// Neumaier's variant of Kahan summation, which also keeps the low order
+	// bits of the running sum when a value larger than the sum is added
+	let mut sum = 0.;
+	let mut compensation = 0.;
+	for x in slice.iter() {
+		let t = sum + x;
+		compensation += match f64::abs(sum) >= f64::abs(*x) {
+			true => (sum - t) + x,
+			false => (x - t) + sum,
+		};
+		sum = t;
+	}
+	// the compensation of an infinite sum is NAN
+	match sum.is_finite() {
+		true => sum + compensation,
+		false => sum,
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use std::f64::{ NAN, INFINITY, NEG_INFINITY };
 	use round;
+	use stats;
 
 	macro_rules! test_mean {
 		($func:path [ $($name:ident: $params:expr,)* ]) => {
@@ -90,20 +898,32 @@ mod tests {
 		arithmetic_5: (&[1., 2., 6., 4., 13.], 5.2),
 		arithmetic_6: (&[1., 5., 10., 20., 25.], 12.2),
 		arithmetic_7: (&[2., 3., 5., 7., 11.], 5.6),
-		arithmetic_8: (&[NEG_INFINITY, 1., 2., 3., 4.], NEG_INFINITY),
-		arithmetic_9: (&[1., 2., 3., 4., INFINITY], INFINITY),
+		arithmetic_8: (&[f64::NEG_INFINITY, 1., 2., 3., 4.], f64::NEG_INFINITY),
+		arithmetic_9: (&[1., 2., 3., 4., f64::INFINITY], f64::INFINITY),
+	]}
+
+	test_mean! { super::contraharmonic [
+		contraharmonic_1: (&[-7., -4., 1., 3., 8.], 139.),
+		contraharmonic_2: (&[-4., 1., 3., 8., 12.], 11.7),
+		contraharmonic_3: (&[0., 0., 0., 0., 0.], f64::NAN),
+		contraharmonic_4: (&[0., 4., 7., 9., 17.], 11.756757),
+		contraharmonic_5: (&[1., 2., 6., 4., 13.], 8.692308),
+		contraharmonic_6: (&[1., 5., 10., 20., 25.], 18.868852),
+		contraharmonic_7: (&[2., 3., 5., 7., 11.], 7.428571),
+		contraharmonic_8: (&[-3., 1., 2.], f64::NAN),
+		contraharmonic_9: (&[], f64::NAN),
 	]}
 
 	test_mean! { super::geometric [
 		geometric_1: (&[-7., -4., 1., 3., 8.], 3.676833),
-		geometric_2: (&[-4., 1., 3., 8., 12.], NAN),
+		geometric_2: (&[-4., 1., 3., 8., 12.], f64::NAN),
 		geometric_3: (&[0., 0., 0., 0., 0.], 0.),
 		geometric_4: (&[0., 4., 7., 9., 17.], 0.),
 		geometric_5: (&[1., 2., 6., 4., 13.], 3.622738),
 		geometric_6: (&[1., 5., 10., 20., 25.], 7.578583),
 		geometric_7: (&[2., 3., 5., 7., 11.], 4.706764),
-		geometric_8: (&[NEG_INFINITY, 1., 2., 3., 4.], NAN),
-		geometric_9: (&[1., 2., 3., 4., INFINITY], INFINITY),
+		geometric_8: (&[f64::NEG_INFINITY, 1., 2., 3., 4.], f64::NAN),
+		geometric_9: (&[1., 2., 3., 4., f64::INFINITY], f64::INFINITY),
 	]}
 
 	test_mean! { super::harmonic [
@@ -114,7 +934,496 @@ mod tests {
 		harmonic_5: (&[1., 2., 6., 4., 13.], 2.508039),
 		harmonic_6: (&[1., 5., 10., 20., 25.], 3.597122),
 		harmonic_7: (&[2., 3., 5., 7., 11.], 3.94602),
-		harmonic_8: (&[NEG_INFINITY, 1., 2., 3., 4.], 2.4),
-		harmonic_9: (&[1., 2., 3., 4., INFINITY], 2.4),
+		harmonic_8: (&[f64::NEG_INFINITY, 1., 2., 3., 4.], 2.4),
+		harmonic_9: (&[1., 2., 3., 4., f64::INFINITY], 2.4),
 	]}
+
+	test_mean! { super::median [
+		median_1: (&[-7., 8., 1., -4., 3.], 1.),
+		median_2: (&[12., -4., 8., 1.], 4.5),
+		median_3: (&[0., 0., 0., 0., 0.], 0.),
+		median_4: (&[17.], 17.),
+		median_5: (&[], f64::NAN),
+		median_6: (&[1., f64::NAN, 2.], 2.),
+		median_7: (&[f64::NEG_INFINITY, 1., 2., 3.], 1.5),
+		median_8: (&[1., 2., 3., f64::INFINITY], 2.5),
+	]}
+
+	test_mean! { super::quadratic [
+		quadratic_1: (&[-7., -4., 1., 3., 8.], 5.272571),
+		quadratic_2: (&[-4., 1., 3., 8., 12.], 6.841053),
+		quadratic_3: (&[0., 0., 0., 0., 0.], 0.),
+		quadratic_4: (&[0., 4., 7., 9., 17.], 9.327379),
+		quadratic_5: (&[1., 2., 6., 4., 13.], 6.723095),
+		quadratic_6: (&[1., 5., 10., 20., 25.], 15.172343),
+		quadratic_7: (&[2., 3., 5., 7., 11.], 6.449806),
+		quadratic_8: (&[f64::NEG_INFINITY, 1., 2., 3., 4.], f64::INFINITY),
+		quadratic_9: (&[1., 2., 3., 4., f64::INFINITY], f64::INFINITY),
+		quadratic_10: (&[3., 4.], 3.535534),
+		quadratic_11: (&[-3., -4.], 3.535534),
+		quadratic_12: (&[], f64::NAN),
+	]}
+
+	#[test]
+	fn geometric_large_values() {
+		let slice: Vec<f64> = (0..1000).map(|i| 1e150 * (1. + (i % 10) as f64 / 10.)).collect();
+		let expected = 1e150 * (1..11).fold(1., |a, i| a * (0.9 + i as f64 / 10.)).powf(0.1);
+		assert!(slice.iter().fold(1., |a: f64, b| a * b).is_infinite());
+		assert!((super::geometric(&slice) / expected - 1.).abs() < 1e-12);
+	}
+
+	#[test]
+	fn geometric_small_values() {
+		let slice = vec![1e-200; 1000];
+		assert!((super::geometric(&slice) / 1e-200 - 1.).abs() < 1e-12);
+	}
+
+	#[test]
+	fn geometric_special() {
+		assert!(super::geometric(&[]).is_nan());
+		assert!(super::geometric(&[1., f64::NAN]).is_nan());
+		assert!(super::geometric(&[0., f64::INFINITY]).is_nan());
+		assert_eq!(super::geometric(&[-4., 0., 9.]), 0.);
+		assert_eq!(super::geometric(&[-4., -9.]), 6.);
+	}
+
+	#[test]
+	fn nan_ignore() {
+		let slice = [f64::NAN, 1., 2., f64::NAN, 6., 4., 13., f64::NAN];
+		let present = [1., 2., 6., 4., 13.];
+		assert_eq!(super::arithmetic_nan_ignore(&slice), super::arithmetic(&present));
+		assert_eq!(super::geometric_nan_ignore(&slice), super::geometric(&present));
+		assert_eq!(super::harmonic_nan_ignore(&slice), super::harmonic(&present));
+		assert!(super::arithmetic(&slice).is_nan());
+	}
+
+	#[test]
+	fn nan_ignore_infinity() {
+		let slice = [1., f64::NAN, f64::INFINITY];
+		assert_eq!(super::arithmetic_nan_ignore(&slice), f64::INFINITY);
+		assert_eq!(super::geometric_nan_ignore(&slice), f64::INFINITY);
+		assert_eq!(super::harmonic_nan_ignore(&slice), 2.);
+	}
+
+	#[test]
+	fn nan_ignore_empty() {
+		for slice in [&[][..], &[f64::NAN, f64::NAN][..]].iter() {
+			assert!(super::arithmetic_nan_ignore(slice).is_nan());
+			assert!(super::geometric_nan_ignore(slice).is_nan());
+			assert!(super::harmonic_nan_ignore(slice).is_nan());
+		}
+	}
+
+	#[test]
+	fn power_special_cases() {
+		let slices: [&[f64]; 5] = [
+			&[-7., -4., 1., 3., 8.],
+			&[0., 4., 7., 9., 17.],
+			&[1., 2., 6., 4., 13.],
+			&[2., 3., 5., 7., 11.],
+			&[1., 2., 3., 4., f64::INFINITY],
+		];
+		for slice in slices.iter() {
+			let power = |p| round::half_up(super::power(slice, p), 6);
+			assert_eq!(power(1.), round::half_up(super::arithmetic(slice), 6));
+			assert_eq!(power(-1.), round::half_up(super::harmonic(slice), 6));
+			assert_eq!(power(0.), round::half_up(super::geometric(slice), 6));
+			assert_eq!(power(2.), round::half_up(super::quadratic(slice), 6));
+		}
+	}
+
+	#[test]
+	fn power_limit() {
+		let slice = [1., 2., 6., 4., 13.];
+		let geometric = super::geometric(&slice);
+		assert!((super::power(&slice, 1e-9) - geometric).abs() < 1e-6);
+		assert!((super::power(&slice, -1e-9) - geometric).abs() < 1e-6);
+		assert!(super::power(&slice, 3.) > super::quadratic(&slice));
+	}
+
+	#[test]
+	fn power_empty() {
+		assert!(super::power(&[], 0.).is_nan());
+		assert!(super::power(&[], 2.).is_nan());
+	}
+
+	#[test]
+	fn trimmed() {
+		assert_eq!(super::trimmed(&[-7., -4., 1., 3., 8.], 0.), 0.2);
+		assert_eq!(super::trimmed(&[-7., -4., 1., 3., 8.], 0.2), 0.);
+		assert_eq!(super::trimmed(&[-7., -4., 1., 3., 8.], 0.4999), 1.);
+		assert_eq!(super::trimmed(&[5.], 0.25), 5.);
+	}
+
+	#[test]
+	fn trimmed_outlier() {
+		let mut slice = vec![10., 12., 9., 11., 10., 8., 12., 9., 11.];
+		slice.push(1e9);
+		assert!(super::arithmetic(&slice) > 1e8);
+		assert_eq!(super::trimmed(&slice, 0.1), 10.5);
+	}
+
+	#[test]
+	fn trimmed_invalid() {
+		assert!(super::trimmed(&[1., 2., 3.], 0.5).is_nan());
+		assert!(super::trimmed(&[1., 2., 3.], -0.1).is_nan());
+		assert!(super::trimmed(&[1., 2., 3.], f64::NAN).is_nan());
+		assert!(super::trimmed(&[], 0.1).is_nan());
+	}
+
+	#[test]
+	fn try_means() {
+		let slice = [1., 2., 6., 4., 13.];
+		assert_eq!(super::try_arithmetic(&slice), Some(super::arithmetic(&slice)));
+		assert_eq!(super::try_geometric(&slice), Some(super::geometric(&slice)));
+		assert_eq!(super::try_harmonic(&slice), Some(super::harmonic(&slice)));
+		assert_eq!(super::try_arithmetic(&[]), None);
+		assert_eq!(super::try_geometric(&[]), None);
+		assert_eq!(super::try_harmonic(&[]), None);
+	}
+
+	#[test]
+	fn try_means_nan() {
+		let slice = [1., f64::NAN];
+		assert!(super::try_arithmetic(&slice).unwrap().is_nan());
+		assert!(super::try_geometric(&slice).unwrap().is_nan());
+		assert!(super::try_harmonic(&slice).unwrap().is_nan());
+	}
+
+	#[test]
+	fn weighted_arithmetic_uniform() {
+		let slice = [-7., -4., 1., 3., 8.];
+		let weights = [2.; 5];
+		assert_eq!(super::weighted_arithmetic(&slice, &weights), super::arithmetic(&slice));
+	}
+
+	#[test]
+	fn weighted_arithmetic_skewed() {
+		let values = [70., 85., 95.];
+		assert_eq!(super::weighted_arithmetic(&values, &[0.2, 0.3, 0.5]), 87.);
+		assert_eq!(super::weighted_arithmetic(&values, &[0., 0., 4.]), 95.);
+		assert_eq!(super::weighted_arithmetic(&values, &[1., 1., -1.]), 60.);
+	}
+
+	#[test]
+	fn weighted_arithmetic_invalid() {
+		assert!(super::weighted_arithmetic(&[1., 2.], &[1.]).is_nan());
+		assert!(super::weighted_arithmetic(&[1., 2.], &[1., -1.]).is_nan());
+		assert!(super::weighted_arithmetic(&[], &[]).is_nan());
+	}
+
+	#[test]
+	fn mode_unimodal() {
+		assert_eq!(super::mode(&[2., 7., 2., 5., 2., 7.]), vec![2.]);
+		assert_eq!(super::mode(&[-1.5]), vec![-1.5]);
+	}
+
+	#[test]
+	fn mode_bimodal() {
+		assert_eq!(super::mode(&[4., -3., 9., 4., -3.]), vec![-3., 4.]);
+	}
+
+	#[test]
+	fn mode_all_unique() {
+		assert_eq!(super::mode(&[3., 1., 2.]), vec![1., 2., 3.]);
+		assert_eq!(super::mode(&[]), Vec::<f64>::new());
+	}
+
+	#[test]
+	fn mode_exact_equality() {
+		assert_eq!(super::mode(&[0.1 + 0.2, 0.3, 0.3]), vec![0.3]);
+		assert_eq!(super::mode(&[0., -0., 0.]), vec![0.]);
+		let modes = super::mode(&[f64::NAN, 1., f64::NAN]);
+		assert!(modes.len() == 1 && modes[0].is_nan());
+	}
+
+	#[test]
+	fn ewma_update() {
+		assert_eq!(super::ewma_update(10., 20., 0.25), 12.5);
+		assert_eq!(super::ewma_update(10., 20., 1.), 20.);
+		assert!(super::ewma_update(10., 20., 0.).is_nan());
+		assert!(super::ewma_update(10., 20., 1.5).is_nan());
+	}
+
+	#[test]
+	fn ewma_recurrence() {
+		let slice = [3., 5., 4., 8., 6.];
+		let mut ewma = super::Ewma::new(0.3);
+		for (x, expected) in slice.iter().zip(stats::ema(&slice, 0.3)) {
+			ewma.push(*x);
+			assert_eq!(ewma.value(), expected);
+		}
+	}
+
+	#[test]
+	fn ewma_seed() {
+		let mut ewma = super::Ewma::new(0.3);
+		ewma.push(3.);
+		assert_eq!(ewma.value(), 3.);
+		ewma.push(5.);
+		assert_eq!(ewma.value(), super::ewma_update(3., 5., 0.3));
+	}
+
+	#[test]
+	fn ewma_tracks_latest() {
+		let mut ewma = super::Ewma::new(1.);
+		assert!(ewma.value().is_nan());
+		for x in [3., -5., 4.].iter() {
+			ewma.push(*x);
+			assert_eq!(ewma.value(), *x);
+		}
+	}
+
+	#[test]
+	fn ewma_invalid_alpha() {
+		let mut ewma = super::Ewma::new(0.);
+		ewma.push(1.);
+		assert!(ewma.value().is_nan());
+		let mut ewma = super::Ewma::new(1.5);
+		ewma.push(1.);
+		ewma.push(2.);
+		assert!(ewma.value().is_nan());
+	}
+
+	#[test]
+	fn running() {
+		let slice: Vec<f64> = (0..100000).map(|i| ((i * 7919) % 1000) as f64 / 7. - 50.).collect();
+		let mut running = super::Running::new();
+		for x in slice.iter() {
+			running.push(*x);
+		}
+		assert_eq!(running.count(), slice.len());
+		assert!((running.mean() - super::arithmetic(&slice)).abs() < 1e-9);
+	}
+
+	#[test]
+	fn running_large_values() {
+		let mut running = super::Running::new();
+		for _ in 0..10 {
+			running.push(f64::MAX);
+		}
+		assert_eq!(running.mean(), f64::MAX);
+		let mut running = super::Running::new();
+		running.push(-f64::MAX);
+		running.push(f64::MAX);
+		assert_eq!(running.mean(), 0.);
+		running.push(f64::MAX);
+		assert_eq!(running.mean(), super::arithmetic(&[-f64::MAX, f64::MAX, f64::MAX]));
+	}
+
+	#[test]
+	fn running_not_finite() {
+		let mut running = super::Running::new();
+		running.push(1.);
+		running.push(f64::INFINITY);
+		running.push(2.);
+		assert_eq!(running.mean(), f64::INFINITY);
+		running.push(f64::INFINITY);
+		assert_eq!(running.mean(), f64::INFINITY);
+		running.push(f64::NEG_INFINITY);
+		assert!(running.mean().is_nan());
+		let mut running = super::Running::new();
+		running.push(f64::NEG_INFINITY);
+		running.push(-f64::MAX);
+		assert_eq!(running.mean(), f64::NEG_INFINITY);
+		let mut running = super::Running::new();
+		running.push(f64::NAN);
+		running.push(1.);
+		assert!(running.mean().is_nan());
+		assert_eq!(running.count(), 2);
+	}
+
+	#[test]
+	fn running_empty() {
+		let running = super::Running::new();
+		assert_eq!(running.count(), 0);
+		assert!(running.mean().is_nan());
+		assert_eq!(running, super::Running::default());
+	}
+
+	#[test]
+	fn arithmetic_compensated() {
+		let mut slice = vec![1e16];
+		slice.extend(vec![1.; 1000000]);
+		slice.push(-1e16);
+		let naive = slice.iter().fold(0., |a, b| a + b) / slice.len() as f64;
+		let expected = 1e6 / 1000002.;
+		assert_eq!(naive, 0.);
+		assert!((super::arithmetic(&slice) - expected).abs() < 1e-15);
+	}
+
+	#[test]
+	fn arithmetic_varying_magnitudes() {
+		let slice = [1e100, 1., -1e100, 1e-3, 3., 1e20, -1e20];
+		assert!((super::arithmetic(&slice) - 4.001 / 7.).abs() < 1e-15);
+	}
+
+	#[test]
+	fn contraharmonic_above_arithmetic() {
+		let slices: [&[f64]; 3] = [&[1., 5., 10., 20., 25.], &[2., 3., 5., 7., 11.], &[4., 4.]];
+		for slice in slices.iter() {
+			assert!(super::contraharmonic(slice) >= super::arithmetic(slice));
+		}
+	}
+
+	#[test]
+	fn weighted_geometric_uniform() {
+		let values = [1., 3., 9., 27.];
+		let weighted = super::weighted_geometric(&values, &[0.5, 0.5, 0.5, 0.5]);
+		assert_eq!(round::half_up(weighted, 12), round::half_up(super::geometric(&values), 12));
+		assert_eq!(round::half_up(super::weighted_geometric(&[4., 9.], &[1., 1.]), 12), 6.);
+	}
+
+	#[test]
+	fn weighted_geometric_invalid() {
+		assert!(super::weighted_geometric(&[1., 2.], &[1.]).is_nan());
+		assert!(super::weighted_geometric(&[1., 2.], &[1., -1.]).is_nan());
+		assert!(super::weighted_geometric(&[1., 0.], &[1., 1.]).is_nan());
+		assert!(super::weighted_geometric(&[-1., -4.], &[1., 1.]).is_nan());
+		assert!(super::weighted_geometric(&[], &[]).is_nan());
+	}
+
+	#[test]
+	fn weighted_harmonic_uniform() {
+		let values = [1., 2., 4., 5.];
+		assert_eq!(super::weighted_harmonic(&values, &[3., 3., 3., 3.]), super::harmonic(&values));
+		assert_eq!(super::weighted_harmonic(&[60., 30.], &[1., 2.]), 36.);
+	}
+
+	#[test]
+	fn weighted_harmonic_invalid() {
+		assert!(super::weighted_harmonic(&[1., 2.], &[1.]).is_nan());
+		assert!(super::weighted_harmonic(&[1., 2.], &[1., -1.]).is_nan());
+		assert!(super::weighted_harmonic(&[], &[]).is_nan());
+	}
+
+	#[test]
+	fn lehmer_special_cases() {
+		let slice = [1., 2., 4., 5., 8.];
+		assert_eq!(round::half_up(super::lehmer(&slice, 1.), 12), round::half_up(super::arithmetic(&slice), 12));
+		assert_eq!(round::half_up(super::lehmer(&slice, 0.), 12), round::half_up(super::harmonic(&slice), 12));
+		assert_eq!(round::half_up(super::lehmer(&slice, 2.), 12), round::half_up(super::contraharmonic(&slice), 12));
+		assert_eq!(round::half_up(super::lehmer(&[4., 9.], 0.5), 12), 6.);
+	}
+
+	#[test]
+	fn lehmer_invalid() {
+		assert!(super::lehmer(&[], 1.).is_nan());
+		assert!(super::lehmer(&[-1., 1.], 2.).is_nan());
+		assert!(super::lehmer(&[0., 0.], 3.).is_nan());
+	}
+
+	#[test]
+	fn midrange() {
+		assert_eq!(super::midrange(&[4.5]), 4.5);
+		assert_eq!(super::midrange(&[-8., 3., 2., -1.]), -2.5);
+		assert_eq!(super::midrange(&[-5., 5.]), 0.);
+		assert_eq!(super::midrange(&[1., 2., 3., 10.]), 5.5);
+		assert_eq!(super::midrange(&[f64::MAX, f64::MAX]), f64::MAX);
+		assert_eq!(super::midrange(&[f64::NEG_INFINITY, 1.]), f64::NEG_INFINITY);
+	}
+
+	#[test]
+	fn midrange_extremes() {
+		assert_eq!(super::midrange(&[5e-324]), 5e-324);
+		assert_eq!(super::midrange(&[5e-324, 1.5e-323]), 1e-323);
+		assert_eq!(super::midrange(&[f64::MAX, f64::MAX / 2.]), f64::MAX * 0.75);
+		assert_eq!(super::midrange(&[-f64::MAX, -f64::MAX]), -f64::MAX);
+		assert_eq!(super::midrange(&[-f64::MAX, f64::MAX]), 0.);
+	}
+
+	#[test]
+	fn midrange_invalid() {
+		assert!(super::midrange(&[]).is_nan());
+		assert!(super::midrange(&[1., f64::NAN, 3.]).is_nan());
+		assert!(super::midrange(&[f64::NEG_INFINITY, f64::INFINITY]).is_nan());
+	}
+
+	#[test]
+	fn heronian() {
+		assert_eq!(super::heronian(1., 4.), 7. / 3.);
+		assert_eq!(super::heronian(3., 3.), 3.);
+		assert_eq!(super::heronian(0., 6.), 2.);
+		let (a, b) = (2., 18.);
+		assert!(super::heronian(a, b) > super::geometric(&[a, b]));
+		assert!(super::heronian(a, b) < super::arithmetic(&[a, b]));
+		assert!(super::heronian(-1., 4.).is_nan());
+		assert!(super::heronian(-1., -4.).is_nan());
+	}
+
+	#[test]
+	fn heronian_wide_range() {
+		for &x in [1e-300, 1e-200, 1e200, 1e300, 5e-324, f64::MAX / 3.].iter() {
+			assert!((super::heronian(x, x) - x).abs() <= x * 1e-15, "{}", x);
+		}
+		assert_eq!(super::heronian(1e-200, 4e-200), 7e-200 / 3.);
+	}
+
+	#[test]
+	fn logarithmic() {
+		assert_eq!(super::logarithmic(2.5, 2.5), 2.5);
+		assert_eq!(super::logarithmic(1., 4.), super::logarithmic(4., 1.));
+		assert_eq!(round::half_up(super::logarithmic(1., 4.), 6), 2.164043);
+		for &(a, b) in [(1., 4.), (0.5, 0.6), (3., 300.), (1., 1. + 1e-9)].iter() {
+			let mean = super::logarithmic(a, b);
+			assert!(mean >= super::geometric(&[a, b]) - 1e-12);
+			assert!(mean <= super::arithmetic(&[a, b]) + 1e-12);
+		}
+	}
+
+	#[test]
+	fn logarithmic_wide_range() {
+		for &(a, b) in [(1e-200, 1e200), (1e200, 1e-200), (1e-300, 1e300), (5e-324, 1.)].iter() {
+			let mean = super::logarithmic(a, b);
+			assert!(mean > super::geometric(&[a, b]));
+			assert!(mean < super::arithmetic(&[a, b]));
+		}
+		let mean = super::logarithmic(1e-200, 1e200);
+		assert!((mean / (1e200 / (400. * std::f64::consts::LN_10)) - 1.).abs() < 1e-12);
+	}
+
+	#[test]
+	fn logarithmic_invalid() {
+		assert!(super::logarithmic(0., 4.).is_nan());
+		assert!(super::logarithmic(1., -4.).is_nan());
+		assert!(super::logarithmic(f64::NAN, 4.).is_nan());
+	}
+
+	#[test]
+	fn running_geometric() {
+		let slice = [1.5, 2.25, 9., 0.75, 4.2, 13.];
+		let mut running = super::RunningGeometric::new();
+		for x in slice.iter() {
+			running.push(*x);
+		}
+		assert_eq!(running.count(), 6);
+		assert_eq!(round::half_up(running.mean(), 12), round::half_up(super::geometric(&slice), 12));
+	}
+
+	#[test]
+	fn running_geometric_non_positive() {
+		let mut running = super::RunningGeometric::new();
+		assert!(running.mean().is_nan());
+		running.push(3.);
+		running.push(0.);
+		assert!(running.mean().is_nan());
+		running.push(3.);
+		assert!(running.mean().is_nan());
+		let mut running = super::RunningGeometric::new();
+		running.push(-2.);
+		running.push(-8.);
+		assert!(running.mean().is_nan());
+	}
+
+	#[test]
+	fn running_harmonic() {
+		let slice = [1.5, 2.25, 9., 0.75, 4.2, 13.];
+		let mut running = super::RunningHarmonic::new();
+		assert!(running.mean().is_nan());
+		for x in slice.iter() {
+			running.push(*x);
+		}
+		assert_eq!(running.count(), 6);
+		assert_eq!(round::half_up(running.mean(), 12), round::half_up(super::harmonic(&slice), 12));
+	}
 }
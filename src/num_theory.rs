@@ -0,0 +1,965 @@
+//! Number theory functions
+
+/// Classification of a number by its aliquot sum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Classification {
+	/// Aliquot sum equals the number, e.g. 6 = 1 + 2 + 3.
+	Perfect,
+	/// Aliquot sum exceeds the number, e.g. 12 < 1 + 2 + 3 + 4 + 6.
+	Abundant,
+	/// Aliquot sum is less than the number, e.g. every prime.
+	Deficient,
+}
+
+/// Calculate aliquot sequence starting at `n`.
+///
+/// Each term is the aliquot sum of the previous one. Iteration stops
+/// after `max_steps` steps, when the sequence reaches 0, or when a term
+/// repeats. The repeated term is included, so perfect numbers yield
+/// `[n, n]` and amicable pairs close their cycle.
+///
+/// # Arguments
+///
+/// * `n` - first term of the sequence
+/// * `max_steps` - maximum number of steps to take
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::aliquot_sequence(12, 20), vec![12, 16, 15, 9, 4, 3, 1, 0]);
+/// assert_eq!(num_theory::aliquot_sequence(220, 20), vec![220, 284, 220]);
+/// ```
+pub fn aliquot_sequence(n: u64, max_steps: usize) -> Vec<u64> {
+	let mut sequence = vec![n];
+	let mut current = n;
+	for _ in 0..max_steps {
+		if current == 0 {
+			break;
+		}
+		current = aliquot_sum(current);
+		let repeat = sequence.contains(&current);
+		sequence.push(current);
+		if repeat {
+			break;
+		}
+	}
+	sequence
+}
+
+/// Calculate aliquot sum of `n`.
+///
+/// The aliquot sum is the sum of all divisors of `n` except `n` itself.
+/// The sum saturates at `u64::MAX`.
+///
+/// # Arguments
+///
+/// * `n` - number to sum divisors of
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::aliquot_sum(12), 16);
+/// ```
+pub fn aliquot_sum(n: u64) -> u64 {
+	divisors(n).iter()
+		.filter(|&&d| d != n)
+		.fold(0, |a: u64, &d| a.saturating_add(d))
+}
+
+/// Classify `n` as perfect, abundant or deficient.
+///
+/// Compares `n` to its aliquot sum. Zero has no proper divisors and is
+/// classified as deficient.
+///
+/// # Arguments
+///
+/// * `n` - number to classify
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory::{ self, Classification };
+///
+/// assert_eq!(num_theory::classify(28), Classification::Perfect);
+/// assert_eq!(num_theory::classify(12), Classification::Abundant);
+/// ```
+pub fn classify(n: u64) -> Classification {
+	let sum = aliquot_sum(n);
+	match n {
+		0 => Classification::Deficient,
+		n if sum == n => Classification::Perfect,
+		n if sum > n => Classification::Abundant,
+		_ => Classification::Deficient,
+	}
+}
+
+/// Find all divisors of `n` in ascending order.
+///
+/// Returns an empty vector if `n` is zero.
+///
+/// # Arguments
+///
+/// * `n` - number to find divisors of
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::divisors(12), vec![1, 2, 3, 4, 6, 12]);
+/// ```
+pub fn divisors(n: u64) -> Vec<u64> {
+	let mut small = vec![];
+	let mut large = vec![];
+	let mut d = 1;
+	while n > 0 && d <= n / d {
+		if n.is_multiple_of(d) {
+			small.push(d);
+			if d != n / d {
+				large.push(n / d);
+			}
+		}
+		d += 1;
+	}
+	small.extend(large.iter().rev());
+	small
+}
+
+/// Factorize `n` into primes in ascending order.
+///
+/// Splits `n` with Pollard's rho algorithm until every factor passes a
+/// deterministic Miller-Rabin test, which handles numbers with large prime
+/// factors far beyond the reach of trial division in `prime_factors`.
+/// Returns an empty vector if `n` is 0 or 1.
+///
+/// # Arguments
+///
+/// * `n` - number to factorize
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::factorize(360), vec![2, 2, 2, 3, 3, 5]);
+/// assert_eq!(num_theory::factorize(1_000_000_016_000_000_063), vec![1_000_000_007, 1_000_000_009]);
+/// ```
+pub fn factorize(n: u64) -> Vec<u64> {
+	if n < 2 {
+		return vec![];
+	}
+	let mut factors = match pollard_rho(n) {
+		Some(d) => [factorize(d), factorize(n / d)].concat(),
+		None => vec![n],
+	};
+	factors.sort();
+	factors
+}
+
+/// Calculate Farey sequence of order `n`.
+///
+/// The sequence consists of all reduced fractions in `[0, 1]` with a
+/// denominator of at most `n` in ascending order, given as
+/// `(numerator, denominator)` pairs. Each term is computed from the two
+/// previous ones, so no sorting is needed.
+/// Returns an empty sequence if `n` is zero.
+///
+/// # Arguments
+///
+/// * `n` - order of the sequence
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::farey(3), vec![(0, 1), (1, 3), (1, 2), (2, 3), (1, 1)]);
+/// ```
+pub fn farey(n: u64) -> Vec<(u64, u64)> {
+	if n == 0 {
+		return vec![];
+	}
+	let (mut a, mut b, mut c, mut d) = (0, 1, 1, n);
+	let mut sequence = vec![(a, b)];
+	while c <= n {
+		let k = (n + b) / d;
+		let next = (k * c - a, k * d - b);
+		a = c;
+		b = d;
+		c = next.0;
+		d = next.1;
+		sequence.push((a, b));
+	}
+	sequence
+}
+
+/// Test whether `n` is a probable prime by Fermat's test.
+///
+/// `n` passes if `b^(n - 1) = 1 (mod n)` for every base `b` in `bases`.
+/// Bases divisible by `n` are skipped. Note that Carmichael numbers pass
+/// the test for every base coprime to them.
+///
+/// # Arguments
+///
+/// * `n` - number to test
+/// * `bases` - bases to test with
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::fermat_primality(97, &[2, 3, 5]), true);
+/// assert_eq!(num_theory::fermat_primality(91, &[2, 3, 5]), false);
+/// ```
+pub fn fermat_primality(n: u64, bases: &[u64]) -> bool {
+	match n {
+		0 | 1 => false,
+		2 | 3 => true,
+		_ => bases.iter()
+			.filter(|&&b| b % n != 0)
+			.all(|&b| mod_pow(b, n - 1, n) == 1),
+	}
+}
+
+/// Calculate greatest common divisor of `a` and `b`.
+///
+/// # Arguments
+///
+/// * `a` - first number
+/// * `b` - second number
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::gcd(12, 18), 6);
+/// ```
+pub fn gcd(a: u64, b: u64) -> u64 {
+	match b == 0 {
+		true => a,
+		false => gcd(b, a % b),
+	}
+}
+
+/// Test whether `n` is a Carmichael number.
+///
+/// Carmichael numbers are composite numbers that pass Fermat's test for
+/// every base coprime to them. They are detected with Korselt's criterion:
+/// `n` is square-free and `p - 1` divides `n - 1` for every prime factor `p`.
+///
+/// # Arguments
+///
+/// * `n` - number to test
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::is_carmichael(561), true);
+/// assert_eq!(num_theory::is_carmichael(563), false);
+/// ```
+pub fn is_carmichael(n: u64) -> bool {
+	let factors = prime_factors(n);
+	factors.len() > 1
+		&& factors.windows(2).all(|pair| pair[0] != pair[1])
+		&& factors.iter().all(|p| (n - 1).is_multiple_of(p - 1))
+}
+
+/// Test whether `n` is a power of two.
+///
+/// Zero is not a power of two.
+///
+/// # Arguments
+///
+/// * `n` - number to test
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::is_power_of_two(64), true);
+/// assert_eq!(num_theory::is_power_of_two(0), false);
+/// ```
+pub fn is_power_of_two(n: u64) -> bool {
+	n.is_power_of_two()
+}
+
+/// Calculate `base` raised to `exp` modulo `modulus`.
+///
+/// # Arguments
+///
+/// * `base` - base
+/// * `exp` - exponent
+/// * `modulus` - modulus
+///
+/// # Panics
+///
+/// Panics if `modulus` is zero.
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::mod_pow(2, 10, 1000), 24);
+/// ```
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+	assert!(modulus > 0, "modulus must be positive");
+	let m = modulus as u128;
+	let mut result = 1 % m;
+	let mut base = base as u128 % m;
+	let mut exp = exp;
+	while exp > 0 {
+		if exp & 1 == 1 {
+			result = result * base % m;
+		}
+		base = base * base % m;
+		exp >>= 1;
+	}
+	result as u64
+}
+
+/// Find smallest power of two greater than or equal to `n`.
+///
+/// Returns `Some(1)` for zero and `None` if the result would overflow
+/// `u64`, i.e. for `n` above `2^63`.
+///
+/// # Arguments
+///
+/// * `n` - lower bound
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::next_power_of_two(33), Some(64));
+/// assert_eq!(num_theory::next_power_of_two(u64::MAX), None);
+/// ```
+pub fn next_power_of_two(n: u64) -> Option<u64> {
+	n.checked_next_power_of_two()
+}
+
+/// Find non-trivial factor of `n` with Pollard's rho algorithm.
+///
+/// Iterates `x^2 + c` modulo `n` with Brent's cycle detection, retrying
+/// with another `c` if the cycle closes without a factor. The factor found
+/// is not necessarily prime.
+/// Returns `None` if `n` is 0, 1 or prime.
+///
+/// # Arguments
+///
+/// * `n` - number to factor
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// let d = num_theory::pollard_rho(8051).unwrap();
+/// assert!(d == 83 || d == 97);
+/// assert_eq!(num_theory::pollard_rho(97), None);
+/// ```
+pub fn pollard_rho(n: u64) -> Option<u64> {
+	if n < 4 || miller_rabin(n) {
+		return None;
+	}
+	if n.is_multiple_of(2) {
+		return Some(2);
+	}
+	let batch = 128;
+	for c in 1..n {
+		let f = |x: u64| ((mul_mod(x, x, n) as u128 + c as u128) % n as u128) as u64;
+		let (mut x, mut y, mut saved) = (2, 2, 2);
+		let (mut r, mut q, mut g) = (1, 1, 1);
+		while g == 1 {
+			x = y;
+			for _ in 0..r {
+				y = f(y);
+			}
+			let mut k = 0;
+			while k < r && g == 1 {
+				saved = y;
+				for _ in 0..batch.min(r - k) {
+					y = f(y);
+					q = mul_mod(q, x.abs_diff(y), n);
+				}
+				g = gcd(q, n);
+				k += batch;
+			}
+			r *= 2;
+		}
+		if g == n {
+			// the batch overshot, step through it one value at a time
+			loop {
+				saved = f(saved);
+				g = gcd(x.abs_diff(saved), n);
+				if g > 1 {
+					break;
+				}
+			}
+		}
+		if g != n {
+			return Some(g);
+		}
+	}
+	None
+}
+
+/// Count set bits of `n`.
+///
+/// # Arguments
+///
+/// * `n` - number to count bits of
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::popcount(0b1011), 3);
+/// ```
+pub fn popcount(n: u64) -> u32 {
+	n.count_ones()
+}
+
+/// Calculate power tower of `base` with `height` levels modulo `modulus`.
+///
+/// The tower `base^base^...^base` is reduced using Euler's theorem
+/// recursively over the totients of `modulus`, so the result is well-defined
+/// even when the tower itself is far too large to compute.
+/// A tower of height zero is 1.
+///
+/// # Arguments
+///
+/// * `base` - base of every level
+/// * `height` - number of levels
+/// * `modulus` - modulus
+///
+/// # Panics
+///
+/// Panics if `modulus` is zero.
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::power_tower_mod(2, 3, 100), 16);
+/// ```
+pub fn power_tower_mod(base: u64, height: u32, modulus: u64) -> u64 {
+	assert!(modulus > 0, "modulus must be positive");
+	match base {
+		0 => (1 - height as u64 % 2) % modulus,
+		1 => 1 % modulus,
+		_ => tower(base, height, modulus).0,
+	}
+}
+
+/// Calculate prime factors of `n`.
+///
+/// The factors are found by trial division and returned in ascending order
+/// with multiplicity. Zero and one have no prime factors.
+///
+/// # Arguments
+///
+/// * `n` - number to factor
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::prime_factors(360), vec![2, 2, 2, 3, 3, 5]);
+/// ```
+pub fn prime_factors(n: u64) -> Vec<u64> {
+	let mut factors = vec![];
+	let mut n = n;
+	let mut p = 2;
+	while n > 1 && p <= n / p {
+		while n.is_multiple_of(p) {
+			factors.push(p);
+			n /= p;
+		}
+		p += 1;
+	}
+	if n > 1 {
+		factors.push(n);
+	}
+	factors
+}
+
+/// Decompose `n` into a sum of two squares.
+///
+/// Returns `(a, b)` with `a * a + b * b == n` and `a <= b`, or `None` if no
+/// such decomposition exists, which is the case exactly when a prime
+/// congruent to 3 modulo 4 divides `n` an odd number of times.
+/// The smallest possible `a` is found by searching up to `sqrt(n / 2)`.
+///
+/// # Arguments
+///
+/// * `n` - number to decompose
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::sum_of_two_squares(25), Some((0, 5)));
+/// assert_eq!(num_theory::sum_of_two_squares(3), None);
+/// ```
+pub fn sum_of_two_squares(n: u64) -> Option<(u64, u64)> {
+	(0..=(n / 2).isqrt()).find_map(|a| {
+		let b = (n - a * a).isqrt();
+		match a * a + b * b == n {
+			true => Some((a, b)),
+			false => None,
+		}
+	})
+}
+
+/// Calculate Euler's totient of `n`.
+///
+/// The totient is the number of integers in `1..=n` coprime to `n`.
+///
+/// # Arguments
+///
+/// * `n` - number
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::totient(36), 12);
+/// ```
+pub fn totient(n: u64) -> u64 {
+	let mut result = n;
+	let mut n = n;
+	let mut p = 2;
+	while p <= n / p {
+		if n.is_multiple_of(p) {
+			while n.is_multiple_of(p) {
+				n /= p;
+			}
+			result -= result / p;
+		}
+		p += 1;
+	}
+	if n > 1 {
+		result -= result / n;
+	}
+	result
+}
+
+/// Calculate Euler's totient of every number in `0..=limit`.
+///
+/// Uses a sieve that starts from the identity and, for every prime `p`,
+/// multiplies the values at the multiples of `p` by `1 - 1 / p`. This is
+/// much faster than factoring every number separately. The totient of 0
+/// is given as 0.
+///
+/// # Arguments
+///
+/// * `limit` - largest number
+///
+/// # Example
+///
+/// ```
+/// use math::num_theory;
+///
+/// assert_eq!(num_theory::totient_up_to(6), vec![0, 1, 1, 2, 2, 4, 2]);
+/// ```
+pub fn totient_up_to(limit: u64) -> Vec<u64> {
+	let mut phi: Vec<u64> = (0..=limit).collect();
+	for p in 2..=limit as usize {
+		if phi[p] == p as u64 {
+			for multiple in (p..=limit as usize).step_by(p) {
+				phi[multiple] -= phi[multiple] / p as u64;
+			}
+		}
+	}
+	phi
+}
+
+fn miller_rabin(n: u64) -> bool {
+	// deterministic for all u64 with the first twelve primes as bases
+	let bases = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+	if n < 2 {
+		return false;
+	}
+	if let Some(&p) = bases.iter().find(|&&p| n.is_multiple_of(p)) {
+		return n == p;
+	}
+	let s = (n - 1).trailing_zeros();
+	let d = (n - 1) >> s;
+	bases.iter().all(|&a| {
+		let mut x = mod_pow(a, d, n);
+		if x == 1 || x == n - 1 {
+			return true;
+		}
+		for _ in 1..s {
+			x = mul_mod(x, x, n);
+			if x == n - 1 {
+				return true;
+			}
+		}
+		false
+	})
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+	(a as u128 * b as u128 % modulus as u128) as u64
+}
+
+fn tower(base: u64, height: u32, modulus: u64) -> (u64, bool) {
+	// returns the tower modulo `modulus` and whether the tower itself is at
+	// least `modulus`, in which case an exponent reduced modulo the totient
+	// has to be raised by the totient again
+	let exact = tower_saturating(base, height, modulus);
+	if exact < modulus || height == 0 {
+		return (exact % modulus, exact >= modulus);
+	}
+	let phi = totient(modulus);
+	let (exp, large) = tower(base, height - 1, phi);
+	match large {
+		true => (mul_mod(mod_pow(base, exp, modulus), mod_pow(base, phi, modulus), modulus), true),
+		false => (mod_pow(base, exp, modulus), true),
+	}
+}
+
+fn tower_saturating(base: u64, height: u32, limit: u64) -> u64 {
+	let mut value = 1u64;
+	for _ in 0..height {
+		value = match value < 64 {
+			true => base.checked_pow(value as u32).unwrap_or(limit).min(limit),
+			false => limit,
+		};
+	}
+	value
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Classification;
+
+	#[test]
+	fn aliquot_sequence() {
+		assert_eq!(super::aliquot_sequence(12, 20), vec![12, 16, 15, 9, 4, 3, 1, 0]);
+		assert_eq!(super::aliquot_sequence(12, 3), vec![12, 16, 15, 9]);
+		assert_eq!(super::aliquot_sequence(7, 0), vec![7]);
+		assert_eq!(super::aliquot_sequence(0, 5), vec![0]);
+	}
+
+	#[test]
+	fn aliquot_sequence_cycles() {
+		for &n in [6, 28, 496].iter() {
+			let sequence = super::aliquot_sequence(n, 10);
+			assert_eq!(sequence, vec![n, n]);
+		}
+		assert_eq!(super::aliquot_sequence(220, 10), vec![220, 284, 220]);
+		// sociable numbers of period 5
+		assert_eq!(super::aliquot_sequence(12496, 10), vec![12496, 14288, 15472, 14536, 14264, 12496]);
+	}
+
+	#[test]
+	fn aliquot_sum() {
+		assert_eq!(super::aliquot_sum(0), 0);
+		assert_eq!(super::aliquot_sum(1), 0);
+		assert_eq!(super::aliquot_sum(13), 1);
+		assert_eq!(super::aliquot_sum(12), 16);
+		assert_eq!(super::aliquot_sum(220), 284);
+		assert_eq!(super::aliquot_sum(284), 220);
+	}
+
+	#[test]
+	fn classify() {
+		assert_eq!(super::classify(6), Classification::Perfect);
+		assert_eq!(super::classify(28), Classification::Perfect);
+		assert_eq!(super::classify(8128), Classification::Perfect);
+		assert_eq!(super::classify(12), Classification::Abundant);
+		assert_eq!(super::classify(945), Classification::Abundant);
+		assert_eq!(super::classify(13), Classification::Deficient);
+		assert_eq!(super::classify(1), Classification::Deficient);
+		assert_eq!(super::classify(0), Classification::Deficient);
+	}
+
+	#[test]
+	fn divisors() {
+		assert_eq!(super::divisors(0), vec![]);
+		assert_eq!(super::divisors(1), vec![1]);
+		assert_eq!(super::divisors(13), vec![1, 13]);
+		assert_eq!(super::divisors(36), vec![1, 2, 3, 4, 6, 9, 12, 18, 36]);
+		for n in 1..200 {
+			let expected: Vec<u64> = (1..=n).filter(|d| n % d == 0).collect();
+			assert_eq!(super::divisors(n), expected);
+		}
+	}
+
+	#[test]
+	fn farey() {
+		assert_eq!(super::farey(0), vec![]);
+		assert_eq!(super::farey(1), vec![(0, 1), (1, 1)]);
+		assert_eq!(super::farey(3), vec![(0, 1), (1, 3), (1, 2), (2, 3), (1, 1)]);
+	}
+
+	#[test]
+	fn farey_length() {
+		for n in 1..50 {
+			let length = (1..=n).fold(1, |a, k| a + super::totient(k));
+			assert_eq!(super::farey(n).len() as u64, length);
+		}
+	}
+
+	#[test]
+	fn farey_reduced_and_ascending() {
+		let sequence = super::farey(20);
+		for &(a, b) in sequence.iter() {
+			assert_eq!(super::gcd(a, b), 1);
+			assert!(a <= b && b <= 20);
+		}
+		for pair in sequence.windows(2) {
+			assert!(pair[0].0 * pair[1].1 < pair[1].0 * pair[0].1);
+		}
+	}
+
+	#[test]
+	fn fermat_primality() {
+		for p in [2, 3, 5, 7, 97, 7919, 1_000_000_007].iter() {
+			assert!(super::fermat_primality(*p, &[2, 3, 5, 7]));
+		}
+		for n in [0, 1, 4, 91, 341 * 3, 1_000_000].iter() {
+			assert!(!super::fermat_primality(*n, &[2, 3, 5, 7]));
+		}
+	}
+
+	#[test]
+	fn fermat_primality_carmichael() {
+		let coprime: Vec<u64> = (2..561).filter(|b| super::gcd(*b, 561) == 1).collect();
+		assert!(super::fermat_primality(561, &coprime));
+		assert!(!super::fermat_primality(561, &[3]));
+	}
+
+	#[test]
+	fn is_carmichael() {
+		let expected = [561, 1105, 1729, 2465, 2821, 6601, 8911];
+		let found: Vec<u64> = (0..10000).filter(|n| super::is_carmichael(*n)).collect();
+		assert_eq!(found, expected.to_vec());
+	}
+
+	#[test]
+	fn gcd() {
+		assert_eq!(super::gcd(12, 18), 6);
+		assert_eq!(super::gcd(17, 5), 1);
+		assert_eq!(super::gcd(0, 7), 7);
+		assert_eq!(super::gcd(7, 0), 7);
+	}
+
+	#[test]
+	fn mod_pow() {
+		assert_eq!(super::mod_pow(2, 10, 1000), 24);
+		assert_eq!(super::mod_pow(3, 0, 7), 1);
+		assert_eq!(super::mod_pow(3, 0, 1), 0);
+		assert_eq!(super::mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+	}
+
+	#[test]
+	fn prime_factors() {
+		assert_eq!(super::prime_factors(0), vec![]);
+		assert_eq!(super::prime_factors(1), vec![]);
+		assert_eq!(super::prime_factors(2), vec![2]);
+		assert_eq!(super::prime_factors(360), vec![2, 2, 2, 3, 3, 5]);
+		assert_eq!(super::prime_factors(561), vec![3, 11, 17]);
+		assert_eq!(super::prime_factors(1_000_000_007), vec![1_000_000_007]);
+	}
+
+	#[test]
+	fn sum_of_two_squares() {
+		assert_eq!(super::sum_of_two_squares(0), Some((0, 0)));
+		assert_eq!(super::sum_of_two_squares(1), Some((0, 1)));
+		assert_eq!(super::sum_of_two_squares(2), Some((1, 1)));
+		assert_eq!(super::sum_of_two_squares(25), Some((0, 5)));
+		assert_eq!(super::sum_of_two_squares(65), Some((1, 8)));
+		assert_eq!(super::sum_of_two_squares(3), None);
+		assert_eq!(super::sum_of_two_squares(21), None);
+	}
+
+	#[test]
+	fn sum_of_two_squares_criterion() {
+		for n in 0..2000u64 {
+			let mut factors = super::prime_factors(n);
+			factors.dedup();
+			let representable = factors.iter()
+				.filter(|p| *p % 4 == 3)
+				.all(|p| super::prime_factors(n).iter().filter(|q| *q == p).count() % 2 == 0);
+			match super::sum_of_two_squares(n) {
+				Some((a, b)) => {
+					assert!(representable);
+					assert!(a <= b);
+					assert_eq!(a * a + b * b, n);
+				},
+				None => assert!(!representable),
+			}
+		}
+	}
+
+	#[test]
+	fn totient() {
+		let expected = [0, 1, 1, 2, 2, 4, 2, 6, 4, 6, 4, 10, 4, 12];
+		for (n, phi) in expected.iter().enumerate() {
+			assert_eq!(super::totient(n as u64), *phi);
+		}
+		assert_eq!(super::totient(97), 96);
+	}
+
+	#[test]
+	fn totient_up_to() {
+		assert_eq!(super::totient_up_to(0), vec![0]);
+		assert_eq!(super::totient_up_to(1), vec![0, 1]);
+		let phi = super::totient_up_to(500);
+		assert_eq!(phi.len(), 501);
+		for (n, value) in phi.iter().enumerate() {
+			assert_eq!(*value, super::totient(n as u64));
+		}
+		for &p in [2, 3, 5, 7, 11, 13, 499].iter() {
+			assert_eq!(phi[p], p as u64 - 1);
+		}
+	}
+
+	#[test]
+	fn totient_up_to_large() {
+		let phi = super::totient_up_to(1_000_000);
+		assert_eq!(phi[999_983], 999_982);
+		assert_eq!(phi[1_000_000], super::totient(1_000_000));
+		assert_eq!(phi[720_720], super::totient(720_720));
+	}
+
+	#[test]
+	fn power_tower_mod_small() {
+		assert_eq!(super::power_tower_mod(2, 3, 100), 16);
+		for m in 1..500 {
+			assert_eq!(super::power_tower_mod(2, 4, m), 65536 % m);
+			assert_eq!(super::power_tower_mod(3, 3, m), super::mod_pow(3, 27, m));
+			assert_eq!(super::power_tower_mod(10, 2, m), super::mod_pow(10, 10, m));
+		}
+	}
+
+	#[test]
+	fn power_tower_mod_base_cases() {
+		assert_eq!(super::power_tower_mod(7, 0, 5), 1);
+		assert_eq!(super::power_tower_mod(7, 0, 1), 0);
+		assert_eq!(super::power_tower_mod(7, 5, 1), 0);
+		assert_eq!(super::power_tower_mod(1, 9, 5), 1);
+		assert_eq!(super::power_tower_mod(0, 1, 5), 0);
+		assert_eq!(super::power_tower_mod(0, 2, 5), 1);
+	}
+
+	#[test]
+	fn power_tower_mod_deep() {
+		// last ten digits of Graham's number
+		assert_eq!(super::power_tower_mod(3, 30, 10_000_000_000), 2464195387);
+		assert_eq!(super::power_tower_mod(3, 31, 10_000_000_000), 2464195387);
+	}
+
+	#[test]
+	fn power_tower_mod_large_modulus() {
+		let m = u64::MAX - 10;
+		assert_eq!(super::power_tower_mod(3, 2, m), 27);
+		assert_eq!(super::power_tower_mod(3, 3, m), super::mod_pow(3, 27, m));
+		assert_eq!(super::power_tower_mod(2, 4, m), 65536);
+		assert_eq!(super::power_tower_mod(2, 5, m), super::mod_pow(2, 65536, m));
+		assert_eq!(super::power_tower_mod(3, 5, m), 9462301436654061757);
+		// the tower stops changing once it is taller than the totient chain
+		assert_eq!(super::power_tower_mod(3, 80, m), super::power_tower_mod(3, 81, m));
+		let m = u64::MAX / 2 + 2;
+		assert_eq!(super::power_tower_mod(3, 3, m), super::mod_pow(3, 27, m));
+	}
+
+	#[test]
+	fn is_power_of_two() {
+		assert!(!super::is_power_of_two(0));
+		assert!(super::is_power_of_two(1));
+		assert!(super::is_power_of_two(2));
+		assert!(!super::is_power_of_two(3));
+		assert!(super::is_power_of_two(1 << 40));
+		assert!(!super::is_power_of_two((1 << 40) + 1));
+		assert!(super::is_power_of_two(1 << 63));
+		assert!(!super::is_power_of_two(u64::MAX));
+	}
+
+	#[test]
+	fn next_power_of_two() {
+		assert_eq!(super::next_power_of_two(0), Some(1));
+		assert_eq!(super::next_power_of_two(1), Some(1));
+		assert_eq!(super::next_power_of_two(2), Some(2));
+		assert_eq!(super::next_power_of_two(3), Some(4));
+		assert_eq!(super::next_power_of_two(1024), Some(1024));
+		assert_eq!(super::next_power_of_two(1025), Some(2048));
+		assert_eq!(super::next_power_of_two(1 << 63), Some(1 << 63));
+		assert_eq!(super::next_power_of_two((1 << 63) + 1), None);
+		assert_eq!(super::next_power_of_two(u64::MAX), None);
+	}
+
+	#[test]
+	fn popcount() {
+		assert_eq!(super::popcount(0), 0);
+		assert_eq!(super::popcount(1), 1);
+		assert_eq!(super::popcount(1 << 63), 1);
+		assert_eq!(super::popcount((1 << 20) + 1), 2);
+		assert_eq!(super::popcount(0b1011), 3);
+		assert_eq!(super::popcount(u64::MAX), 64);
+	}
+
+	#[test]
+	fn factorize() {
+		assert_eq!(super::factorize(0), vec![]);
+		assert_eq!(super::factorize(1), vec![]);
+		for n in 2..3000 {
+			assert_eq!(super::factorize(n), super::prime_factors(n));
+		}
+	}
+
+	#[test]
+	fn factorize_large() {
+		let p = 4_294_967_291;
+		let q = 4_294_967_279;
+		assert_eq!(super::factorize(p * q), vec![q, p]);
+		assert_eq!(super::factorize(1_000_000_007 * 1_000_000_007), vec![1_000_000_007, 1_000_000_007]);
+		assert_eq!(super::factorize(18_446_744_073_709_551_557), vec![18_446_744_073_709_551_557]);
+		assert_eq!(super::factorize(u64::MAX), vec![3, 5, 17, 257, 641, 65_537, 6_700_417]);
+		assert_eq!(super::factorize(1 << 63), vec![2; 63]);
+	}
+
+	#[test]
+	fn pollard_rho() {
+		let n = 1_000_000_007 * 998_244_353;
+		let d = super::pollard_rho(n).unwrap();
+		assert!(d == 1_000_000_007 || d == 998_244_353);
+		for n in 4..2000u64 {
+			match super::prime_factors(n).len() > 1 {
+				true => {
+					let d = super::pollard_rho(n).unwrap();
+					assert!(d > 1 && d < n && n % d == 0);
+				},
+				false => assert_eq!(super::pollard_rho(n), None),
+			}
+		}
+	}
+
+	#[test]
+	fn pollard_rho_trivial() {
+		assert_eq!(super::pollard_rho(0), None);
+		assert_eq!(super::pollard_rho(1), None);
+		assert_eq!(super::pollard_rho(2), None);
+		assert_eq!(super::pollard_rho(1_000_000_007), None);
+		assert_eq!(super::pollard_rho(18_446_744_073_709_551_557), None);
+	}
+}
@@ -1,6 +1,97 @@
 //! Rounding functions
+//!
+//! Values are scaled by a power of ten before they are rounded. If `scale`
+//! asks for more digits than the float type can hold, e.g. a scale of 19 for
+//! `1.23`, the value is returned unchanged.
+#[cfg(not(feature = "std"))]
+extern crate libm;
+#[cfg(feature = "std")]
 extern crate rand;
 
+#[cfg(feature = "std")]
+use matrix::Matrix;
+#[cfg(feature = "serde")]
+use serde::{ Deserialize, Serialize };
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{ Add, Div, Mul, Rem, Sub };
+use std::str::FromStr;
+
+#[cfg(feature = "std")]
+pub mod exact;
+
+/// Rounding mode.
+///
+/// Selects one of the rounding functions of this module at runtime.
+/// The mode is displayed and parsed as the kebab case name of its rounding
+/// function, e.g. `"half-up"`, and `"half-even"` and `"half-odd"` are
+/// parsed as well. With the `serde` feature the mode is
+/// serialized as the snake case name of its rounding function, e.g.
+/// `"half_up"`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RoundingMode {
+	/// Round up, see `ceil`.
+	Ceil,
+	/// Round down, see `floor`.
+	Floor,
+	/// Round half up, see `half_up`.
+	HalfUp,
+	/// Round half down, see `half_down`.
+	HalfDown,
+	/// Round half away from zero, see `half_away_from_zero`.
+	HalfAwayFromZero,
+	/// Round half towards zero, see `half_towards_zero`.
+	HalfTowardsZero,
+	/// Round half to nearest even number, see `half_to_even`.
+	HalfToEven,
+	/// Round half to nearest odd number, see `half_to_odd`.
+	HalfToOdd,
+	/// Round half randomly up or down, see `stochastic`. Without the `std`
+	/// feature there is no random source and ties are rounded to even.
+	Stochastic,
+}
+
+impl fmt::Display for RoundingMode {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match *self {
+			RoundingMode::Ceil => "ceil",
+			RoundingMode::Floor => "floor",
+			RoundingMode::HalfUp => "half-up",
+			RoundingMode::HalfDown => "half-down",
+			RoundingMode::HalfAwayFromZero => "half-away-from-zero",
+			RoundingMode::HalfTowardsZero => "half-towards-zero",
+			RoundingMode::HalfToEven => "half-to-even",
+			RoundingMode::HalfToOdd => "half-to-odd",
+			RoundingMode::Stochastic => "stochastic",
+		})
+	}
+}
+
+impl FromStr for RoundingMode {
+	type Err = ParseRoundingModeError;
+
+	fn from_str(s: &str) -> Result<RoundingMode, ParseRoundingModeError> {
+		match s {
+			"" => Err(ParseRoundingModeError::Empty),
+			"ceil" => Ok(RoundingMode::Ceil),
+			"floor" => Ok(RoundingMode::Floor),
+			"half-up" => Ok(RoundingMode::HalfUp),
+			"half-down" => Ok(RoundingMode::HalfDown),
+			"half-away-from-zero" => Ok(RoundingMode::HalfAwayFromZero),
+			"half-towards-zero" => Ok(RoundingMode::HalfTowardsZero),
+			"half-to-even" | "half-even" => Ok(RoundingMode::HalfToEven),
+			"half-to-odd" | "half-odd" => Ok(RoundingMode::HalfToOdd),
+			"stochastic" => Ok(RoundingMode::Stochastic),
+			#[cfg(feature = "std")]
+			_ => Err(ParseRoundingModeError::Unknown(s.to_string())),
+			#[cfg(not(feature = "std"))]
+			_ => Err(ParseRoundingModeError::Unknown),
+		}
+	}
+}
+
 /// Round up.
 ///
 /// Round `value` up to accuracy defined by `scale`.
@@ -29,8 +120,97 @@ extern crate rand;
 /// assert_eq!(rounded, 3500.);
 /// ```
 pub fn ceil(value: f64, scale: i8) -> f64 {
-	let multiplier = 10f64.powi(scale as i32) as f64;
-	(value * multiplier).ceil() / multiplier
+	Round::ceil(value, scale)
+}
+
+/// Round timestamp up to bucket.
+///
+/// Returns the smallest multiple of `bucket_seconds` not less than
+/// `timestamp`. Works for timestamps before the epoch as well. Saturates
+/// at `i64::MAX` if that multiple is not representable.
+///
+/// # Arguments
+///
+/// * `timestamp` - Unix timestamp in seconds
+/// * `bucket_seconds` - bucket size in seconds
+///
+/// # Panics
+///
+/// Panics if `bucket_seconds` is not positive.
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::ceil_to_bucket(1000, 60), 1020);
+/// ```
+pub fn ceil_to_bucket(timestamp: i64, bucket_seconds: i64) -> i64 {
+	assert!(bucket_seconds > 0, "bucket size must be positive");
+	let remainder = timestamp.rem_euclid(bucket_seconds);
+	match remainder == 0 {
+		true => timestamp,
+		false => timestamp.saturating_add(bucket_seconds - remainder),
+	}
+}
+
+/// Clamp and round.
+///
+/// Clamp `value` to the range `[min, max]` and round the clamped value to
+/// accuracy defined by `scale` using rounding mode `mode`. As the value is
+/// rounded last, the result can lie outside the range if `min` or `max`
+/// has more digits than `scale` keeps.
+/// Returns `NAN` if `min` is greater than `max` or either bound is `NAN`.
+///
+/// # Arguments
+///
+/// * `value` - value to clamp and round
+/// * `min` - lower bound
+/// * `max` - upper bound
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// assert_eq!(round::clamp_then_round(1.257, 0., 1., 2, RoundingMode::HalfUp), 1.);
+/// assert_eq!(round::clamp_then_round(0.257, 0., 1., 2, RoundingMode::HalfUp), 0.26);
+/// ```
+pub fn clamp_then_round(value: f64, min: f64, max: f64, scale: i8, mode: RoundingMode) -> f64 {
+	// f64::clamp panics on these bounds
+	match min > max || min.is_nan() || max.is_nan() {
+		true => f64::NAN,
+		false => round_with(value.clamp(min, max), scale, mode),
+	}
+}
+
+/// Compare two rounding modes.
+///
+/// Round `value` to accuracy defined by `scale` using both rounding modes
+/// `a` and `b`. Returns both rounded values and whether they differ.
+/// Two `NAN` results are not considered different.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+/// * `a` - first rounding mode
+/// * `b` - second rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let result = round::compare_modes(2.5, 0, RoundingMode::HalfUp, RoundingMode::HalfToEven);
+/// assert_eq!(result, (3., 2., true));
+/// ```
+pub fn compare_modes(value: f64, scale: i8, a: RoundingMode, b: RoundingMode) -> (f64, f64, bool) {
+	let x = round_with(value, scale, a);
+	let y = round_with(value, scale, b);
+	(x, y, !(x == y || (x.is_nan() && y.is_nan())))
 }
 
 /// Round down.
@@ -61,8 +241,63 @@ pub fn ceil(value: f64, scale: i8) -> f64 {
 /// assert_eq!(rounded, 3400.);
 /// ```
 pub fn floor(value: f64, scale: i8) -> f64 {
-	let multiplier = 10f64.powi(scale as i32) as f64;
-	(value * multiplier).floor() / multiplier
+	Round::floor(value, scale)
+}
+
+/// Round timestamp down to bucket.
+///
+/// Returns the largest multiple of `bucket_seconds` not exceeding
+/// `timestamp`. Uses floor division, so timestamps before the epoch are
+/// rounded down as well rather than towards zero. Saturates at `i64::MIN`
+/// if that multiple is not representable.
+///
+/// # Arguments
+///
+/// * `timestamp` - Unix timestamp in seconds
+/// * `bucket_seconds` - bucket size in seconds
+///
+/// # Panics
+///
+/// Panics if `bucket_seconds` is not positive.
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::floor_to_bucket(1000, 60), 960);
+/// assert_eq!(round::floor_to_bucket(-1000, 60), -1020);
+/// ```
+pub fn floor_to_bucket(timestamp: i64, bucket_seconds: i64) -> i64 {
+	assert!(bucket_seconds > 0, "bucket size must be positive");
+	timestamp.saturating_sub(timestamp.rem_euclid(bucket_seconds))
+}
+
+/// Round to fraction.
+///
+/// Round `value` to a multiple of `1 / denominator` with rounding mode
+/// `mode`, e.g. to the nearest eighth.
+/// If `denominator` is zero, function returns `NAN`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `denominator` - denominator of the fraction
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// assert_eq!(round::fraction(0.33, 8, RoundingMode::HalfUp), 0.375);
+/// assert_eq!(round::fraction(0.3, 8, RoundingMode::HalfUp), 0.25);
+/// ```
+pub fn fraction(value: f64, denominator: u32, mode: RoundingMode) -> f64 {
+	match denominator {
+		0 => f64::NAN,
+		_ => round_with(value * denominator as f64, 0, mode) / denominator as f64,
+	}
 }
 
 /// Round half away from zero.
@@ -94,7 +329,7 @@ pub fn floor(value: f64, scale: i8) -> f64 {
 /// assert_eq!(rounded, 3500.);
 /// ```
 pub fn half_away_from_zero(value: f64, scale: i8) -> f64 {
-	towards_zero(value, scale, false)
+	Round::half_away_from_zero(value, scale)
 }
 
 /// Round half down.
@@ -116,17 +351,17 @@ pub fn half_away_from_zero(value: f64, scale: i8) -> f64 {
 /// use math::round;
 ///
 /// let rounded = round::half_down(3.14159, 3);
-/// assert_eq!(rounded, 3.141);
+/// assert_eq!(rounded, 3.142);
 /// ```
 ///
 /// ```
 /// use math::round;
 ///
 /// let rounded = round::half_down(3456., -2);
-/// assert_eq!(rounded, 3400.);
+/// assert_eq!(rounded, 3500.);
 /// ```
 pub fn half_down(value: f64, scale: i8) -> f64 {
-	up_or_down(value, scale, false)
+	Round::half_down(value, scale)
 }
 
 /// Round half to nearest even number.
@@ -155,10 +390,10 @@ pub fn half_down(value: f64, scale: i8) -> f64 {
 /// use math::round;
 ///
 /// let rounded = round::half_to_even(3456., -2);
-/// assert_eq!(rounded, 3400.);
+/// assert_eq!(rounded, 3500.);
 /// ```
 pub fn half_to_even(value: f64, scale: i8) -> f64 {
-	even_or_odd(value, scale, true)
+	Round::half_to_even(value, scale)
 }
 
 /// Round half to nearest odd number.
@@ -180,7 +415,7 @@ pub fn half_to_even(value: f64, scale: i8) -> f64 {
 /// use math::round;
 ///
 /// let rounded = round::half_to_odd(3.14159, 3);
-/// assert_eq!(rounded, 3.141);
+/// assert_eq!(rounded, 3.142);
 /// ```
 ///
 /// ```
@@ -190,7 +425,7 @@ pub fn half_to_even(value: f64, scale: i8) -> f64 {
 /// assert_eq!(rounded, 3500.);
 /// ```
 pub fn half_to_odd(value: f64, scale: i8) -> f64 {
-	even_or_odd(value, scale, false)
+	Round::half_to_odd(value, scale)
 }
 
 /// Round half towards zero.
@@ -212,17 +447,17 @@ pub fn half_to_odd(value: f64, scale: i8) -> f64 {
 /// use math::round;
 ///
 /// let rounded = round::half_towards_zero(3.14159, 3);
-/// assert_eq!(rounded, 3.141);
+/// assert_eq!(rounded, 3.142);
 /// ```
 ///
 /// ```
 /// use math::round;
 ///
 /// let rounded = round::half_towards_zero(3456., -2);
-/// assert_eq!(rounded, 3400.);
+/// assert_eq!(rounded, 3500.);
 /// ```
 pub fn half_towards_zero(value: f64, scale: i8) -> f64 {
-	towards_zero(value, scale, true)
+	Round::half_towards_zero(value, scale)
 }
 
 /// Round half up.
@@ -254,101 +489,1370 @@ pub fn half_towards_zero(value: f64, scale: i8) -> f64 {
 /// assert_eq!(rounded, 3500.);
 /// ```
 pub fn half_up(value: f64, scale: i8) -> f64 {
-	up_or_down(value, scale, true)
+	Round::half_up(value, scale)
 }
 
-/// Round half randomly up or down.
+/// Largest scale at which `value` can be rounded exactly.
 ///
-/// Round `value` to accuracy defined by `scale`
-/// rounding half randomly up or down.
-/// Positive `scale` defines the number of decimal digits in the result
-/// while negative `scale` rounds to a whole number and defines the number
-/// of trailing zeroes in the result.
+/// The rounding functions multiply `value` by `10^scale`. Every `f64`
+/// below `2^53` in magnitude has a precision of at least one unit, so the
+/// scaled value still resolves the digit being rounded as long as
+/// `|value| * 10^scale < 2^53`. The scale is further capped at 22, past
+/// which `10^scale` itself is not exactly representable.
+/// Returns 0 if `value` is not finite or too large for any positive scale.
 ///
 /// # Arguments
 ///
 /// * `value` - value to round
-/// * `scale` - result accuracy
 ///
-/// # Examples
+/// # Example
 ///
 /// ```
 /// use math::round;
 ///
-/// let rounded = round::stochastic(3.14159, 3);
-/// assert_eq!(rounded == 3.141 || rounded == 3.142, true);
+/// assert_eq!(round::max_exact_scale(1000.), 12);
 /// ```
+#[cfg(feature = "std")]
+pub fn max_exact_scale(value: f64) -> u8 {
+	if !value.is_finite() {
+		return 0;
+	}
+	let limit = 2f64.powi(53);
+	let mut scale = 22;
+	while scale > 0 && value.abs() * 10f64.powi(scale) >= limit {
+		scale -= 1;
+	}
+	scale as u8
+}
+
+/// Calculate midpoint of `a` and `b`.
+///
+/// Unlike `(a + b) / 2` this does not overflow when both values are close
+/// to `MAX`, and unlike `a + (b - a) / 2` it does not overflow when they
+/// have opposite signs. The result is correctly rounded. Returns `NAN` if
+/// either value is `NAN`.
+///
+/// # Arguments
+///
+/// * `a` - first value
+/// * `b` - second value
+///
+/// # Example
 ///
 /// ```
 /// use math::round;
 ///
-/// let rounded = round::stochastic(3456., -2);
-/// assert_eq!(rounded == 3400. || rounded == 3500., true);
+/// assert_eq!(round::midpoint(f64::MAX, f64::MAX), f64::MAX);
+/// assert_eq!(round::midpoint(-3., 8.), 2.5);
 /// ```
-pub fn stochastic(value: f64, scale: i8) -> f64 {
-	let digits = significant_digits(value, scale);
-	to_nearest(value, scale, digits.1)
+pub fn midpoint(a: f64, b: f64) -> f64 {
+	a.midpoint(b)
+}
+
+/// Reconcile rounded items with rounded total.
+///
+/// Round every item of `items` and their total separately to accuracy
+/// defined by `scale` using rounding mode `mode`. Returns the rounded
+/// items, the sum of the rounded items and the rounded total so that any
+/// discrepancy between the last two can be detected and reported.
+/// The sum of the rounded items is itself rounded half away from zero to
+/// drop floating point noise introduced by the addition.
+///
+/// # Arguments
+///
+/// * `items` - values to round
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let (rounded, sum, total) = round::reconcile_sum(&[0.335, 0.335, 0.335], 2, RoundingMode::HalfUp);
+/// assert_eq!(rounded, vec![0.34, 0.34, 0.34]);
+/// assert_eq!((sum, total), (1.02, 1.01));
+/// ```
+#[cfg(feature = "std")]
+pub fn reconcile_sum(items: &[f64], scale: i8, mode: RoundingMode) -> (Vec<f64>, f64, f64) {
+	let rounded: Vec<f64> = items.iter().map(|x| round_with(*x, scale, mode)).collect();
+	let sum = half_away_from_zero(rounded.iter().fold(0., |a, b| a + b), scale);
+	let total = round_with(items.iter().fold(0., |a, b| a + b), scale, mode);
+	(rounded, sum, total)
+}
+
+/// Round to binary fraction.
+///
+/// Round `value` to a multiple of `2^-frac_bits` using rounding mode
+/// `mode`. Powers of two are exactly representable, so unlike rounding to
+/// decimal digits the result is always exact.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `frac_bits` - number of fractional bits in the result
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let rounded = round::round_bits(0.1, 4, RoundingMode::HalfUp);
+/// assert_eq!(rounded, 0.125);
+/// ```
+pub fn round_bits(value: f64, frac_bits: u8, mode: RoundingMode) -> f64 {
+	let multiplier = 2f64.powi(frac_bits as i32);
+	match (value * multiplier).abs() < 2f64.powi(52) {
+		true => round_with(value * multiplier, 0, mode) / multiplier,
+		false => value,
+	}
+}
+
+/// Round every element of matrix `m`.
+///
+/// Returns a new matrix of the same dimensions with every element rounded
+/// to accuracy defined by `scale` using rounding mode `mode`. `NAN` and
+/// infinite elements are passed through.
+///
+/// # Arguments
+///
+/// * `m` - matrix to round
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+/// use math::round::{ self, RoundingMode };
+///
+/// let m = Matrix::from_rows(&[vec![1.234, 5.678]]).unwrap();
+/// let rounded = round::round_matrix(&m, 1, RoundingMode::HalfUp);
+/// assert_eq!(rounded, Matrix::from_rows(&[vec![1.2, 5.7]]).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn round_matrix(m: &Matrix, scale: i8, mode: RoundingMode) -> Matrix {
+	let mut rounded = m.clone();
+	round_matrix_mut(&mut rounded, scale, mode);
+	rounded
 }
 
-fn even_or_odd(value: f64, scale: i8, even: bool) -> f64 {
-	let digits = significant_digits(value, scale);
-	match digits.1 == 5 {
-		true => round(value, scale, (value < 0.) ^ even ^ (digits.0 % 2 == 0)),
-		false => to_nearest(value, scale, digits.1),
+/// Round every element of matrix `m` in place.
+///
+/// See `round_matrix`.
+///
+/// # Arguments
+///
+/// * `m` - matrix to round
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+/// use math::round::{ self, RoundingMode };
+///
+/// let mut m = Matrix::from_rows(&[vec![1.234], vec![-5.678]]).unwrap();
+/// round::round_matrix_mut(&mut m, 2, RoundingMode::Floor);
+/// assert_eq!(m, Matrix::from_rows(&[vec![1.23], vec![-5.68]]).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn round_matrix_mut(m: &mut Matrix, scale: i8, mode: RoundingMode) {
+	for i in 0..m.rows() {
+		for j in 0..m.cols() {
+			m[(i, j)] = round_with(m[(i, j)], scale, mode);
+		}
 	}
 }
 
-fn round(value: f64, scale: i8, up: bool) -> f64 {
-	match up {
-		true => ceil(value, scale),
-		false => floor(value, scale),
+/// Round every element of slice `values`.
+///
+/// Returns a vector with every element rounded to accuracy defined by
+/// `scale` using rounding mode `mode`. `NAN` and infinite elements are
+/// passed through.
+///
+/// # Arguments
+///
+/// * `values` - values to round
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let rounded = round::round_slice(&[1.234, -5.678], 1, RoundingMode::HalfUp);
+/// assert_eq!(rounded, vec![1.2, -5.7]);
+/// ```
+#[cfg(feature = "std")]
+pub fn round_slice(values: &[f64], scale: i8, mode: RoundingMode) -> Vec<f64> {
+	let mut rounded = values.to_vec();
+	round_slice_mut(&mut rounded, scale, mode);
+	rounded
+}
+
+/// Round every element of slice `values` in place.
+///
+/// See `round_slice`.
+///
+/// # Arguments
+///
+/// * `values` - values to round
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let mut values = [1.234, -5.678];
+/// round::round_slice_mut(&mut values, 2, RoundingMode::Floor);
+/// assert_eq!(values, [1.23, -5.68]);
+/// ```
+#[cfg(feature = "std")]
+pub fn round_slice_mut(values: &mut [f64], scale: i8, mode: RoundingMode) {
+	for value in values.iter_mut() {
+		*value = round_with(*value, scale, mode);
 	}
 }
 
-fn significant_digits(value: f64, scale: i8) -> (u8, u8) {
-	if value.is_nan() || value.is_infinite() {
-		return (0, 0);
+/// Round with IEEE 754 `roundTiesToAway`.
+///
+/// Same as `half_away_from_zero`. IEEE 754-2008 (section 4.3) names the
+/// rounding direction attribute `roundTiesToAway`, which rounds to the
+/// nearest value and ties away from zero. The attribute is applied at the
+/// decimal accuracy defined by `scale` rather than at the last bit of the
+/// binary format.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::round_ties_to_away(-2.5, 0), -3.);
+/// ```
+pub fn round_ties_to_away(value: f64, scale: i8) -> f64 {
+	half_away_from_zero(value, scale)
+}
+
+/// Round with IEEE 754 `roundTiesToEven`.
+///
+/// Same as `half_to_even`. IEEE 754-2008 (section 4.3) names the rounding
+/// direction attribute `roundTiesToEven`, which rounds to the nearest value
+/// and ties to the one with an even last digit. The attribute is applied at
+/// the decimal accuracy defined by `scale` rather than at the last bit of
+/// the binary format.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::round_ties_to_even(2.25, 1), 2.2);
+/// ```
+pub fn round_ties_to_even(value: f64, scale: i8) -> f64 {
+	half_to_even(value, scale)
+}
+
+/// Round to multiple.
+///
+/// Round `value` to an integer multiple of `multiple` with rounding mode
+/// `mode`, e.g. to the nearest quarter. The sign of `multiple` is ignored.
+/// A quotient within a few ulps of an integer is taken to be that integer,
+/// so that exact multiples such as 0.3 of 0.1 are not moved by directed
+/// modes, and the result is rounded to the decimal digits of `multiple`.
+/// If `multiple` is zero, function returns `NAN`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `multiple` - step size
+/// * `mode` - rounding mode
+///
+/// # Examples
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let rounded = round::round_to_multiple(2.4, 0.25, RoundingMode::HalfUp);
+/// assert_eq!(rounded, 2.5);
+/// ```
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let rounded = round::round_to_multiple(2.375, 0.25, RoundingMode::HalfDown);
+/// assert_eq!(rounded, 2.25);
+/// ```
+pub fn round_to_multiple(value: f64, multiple: f64, mode: RoundingMode) -> f64 {
+	if multiple == 0. {
+		return f64::NAN;
 	}
-	let v = value.abs();
-	let m = 10f64.powi(scale as i32 + 2);
-	let f = 10f64.powi(-1 * (scale as i32 + 1));
-	let a = (v * m) as i64;
-	let b = ((v + f) * m) as i64;
-	let c = ((v - f) * m) as i64;
-	let r = match b - a > a - c {
-		true => a / 10 + 1,
-		false => a / 10,
+	let multiple = multiple.abs();
+	let quotient = value / multiple;
+	let nearest = half_away_from_zero(quotient, 0);
+	let quotient = match (quotient - nearest).abs() <= 4. * f64::EPSILON * nearest.abs() {
+		true => nearest,
+		false => quotient,
 	};
-	((r / 10 % 10) as u8, (r % 10) as u8)
+	let rounded = round_with(quotient, 0, mode) * multiple;
+	match (0..18).find(|&scale| half_away_from_zero(multiple, scale) == multiple) {
+		Some(scale) => half_away_from_zero(rounded, scale),
+		None => rounded,
+	}
+}
+
+/// Round with IEEE 754 `roundTowardNegative`.
+///
+/// Same as `floor`. IEEE 754-2008 (section 4.3) names the rounding
+/// direction attribute `roundTowardNegative`, which rounds towards negative
+/// infinity. The attribute is applied at the decimal accuracy defined by
+/// `scale` rather than at the last bit of the binary format.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::round_toward_negative(-2.21, 1), -2.3);
+/// ```
+pub fn round_toward_negative(value: f64, scale: i8) -> f64 {
+	floor(value, scale)
+}
+
+/// Round with IEEE 754 `roundTowardPositive`.
+///
+/// Same as `ceil`. IEEE 754-2008 (section 4.3) names the rounding direction
+/// attribute `roundTowardPositive`, which rounds towards positive infinity.
+/// The attribute is applied at the decimal accuracy defined by `scale`
+/// rather than at the last bit of the binary format.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::round_toward_positive(2.21, 1), 2.3);
+/// ```
+pub fn round_toward_positive(value: f64, scale: i8) -> f64 {
+	ceil(value, scale)
+}
+
+/// Round with IEEE 754 `roundTowardZero`.
+///
+/// Same as `truncate`. IEEE 754-2008 (section 4.3) names the rounding
+/// direction attribute `roundTowardZero`, which rounds towards zero. The
+/// attribute is applied at the decimal accuracy defined by `scale` rather
+/// than at the last bit of the binary format.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::round_toward_zero(-2.29, 1), -2.2);
+/// ```
+pub fn round_toward_zero(value: f64, scale: i8) -> f64 {
+	truncate(value, scale)
+}
+
+/// Round with rounding mode and track the adjustment.
+///
+/// Rounds like `round_with`, but also returns the difference between the
+/// rounded and the original value, so that the rounding error accumulated
+/// over many values can be audited by summing the deltas.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// let rounded = round::round_tracked(2.5, 0, RoundingMode::HalfUp);
+/// assert_eq!((rounded.value, rounded.delta), (3., 0.5));
+/// ```
+pub fn round_tracked(value: f64, scale: i8, mode: RoundingMode) -> Rounded {
+	let rounded = round_with(value, scale, mode);
+	// infinities are returned unchanged, their difference would be NAN
+	let delta = match rounded == value {
+		true => 0.,
+		false => rounded - value,
+	};
+	Rounded { value: rounded, delta }
+}
+
+/// Round with rounding mode.
+///
+/// Round `value` to accuracy defined by `scale` with the rounding function
+/// selected by `mode`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// assert_eq!(round::round_with(2.5, 0, RoundingMode::HalfToEven), 2.);
+/// assert_eq!(round::round_with(2.5, 0, RoundingMode::HalfToOdd), 3.);
+/// ```
+pub fn round_with(value: f64, scale: i8, mode: RoundingMode) -> f64 {
+	with_mode(value, scale as i32, mode)
+}
+
+/// Find shortest scale that round-trips `value`.
+///
+/// Returns the smallest number of decimal digits with which `value`
+/// can be formatted and parsed back to exactly the same `f64`, e.g. 1 for
+/// `0.1` and 17 for `0.1 + 0.2`. Integers need 0. Values so small that
+/// they need more than `u8::MAX` digits return `u8::MAX`.
+/// Returns 0 if `value` is not finite.
+///
+/// # Arguments
+///
+/// * `value` - value to represent
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::shortest_round_trip_scale(0.1), 1);
+/// assert_eq!(round::shortest_round_trip_scale(0.1 + 0.2), 17);
+/// ```
+#[cfg(feature = "std")]
+pub fn shortest_round_trip_scale(value: f64) -> u8 {
+	if !value.is_finite() {
+		return 0;
+	}
+	(0..u8::MAX)
+		.find(|&scale| format!("{:.*}", scale as usize, value).parse::<f64>() == Ok(value))
+		.unwrap_or(u8::MAX)
+}
+
+/// Round to significant figures and report carry.
+///
+/// Round `value` half away from zero to `figures` significant figures.
+/// Rounding can carry into a new leading digit, e.g. 9.96 becomes 10 at
+/// two figures, which moves the decimal exponent of the result. The second
+/// element of the returned tuple is `true` if that happened, so that
+/// callers formatting in scientific notation can adjust the exponent.
+/// Zero, non-finite values and values whose rounding position falls
+/// outside the `i8` scale range are returned unchanged.
+/// Returns `NAN` if `figures` is 0.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `figures` - number of significant figures in the result
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::sig_figs_checked(9.96, 2), (10., true));
+/// assert_eq!(round::sig_figs_checked(1234.5, 2), (1200., false));
+/// ```
+#[cfg(feature = "std")]
+pub fn sig_figs_checked(value: f64, figures: u8) -> (f64, bool) {
+	if figures == 0 {
+		return (f64::NAN, false);
+	}
+	if value == 0. || !value.is_finite() {
+		return (value, false);
+	}
+	let exponent = decimal_exponent(value);
+	let scale = figures as i32 - 1 - exponent;
+	if scale < i8::MIN as i32 || scale > i8::MAX as i32 {
+		return (value, false);
+	}
+	let rounded = half_away_from_zero(value, scale as i8);
+	let carry = rounded != 0. && decimal_exponent(rounded) != exponent;
+	(rounded, carry)
+}
+
+/// Round to significant figures.
+///
+/// Round `value` to `figures` significant figures with rounding mode
+/// `mode`, e.g. 0.00012345 to 0.000123 or 12345 to 12300 at three figures.
+/// Zero and non-finite values are returned unchanged.
+/// Returns `NAN` if `figures` is 0.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `figures` - number of significant figures in the result
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// assert_eq!(round::significant_figures(0.00012345, 3, RoundingMode::HalfUp), 0.000123);
+/// assert_eq!(round::significant_figures(-12345., 3, RoundingMode::Floor), -12400.);
+/// ```
+#[cfg(feature = "std")]
+pub fn significant_figures(value: f64, figures: u32, mode: RoundingMode) -> f64 {
+	if figures == 0 {
+		return f64::NAN;
+	}
+	if value == 0. || !value.is_finite() {
+		return value;
+	}
+	// the printed mantissa is the decimal that value stands for, so exact
+	// decimals and ties are kept as such, and rounding it keeps the scale
+	// small; the result is parsed to get the f64 closest to the rounded
+	// decimal for any exponent
+	let formatted = format!("{:e}", value);
+	let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap());
+	let mantissa: f64 = mantissa.parse().unwrap();
+	// more figures than any f64 has leave the mantissa unchanged
+	let scale = figures.min(400) as i32 - 1;
+	// a mantissa with no more digits than the scale is not rounded, as
+	// directed modes could move it when scaling is inexact, e.g. 1.13 * 100
+	if with_mode(mantissa, scale, RoundingMode::HalfAwayFromZero) == mantissa {
+		return value;
+	}
+	format!("{}{}", with_mode(mantissa, scale, mode), exponent).parse().unwrap_or(value)
+}
+
+/// Snap point to grid.
+///
+/// Round coordinates `x` and `y` to the nearest multiple of grid spacing
+/// `grid_x` and `grid_y` respectively, rounding half away from zero.
+/// Returns `(NAN, NAN)` if either grid spacing is not positive.
+///
+/// # Arguments
+///
+/// * `x` - horizontal coordinate
+/// * `y` - vertical coordinate
+/// * `grid_x` - horizontal grid spacing
+/// * `grid_y` - vertical grid spacing
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// let snapped = round::snap_to_grid(7.3, 4.9, 2.5, 2.);
+/// assert_eq!(snapped, (7.5, 4.));
+/// ```
+pub fn snap_to_grid(x: f64, y: f64, grid_x: f64, grid_y: f64) -> (f64, f64) {
+	match grid_x > 0. && grid_y > 0. {
+		true => (to_multiple(x, grid_x), to_multiple(y, grid_y)),
+		false => (f64::NAN, f64::NAN),
+	}
+}
+
+/// Round half randomly up or down.
+///
+/// Round `value` to accuracy defined by `scale`
+/// rounding half randomly up or down.
+/// Positive `scale` defines the number of decimal digits in the result
+/// while negative `scale` rounds to a whole number and defines the number
+/// of trailing zeroes in the result.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Examples
+///
+/// ```
+/// use math::round;
+///
+/// let rounded = round::stochastic(3.14159, 3);
+/// assert_eq!(rounded == 3.141 || rounded == 3.142, true);
+/// ```
+///
+/// ```
+/// use math::round;
+///
+/// let rounded = round::stochastic(3456., -2);
+/// assert_eq!(rounded == 3400. || rounded == 3500., true);
+/// ```
+#[cfg(feature = "std")]
+pub fn stochastic(value: f64, scale: i8) -> f64 {
+	Round::stochastic(value, scale)
+}
+
+/// Round randomly up or down with probability proportional to distance.
+///
+/// Round `value` up to accuracy defined by `scale` with probability equal
+/// to its fractional distance from the value rounded down, and down
+/// otherwise, e.g. 3.1417 is rounded to 3.142 70 % of the time at scale 3.
+/// Unlike `stochastic`, which only randomizes ties, the expected result
+/// equals `value`, so that rounding errors do not accumulate in sums.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+/// * `rng` - random number generator
+///
+/// # Example
+///
+/// ```
+/// extern crate rand;
+/// extern crate math;
+///
+/// use math::round;
+///
+/// # fn main() {
+/// let rounded = round::stochastic_proportional(3.1417, 3, &mut rand::thread_rng());
+/// assert!(rounded == 3.141 || rounded == 3.142);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn stochastic_proportional<R: rand::Rng>(value: f64, scale: i8, rng: &mut R) -> f64 {
+	let multiplier = 10f64.powi(scale as i32);
+	let scaled = value * multiplier;
+	match scaled.abs() < 1. / f64::EPSILON {
+		true => {
+			let down = scaled.floor();
+			match rng.gen::<f64>() < scaled - down {
+				true => (down + 1.) / multiplier,
+				false => down / multiplier,
+			}
+		},
+		false => value,
+	}
+}
+
+/// Round half randomly up or down with generator `rng`.
+///
+/// Same as `stochastic` but the ties are resolved with the caller provided
+/// random number generator, so that a seeded generator gives reproducible
+/// results.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+/// * `rng` - random number generator
+///
+/// # Example
+///
+/// ```
+/// extern crate rand;
+/// extern crate math;
+///
+/// use math::round;
+/// use rand::{ SeedableRng, StdRng };
+///
+/// # fn main() {
+/// let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+/// let rounded = round::stochastic_with(3.14159, 3, &mut rng);
+/// assert!(rounded == 3.141 || rounded == 3.142);
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn stochastic_with<R: rand::Rng>(value: f64, scale: i8, rng: &mut R) -> f64 {
+	match halfway(value, scale as i32) {
+		Ordering::Equal => round(value, scale as i32, rng.gen()),
+		position => to_nearest(value, scale as i32, position),
+	}
+}
+
+/// Convert to engineering notation.
+///
+/// Round `value` half away from zero to `sig_figs` significant figures and
+/// split it into a mantissa with magnitude in `[1, 1000)` and an exponent
+/// that is a multiple of 3, so that `value ≈ mantissa * 10^exponent`.
+/// Zero and non-finite values are returned as the mantissa with exponent
+/// 0. Returns `(NAN, 0)` if `sig_figs` is 0.
+///
+/// # Arguments
+///
+/// * `value` - value to convert
+/// * `sig_figs` - number of significant figures in the mantissa
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::to_engineering(12345., 3), (12.3, 3));
+/// assert_eq!(round::to_engineering(0.0022, 2), (2.2, -3));
+/// ```
+#[cfg(feature = "std")]
+pub fn to_engineering(value: f64, sig_figs: u8) -> (f64, i32) {
+	let (mantissa, exponent) = split_exponent(value, sig_figs);
+	let engineering = exponent.div_euclid(3) * 3;
+	let scale = sig_figs as i32 - 1 - (exponent - engineering);
+	match exponent == engineering {
+		true => (mantissa, exponent),
+		false => (rescale(mantissa, exponent - engineering, scale), engineering),
+	}
+}
+
+/// Round to decimal place.
+///
+/// Round `value` to decimal place `place` with rounding mode `mode`.
+/// Positive `place` defines the number of decimal digits in the result,
+/// zero rounds to a whole number and negative `place` rounds to the left of
+/// the decimal point, e.g. `-2` to the nearest hundred. Unlike `scale` of
+/// the other functions `place` is not limited to the range of `i8`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `place` - decimal place to round to
+/// * `mode` - rounding mode
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ self, RoundingMode };
+///
+/// assert_eq!(round::to_place(12345., -2, RoundingMode::HalfUp), 12300.);
+/// assert_eq!(round::to_place(12345., -3, RoundingMode::Ceil), 13000.);
+/// assert_eq!(round::to_place(1.23456, 3, RoundingMode::HalfUp), 1.235);
+/// ```
+pub fn to_place(value: f64, place: i32, mode: RoundingMode) -> f64 {
+	if place < -308 && value.is_finite() {
+		// the step is larger than any finite value and the multiplier would
+		// underflow, so round a fraction of the step with the sign of `value`
+		let fraction = match value == 0. {
+			true => value,
+			false => value.signum() * 0.25,
+		};
+		let rounded = with_mode(fraction, 0, mode);
+		return match rounded == 0. {
+			true => rounded,
+			false => rounded * f64::INFINITY,
+		};
+	}
+	// places above 400 are past the precision of any value
+	with_mode(value, place.min(400), mode)
+}
+
+/// Format with SI prefix.
+///
+/// Convert `value` to engineering notation with `sig_figs` significant
+/// figures and render it with the matching SI prefix from `y` (10^-24)
+/// to `Y` (10^24), e.g. `12.3k` or `2.2m`. Values outside of the prefix
+/// range are rendered in scientific notation, e.g. `1.5e30`, zero is
+/// rendered as `0` and non-finite values as by `Display`.
+///
+/// # Arguments
+///
+/// * `value` - value to format
+/// * `sig_figs` - number of significant figures
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::to_si_string(12345., 3), "12.3k");
+/// assert_eq!(round::to_si_string(0.0022, 2), "2.2m");
+/// assert_eq!(round::to_si_string(-4.7e-8, 2), "-47n");
+/// ```
+#[cfg(feature = "std")]
+pub fn to_si_string(value: f64, sig_figs: u8) -> String {
+	const PREFIXES: [&str; 17] = ["y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y"];
+	if value == 0. || !value.is_finite() || sig_figs == 0 {
+		return format!("{}", to_engineering(value, sig_figs).0);
+	}
+	let (mantissa, exponent) = to_engineering(value, sig_figs);
+	let prefix = (exponent / 3 + 8) as usize;
+	match exponent.abs() <= 24 {
+		true => {
+			let digits = (mantissa.abs().log10().floor() as i32 + 1).max(1);
+			let decimals = (sig_figs as i32 - digits).max(0) as usize;
+			format!("{:.*}{}", decimals, mantissa, PREFIXES[prefix])
+		},
+		false => {
+			let (mantissa, exponent) = split_exponent(value, sig_figs);
+			format!("{:.*}e{}", sig_figs as usize - 1, mantissa, exponent)
+		},
+	}
+}
+
+/// Truncate.
+///
+/// Drop the digits of `value` beyond accuracy defined by `scale`, rounding
+/// towards zero. Positive `scale` defines the number of decimal digits in
+/// the result while negative `scale` rounds to a whole number and defines
+/// the number of trailing zeroes in the result.
+///
+/// # Arguments
+///
+/// * `value` - value to truncate
+/// * `scale` - result accuracy
+///
+/// # Examples
+///
+/// ```
+/// use math::round;
+///
+/// let truncated = round::truncate(-3.14159, 3);
+/// assert_eq!(truncated, -3.141);
+/// ```
+///
+/// ```
+/// use math::round;
+///
+/// let truncated = round::truncate(3456., -2);
+/// assert_eq!(truncated, 3400.);
+/// ```
+pub fn truncate(value: f64, scale: i8) -> f64 {
+	Round::truncate(value, scale)
+}
+
+/// Truncate to binary fraction.
+///
+/// Drop the bits of `value` beyond `frac_bits` fractional bits, rounding
+/// towards zero.
+///
+/// # Arguments
+///
+/// * `value` - value to truncate
+/// * `frac_bits` - number of fractional bits in the result
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::truncate_bits(0.3, 2), 0.25);
+/// assert_eq!(round::truncate_bits(-0.3, 2), -0.25);
+/// ```
+pub fn truncate_bits(value: f64, frac_bits: u8) -> f64 {
+	let multiplier = 2f64.powi(frac_bits as i32);
+	match (value * multiplier).abs() < 2f64.powi(52) {
+		true => (value * multiplier).trunc() / multiplier,
+		false => value,
+	}
+}
+
+/// Unit in the last place of `value`.
+///
+/// Returns the distance from `|value|` to the next larger `f64`, or to
+/// the next smaller one at `MAX`. This is the finest difference the type
+/// can represent at the magnitude of `value`.
+///
+/// # Arguments
+///
+/// * `value` - value to inspect
+///
+/// # Example
+///
+/// ```
+/// use math::round;
+///
+/// assert_eq!(round::ulp(1.), f64::EPSILON);
+/// ```
+pub fn ulp(value: f64) -> f64 {
+	let v = value.abs();
+	match v.is_finite() {
+		true if v == f64::MAX => v - f64::from_bits(v.to_bits() - 1),
+		true => f64::from_bits(v.to_bits() + 1) - v,
+		false => v,
+	}
+}
+
+/// Rounder with error feedback.
+///
+/// Rounds a stream of values half away from zero, adding the residual of
+/// every rounding to the next value before rounding it. The running sum
+/// of the rounded values therefore stays within half a unit of the
+/// running sum of the inputs, while rounding every value independently
+/// lets the error grow with the length of the stream.
+///
+/// # Example
+///
+/// ```
+/// use math::round::ErrorFeedbackRounder;
+///
+/// let mut rounder = ErrorFeedbackRounder::new(0);
+/// let rounded: Vec<f64> = [0.4, 0.4, 0.4].iter().map(|x| rounder.round(*x)).collect();
+/// assert_eq!(rounded, vec![0., 1., 0.]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorFeedbackRounder {
+	scale: i8,
+	residual: f64,
+}
+
+impl ErrorFeedbackRounder {
+	/// Create rounder with accuracy defined by `scale`.
+	///
+	/// # Arguments
+	///
+	/// * `scale` - result accuracy
+	pub fn new(scale: i8) -> ErrorFeedbackRounder {
+		ErrorFeedbackRounder { scale, residual: 0. }
+	}
+
+	/// Residual carried into the next value.
+	pub fn residual(&self) -> f64 {
+		self.residual
+	}
+
+	/// Round `value` together with the carried residual.
+	///
+	/// # Arguments
+	///
+	/// * `value` - value to round
+	pub fn round(&mut self, value: f64) -> f64 {
+		let adjusted = value + self.residual;
+		let rounded = half_away_from_zero(adjusted, self.scale);
+		self.residual = adjusted - rounded;
+		rounded
+	}
+}
+
+/// Error returned when parsing a `RoundingMode` fails.
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ ParseRoundingModeError, RoundingMode };
+///
+/// assert_eq!("half-even".parse(), Ok(RoundingMode::HalfToEven));
+/// assert_eq!("".parse::<RoundingMode>(), Err(ParseRoundingModeError::Empty));
+/// assert!("half-evn".parse::<RoundingMode>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseRoundingModeError {
+	/// The string is empty.
+	Empty,
+	/// The string is not the name of a rounding mode.
+	#[cfg(feature = "std")]
+	Unknown(String),
+	/// The string is not the name of a rounding mode. Without the `std`
+	/// feature the string is not kept, as it would have to be allocated.
+	#[cfg(not(feature = "std"))]
+	Unknown,
+}
+
+impl fmt::Display for ParseRoundingModeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ParseRoundingModeError::Empty => return f.write_str("empty rounding mode"),
+			#[cfg(feature = "std")]
+			ParseRoundingModeError::Unknown(ref s) => write!(f, "unknown rounding mode {:?}, ", s)?,
+			#[cfg(not(feature = "std"))]
+			ParseRoundingModeError::Unknown => f.write_str("unknown rounding mode, ")?,
+		}
+		f.write_str("expected one of ceil, floor, half-up, half-down, half-away-from-zero, \
+			half-towards-zero, half-to-even, half-to-odd or stochastic")
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseRoundingModeError {}
+
+/// Iterator rounding the values of another iterator.
+///
+/// Created by `RoundIterExt::rounded`, which rounds every value with
+/// `round_with` as the iterator is advanced.
+#[derive(Debug, Clone)]
+pub struct RoundIter<I> {
+	iter: I,
+	scale: i8,
+	mode: RoundingMode,
+}
+
+impl<I: Iterator<Item = f64>> Iterator for RoundIter<I> {
+	type Item = f64;
+
+	fn next(&mut self) -> Option<f64> {
+		self.iter.next().map(|value| round_with(value, self.scale, self.mode))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+/// Rounded value returned by `round_tracked`.
+///
+/// `delta` is the rounded value minus the original value, positive if the
+/// value was rounded up and negative if it was rounded down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rounded {
+	/// Rounded value.
+	pub value: f64,
+	/// Adjustment made by rounding.
+	pub delta: f64,
+}
+
+/// Rounding of floating point numbers.
+///
+/// Implemented for `f32` and `f64` so that `f32` values are rounded in
+/// their own precision. Every method behaves like the free function of
+/// the same name in this module. `ceil` and `floor` are shadowed by the
+/// inherent methods of the float types and have to be called as
+/// `Round::ceil(value, scale)`.
+///
+/// # Example
+///
+/// ```
+/// use math::round::Round;
+///
+/// assert_eq!(3.14159f32.half_up(3), 3.142);
+/// assert_eq!(Round::ceil(3456f32, -2), 3500.);
+/// ```
+pub trait Round {
+	/// Round up, see `ceil`.
+	fn ceil(self, scale: i8) -> Self;
+	/// Round down, see `floor`.
+	fn floor(self, scale: i8) -> Self;
+	/// Round half away from zero, see `half_away_from_zero`.
+	fn half_away_from_zero(self, scale: i8) -> Self;
+	/// Round half down, see `half_down`.
+	fn half_down(self, scale: i8) -> Self;
+	/// Round half to nearest even number, see `half_to_even`.
+	fn half_to_even(self, scale: i8) -> Self;
+	/// Round half to nearest odd number, see `half_to_odd`.
+	fn half_to_odd(self, scale: i8) -> Self;
+	/// Round half towards zero, see `half_towards_zero`.
+	fn half_towards_zero(self, scale: i8) -> Self;
+	/// Round half up, see `half_up`.
+	fn half_up(self, scale: i8) -> Self;
+	/// Round half randomly up or down, see `stochastic`.
+	#[cfg(feature = "std")]
+	fn stochastic(self, scale: i8) -> Self;
+	/// Truncate, see `truncate`.
+	fn truncate(self, scale: i8) -> Self;
+}
+
+// `max_exact_power` is the largest `n` for which `10^n` is exactly
+// representable, i.e. for which `5^n` fits the mantissa
+macro_rules! impl_round {
+	($($t:ty: $max_exact_power:expr),*) => {
+	$(
+		impl Round for $t {
+			fn ceil(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::Ceil)
+			}
+
+			fn floor(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::Floor)
+			}
+
+			fn half_away_from_zero(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::HalfAwayFromZero)
+			}
+
+			fn half_down(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::HalfDown)
+			}
+
+			fn half_to_even(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::HalfToEven)
+			}
+
+			fn half_to_odd(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::HalfToOdd)
+			}
+
+			fn half_towards_zero(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::HalfTowardsZero)
+			}
+
+			fn half_up(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::HalfUp)
+			}
+
+			#[cfg(feature = "std")]
+			fn stochastic(self, scale: i8) -> $t {
+				with_mode(self, scale as i32, RoundingMode::Stochastic)
+			}
+
+			fn truncate(self, scale: i8) -> $t {
+				scaled(self, scale as i32, Float::trunc)
+			}
+		}
+
+		impl Float for $t {
+			fn abs(self) -> $t { <$t>::abs(self) }
+			#[cfg(feature = "std")]
+			fn ceil(self) -> $t { <$t>::ceil(self) }
+			#[cfg(not(feature = "std"))]
+			fn ceil(self) -> $t { libm::Libm::<$t>::ceil(self) }
+			fn epsilon() -> $t { <$t>::EPSILON }
+			#[cfg(feature = "std")]
+			fn floor(self) -> $t { <$t>::floor(self) }
+			#[cfg(not(feature = "std"))]
+			fn floor(self) -> $t { libm::Libm::<$t>::floor(self) }
+			fn from_f64(value: f64) -> $t { value as $t }
+			fn is_infinite(self) -> bool { <$t>::is_infinite(self) }
+			fn is_nan(self) -> bool { <$t>::is_nan(self) }
+			fn max_exact_power() -> i32 { $max_exact_power }
+			#[cfg(feature = "std")]
+			fn powi(self, n: i32) -> $t { <$t>::powi(self, n) }
+			#[cfg(not(feature = "std"))]
+			fn powi(self, n: i32) -> $t { libm::Libm::<$t>::pow(self, n as $t) }
+			fn to_u8(self) -> u8 { self as u8 }
+			#[cfg(feature = "std")]
+			fn trunc(self) -> $t { <$t>::trunc(self) }
+			#[cfg(not(feature = "std"))]
+			fn trunc(self) -> $t { libm::Libm::<$t>::trunc(self) }
+			fn zero() -> $t { 0. }
+		}
+	)*
+	}
+}
+
+impl_round! { f32: 10, f64: 22 }
+
+/// Lazy rounding of iterators over `f64`.
+///
+/// # Example
+///
+/// ```
+/// use math::round::{ RoundIterExt, RoundingMode };
+///
+/// let values = [1.234, 5.678];
+/// let rounded: Vec<f64> = values.iter().cloned().rounded(1, RoundingMode::HalfUp).collect();
+/// assert_eq!(rounded, vec![1.2, 5.7]);
+/// ```
+pub trait RoundIterExt: Iterator<Item = f64> + Sized {
+	/// Round every value to accuracy defined by `scale` using rounding mode
+	/// `mode`.
+	///
+	/// # Arguments
+	///
+	/// * `scale` - result accuracy
+	/// * `mode` - rounding mode
+	fn rounded(self, scale: i8, mode: RoundingMode) -> RoundIter<Self> {
+		RoundIter { iter: self, scale, mode }
+	}
+}
+
+impl<I: Iterator<Item = f64>> RoundIterExt for I {}
+
+// operations of the float types implementing `Round` that the private
+// helpers below are generic over
+trait Float: Copy + PartialOrd
+	+ Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+	+ Div<Output = Self> + Rem<Output = Self> {
+	fn abs(self) -> Self;
+	fn ceil(self) -> Self;
+	fn epsilon() -> Self;
+	fn floor(self) -> Self;
+	fn from_f64(value: f64) -> Self;
+	fn is_infinite(self) -> bool;
+	fn is_nan(self) -> bool;
+	fn max_exact_power() -> i32;
+	fn powi(self, n: i32) -> Self;
+	fn to_u8(self) -> u8;
+	fn trunc(self) -> Self;
+	fn zero() -> Self;
+}
+
+#[cfg(feature = "std")]
+fn decimal_exponent(value: f64) -> i32 {
+	// log10 can round up to the next integer just below a power of ten
+	let exponent = value.abs().log10().floor() as i32;
+	match 10f64.powi(exponent) > value.abs() {
+		true => exponent - 1,
+		false => exponent,
+	}
+}
+
+fn even_or_odd<F: Float>(value: F, scale: i32, even: bool) -> F {
+	match halfway(value, scale) {
+		Ordering::Equal => {
+			// keep the digit before the rounding position if it already has
+			// the wanted parity, i.e. round towards zero, otherwise away from it
+			let keep = significant_digits(value, scale).0.is_multiple_of(2) == even;
+			round(value, scale, (value < F::zero()) == keep)
+		},
+		position => to_nearest(value, scale, position),
+	}
+}
+
+fn halfway<F: Float>(value: F, scale: i32) -> Ordering {
+	// position of |value| relative to the point halfway between the two
+	// values it can be rounded to, k / 10^scale and (k + 1) / 10^scale. As
+	// long as the power of ten is exact a single division or multiplication
+	// yields the float nearest to such a decimal, so a value is a tie
+	// exactly when it is the float nearest to (2k + 1) / (2 * 10^scale),
+	// e.g. 2.675 at scale 2, while any other value compares correctly
+	// however close it is. A value which already is the float nearest to
+	// one of the two is not rounded, as with 16 or more significant digits
+	// it can be the float nearest to the halfway point as well
+	let v = value.abs();
+	let (one, two, ten) = (F::from_f64(1.), F::from_f64(2.), F::from_f64(10.));
+	let decimal = |n: F| match scale >= 0 {
+		true => n / ten.powi(scale),
+		false => n * ten.powi(-scale),
+	};
+	let k = (v * ten.powi(scale)).floor();
+	if v == decimal(k) {
+		return Ordering::Less;
+	}
+	if v == decimal(k + one) {
+		return Ordering::Greater;
+	}
+	let half = decimal(two * k + one) / two;
+	// past the exact powers of ten allow for the error of the power
+	if scale.abs() > F::max_exact_power() && (v - half).abs() <= v * F::epsilon() {
+		return Ordering::Equal;
+	}
+	v.partial_cmp(&half).unwrap_or(Ordering::Less)
+}
+
+#[cfg(feature = "std")]
+fn rescale(value: f64, shift: i32, scale: i32) -> f64 {
+	let scale = scale.clamp(i8::MIN as i32, i8::MAX as i32) as i8;
+	half_away_from_zero(value * 10f64.powi(shift), scale)
+}
+
+fn round<F: Float>(value: F, scale: i32, up: bool) -> F {
+	match up {
+		true => scaled(value, scale, F::ceil),
+		false => scaled(value, scale, F::floor),
+	}
+}
+
+fn scaled<F: Float>(value: F, scale: i32, op: fn(F) -> F) -> F {
+	let multiplier = F::from_f64(10.).powi(scale);
+	let scaled = value * multiplier;
+	// past the precision of the type the scaled value is an integer already
+	// and scaling back would only add the error of the multiplication
+	match scaled.abs() < F::from_f64(1.) / F::epsilon() {
+		true => op(scaled) / multiplier,
+		false => value,
+	}
+}
+
+fn significant_digits<F: Float>(value: F, scale: i32) -> (u8, u8) {
+	if value.is_nan() || value.is_infinite() {
+		return (0, 0);
+	}
+	let ten = F::from_f64(10.);
+	let v = value.abs();
+	let m = ten.powi(scale + 2);
+	let f = ten.powi(-(scale + 1));
+	let a = (v * m).trunc();
+	let b = ((v + f) * m).trunc();
+	let c = ((v - f) * m).trunc();
+	let r = match b - a > a - c {
+		true => (a / ten).trunc() + F::from_f64(1.),
+		false => (a / ten).trunc(),
+	};
+	(((r / ten).trunc() % ten).to_u8(), (r % ten).to_u8())
+}
+
+#[cfg(feature = "std")]
+fn split_exponent(value: f64, sig_figs: u8) -> (f64, i32) {
+	// returns the mantissa in [1, 10) rounded to sig_figs significant
+	// figures and the decimal exponent; the mantissa is rounded after
+	// normalization so that the scale stays small for any magnitude
+	if sig_figs == 0 {
+		return (f64::NAN, 0);
+	}
+	if value == 0. || !value.is_finite() {
+		return (value, 0);
+	}
+	let exponent = value.abs().log10().floor() as i32;
+	let normalized = match exponent < -300 {
+		true => value * 1e300 / 10f64.powi(exponent + 300),
+		false => value / 10f64.powi(exponent),
+	};
+	let mantissa = rescale(normalized, 0, sig_figs as i32 - 1);
+	match mantissa.abs() >= 10. {
+		true => (rescale(mantissa, -1, sig_figs as i32 - 1), exponent + 1),
+		false => (mantissa, exponent),
+	}
+}
+
+fn to_nearest<F: Float>(value: F, scale: i32, position: Ordering) -> F {
+	// values above the halfway point are rounded away from zero and values
+	// below it towards zero
+	round(value, scale, (value < F::zero()) ^ (position == Ordering::Greater))
 }
 
-fn to_nearest(value: f64, scale: i8, digit: u8) -> f64 {
-	let up = match digit == 5 {
-		true => rand::random::<bool>(),
-		false => (value < 0.) ^ (digit > 5),
-	};
-	round(value, scale, up)
+fn to_multiple(value: f64, multiple: f64) -> f64 {
+	half_away_from_zero(value / multiple, 0) * multiple
+}
+
+fn towards_zero<F: Float>(value: F, scale: i32, towards: bool) -> F {
+	match halfway(value, scale) {
+		Ordering::Equal => round(value, scale, (value < F::zero()) ^ !towards),
+		position => to_nearest(value, scale, position),
+	}
 }
 
-fn towards_zero(value: f64, scale: i8, towards: bool) -> f64 {
-	let digits = significant_digits(value, scale);
-	match digits.1 == 5 {
-		true => round(value, scale, (value < 0.) ^ !towards),
-		false => to_nearest(value, scale, digits.1),
+fn up_or_down<F: Float>(value: F, scale: i32, up: bool) -> F {
+	match halfway(value, scale) {
+		Ordering::Equal => round(value, scale, up),
+		position => to_nearest(value, scale, position),
 	}
 }
 
-fn up_or_down(value: f64, scale: i8, up: bool) -> f64 {
-	let digits = significant_digits(value, scale);
-	match digits.1 == 5 {
-		true => round(value, scale, up),
-		false => to_nearest(value, scale, digits.1),
+fn with_mode<F: Float>(value: F, scale: i32, mode: RoundingMode) -> F {
+	match mode {
+		RoundingMode::Ceil => round(value, scale, true),
+		RoundingMode::Floor => round(value, scale, false),
+		RoundingMode::HalfUp => up_or_down(value, scale, true),
+		RoundingMode::HalfDown => up_or_down(value, scale, false),
+		RoundingMode::HalfAwayFromZero => towards_zero(value, scale, false),
+		RoundingMode::HalfTowardsZero => towards_zero(value, scale, true),
+		RoundingMode::HalfToEven => even_or_odd(value, scale, true),
+		RoundingMode::HalfToOdd => even_or_odd(value, scale, false),
+		#[cfg(feature = "std")]
+		RoundingMode::Stochastic => match halfway(value, scale) {
+			Ordering::Equal => round(value, scale, rand::random::<bool>()),
+			position => to_nearest(value, scale, position),
+		},
+		#[cfg(not(feature = "std"))]
+		RoundingMode::Stochastic => even_or_odd(value, scale, true),
 	}
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
-	use std::f64::{ NAN, INFINITY, NEG_INFINITY };
+	use super::RoundingMode;
+	use super::rand::{ Rng, SeedableRng, StdRng };
+	use matrix::Matrix;
 
 	macro_rules! test_round {
 		($func:path [ $($name:ident: $params:expr,)* ]) => {
@@ -366,13 +1870,29 @@ mod tests {
 		}
 	}
 
+	macro_rules! test_round_f32 {
+		($func:ident [ $($name:ident: $params:expr,)* ]) => {
+		$(
+			#[test]
+			fn $name() {
+				let (value, scale, expected): (f32, i8, f32) = $params;
+				let result = <f32 as super::Round>::$func(value, scale);
+				match result.is_nan() {
+					true => assert!(expected.is_nan()),
+					false => assert_eq!(result, expected),
+				}
+			}
+		)*
+		}
+	}
+
 	macro_rules! test_digits {
 		($func:path [ $($name:ident: $params:expr,)* ]) => {
 		$(
 			#[test]
 			fn $name() {
 				let (value, scale, expected): (f64, i8, (u8, u8)) = $params;
-				assert_eq!($func(value, scale), expected);
+				assert_eq!($func(value, scale as i32), expected);
 			}
 		)*
 		}
@@ -403,9 +1923,9 @@ mod tests {
 		ceil_22: (1.33, 1, 1.4),
 		ceil_23: (1.35, 1, 1.4),
 		ceil_24: (1.37, 1, 1.4),
-		ceil_25: (INFINITY, 1, INFINITY),
-		ceil_26: (NAN, 1, NAN),
-		ceil_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		ceil_25: (f64::INFINITY, 1, f64::INFINITY),
+		ceil_26: (f64::NAN, 1, f64::NAN),
+		ceil_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		ceil_28: (-103., -1, -100.),
 		ceil_29: (-105., -1, -100.),
 		ceil_30: (-107., -1, -100.),
@@ -430,9 +1950,9 @@ mod tests {
 		ceil_49: (133., -1, 140.),
 		ceil_50: (135., -1, 140.),
 		ceil_51: (137., -1, 140.),
-		ceil_52: (INFINITY, -1, INFINITY),
-		ceil_53: (NAN, -1, NAN),
-		ceil_54: (NEG_INFINITY, -1, NEG_INFINITY),
+		ceil_52: (f64::INFINITY, -1, f64::INFINITY),
+		ceil_53: (f64::NAN, -1, f64::NAN),
+		ceil_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
 	]}
 
 	test_round! { super::floor [
@@ -460,9 +1980,9 @@ mod tests {
 		floor_22: (1.33, 1, 1.3),
 		floor_23: (1.35, 1, 1.3),
 		floor_24: (1.37, 1, 1.3),
-		floor_25: (INFINITY, 1, INFINITY),
-		floor_26: (NAN, 1, NAN),
-		floor_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		floor_25: (f64::INFINITY, 1, f64::INFINITY),
+		floor_26: (f64::NAN, 1, f64::NAN),
+		floor_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		floor_28: (-103., -1, -110.),
 		floor_29: (-105., -1, -110.),
 		floor_30: (-107., -1, -110.),
@@ -487,9 +2007,9 @@ mod tests {
 		floor_49: (133., -1, 130.),
 		floor_50: (135., -1, 130.),
 		floor_51: (137., -1, 130.),
-		floor_52: (INFINITY, -1, INFINITY),
-		floor_53: (NAN, -1, NAN),
-		floor_54: (NEG_INFINITY, -1, NEG_INFINITY),
+		floor_52: (f64::INFINITY, -1, f64::INFINITY),
+		floor_53: (f64::NAN, -1, f64::NAN),
+		floor_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
 	]}
 
 	test_round! { super::half_away_from_zero [
@@ -517,9 +2037,9 @@ mod tests {
 		half_away_from_zero_22: (1.33, 1, 1.3),
 		half_away_from_zero_23: (1.35, 1, 1.4),
 		half_away_from_zero_24: (1.37, 1, 1.4),
-		half_away_from_zero_25: (INFINITY, 1, INFINITY),
-		half_away_from_zero_26: (NAN, 1, NAN),
-		half_away_from_zero_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		half_away_from_zero_25: (f64::INFINITY, 1, f64::INFINITY),
+		half_away_from_zero_26: (f64::NAN, 1, f64::NAN),
+		half_away_from_zero_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		half_away_from_zero_28: (-103., -1, -100.),
 		half_away_from_zero_29: (-105., -1, -110.),
 		half_away_from_zero_30: (-107., -1, -110.),
@@ -544,9 +2064,9 @@ mod tests {
 		half_away_from_zero_49: (133., -1, 130.),
 		half_away_from_zero_50: (135., -1, 140.),
 		half_away_from_zero_51: (137., -1, 140.),
-		half_away_from_zero_52: (INFINITY, -1, INFINITY),
-		half_away_from_zero_53: (NAN, -1, NAN),
-		half_away_from_zero_54: (NEG_INFINITY, -1, NEG_INFINITY),
+		half_away_from_zero_52: (f64::INFINITY, -1, f64::INFINITY),
+		half_away_from_zero_53: (f64::NAN, -1, f64::NAN),
+		half_away_from_zero_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
 	]}
 
 	test_round! { super::half_down [
@@ -574,9 +2094,9 @@ mod tests {
 		half_down_22: (1.33, 1, 1.3),
 		half_down_23: (1.35, 1, 1.3),
 		half_down_24: (1.37, 1, 1.4),
-		half_down_25: (INFINITY, 1, INFINITY),
-		half_down_26: (NAN, 1, NAN),
-		half_down_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		half_down_25: (f64::INFINITY, 1, f64::INFINITY),
+		half_down_26: (f64::NAN, 1, f64::NAN),
+		half_down_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		half_down_28: (-103., -1, -100.),
 		half_down_29: (-105., -1, -110.),
 		half_down_30: (-107., -1, -110.),
@@ -601,9 +2121,9 @@ mod tests {
 		half_down_49: (133., -1, 130.),
 		half_down_50: (135., -1, 130.),
 		half_down_51: (137., -1, 140.),
-		half_down_52: (INFINITY, -1, INFINITY),
-		half_down_53: (NAN, -1, NAN),
-		half_down_54: (NEG_INFINITY, -1, NEG_INFINITY),
+		half_down_52: (f64::INFINITY, -1, f64::INFINITY),
+		half_down_53: (f64::NAN, -1, f64::NAN),
+		half_down_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
 	]}
 
 	test_round! { super::half_to_even [
@@ -631,9 +2151,9 @@ mod tests {
 		half_to_even_22: (1.33, 1, 1.3),
 		half_to_even_23: (1.35, 1, 1.4),
 		half_to_even_24: (1.37, 1, 1.4),
-		half_to_even_25: (INFINITY, 1, INFINITY),
-		half_to_even_26: (NAN, 1, NAN),
-		half_to_even_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		half_to_even_25: (f64::INFINITY, 1, f64::INFINITY),
+		half_to_even_26: (f64::NAN, 1, f64::NAN),
+		half_to_even_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		half_to_even_28: (2.221000, 3, 2.221),
 		half_to_even_29: (-103., -1, -100.),
 		half_to_even_30: (-105., -1, -100.),
@@ -659,9 +2179,9 @@ mod tests {
 		half_to_even_50: (133., -1, 130.),
 		half_to_even_51: (135., -1, 140.),
 		half_to_even_52: (137., -1, 140.),
-		half_to_even_53: (INFINITY, -1, INFINITY),
-		half_to_even_54: (NAN, -1, NAN),
-		half_to_even_55: (NEG_INFINITY, -1, NEG_INFINITY),
+		half_to_even_53: (f64::INFINITY, -1, f64::INFINITY),
+		half_to_even_54: (f64::NAN, -1, f64::NAN),
+		half_to_even_55: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
 	]}
 
 	test_round! { super::half_to_odd [
@@ -689,9 +2209,9 @@ mod tests {
 		half_to_odd_22: (1.33, 1, 1.3),
 		half_to_odd_23: (1.35, 1, 1.3),
 		half_to_odd_24: (1.37, 1, 1.4),
-		half_to_odd_25: (INFINITY, 1, INFINITY),
-		half_to_odd_26: (NAN, 1, NAN),
-		half_to_odd_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		half_to_odd_25: (f64::INFINITY, 1, f64::INFINITY),
+		half_to_odd_26: (f64::NAN, 1, f64::NAN),
+		half_to_odd_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		half_to_odd_28: (-103., -1, -100.),
 		half_to_odd_29: (-105., -1, -110.),
 		half_to_odd_30: (-107., -1, -110.),
@@ -716,9 +2236,9 @@ mod tests {
 		half_to_odd_49: (133., -1, 130.),
 		half_to_odd_50: (135., -1, 130.),
 		half_to_odd_51: (137., -1, 140.),
-		half_to_odd_52: (INFINITY, -1, INFINITY),
-		half_to_odd_53: (NAN, -1, NAN),
-		half_to_odd_54: (NEG_INFINITY, -1, NEG_INFINITY),
+		half_to_odd_52: (f64::INFINITY, -1, f64::INFINITY),
+		half_to_odd_53: (f64::NAN, -1, f64::NAN),
+		half_to_odd_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
 	]}
 
 	test_round! { super::half_towards_zero [
@@ -746,9 +2266,9 @@ mod tests {
 		half_towards_zero_22: (1.33, 1, 1.3),
 		half_towards_zero_23: (1.35, 1, 1.3),
 		half_towards_zero_24: (1.37, 1, 1.4),
-		half_towards_zero_25: (INFINITY, 1, INFINITY),
-		half_towards_zero_26: (NAN, 1, NAN),
-		half_towards_zero_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		half_towards_zero_25: (f64::INFINITY, 1, f64::INFINITY),
+		half_towards_zero_26: (f64::NAN, 1, f64::NAN),
+		half_towards_zero_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		half_towards_zero_28: (-103., -1, -100.),
 		half_towards_zero_29: (-105., -1, -100.),
 		half_towards_zero_30: (-107., -1, -110.),
@@ -773,9 +2293,9 @@ mod tests {
 		half_towards_zero_49: (133., -1, 130.),
 		half_towards_zero_50: (135., -1, 130.),
 		half_towards_zero_51: (137., -1, 140.),
-		half_towards_zero_52: (INFINITY, -1, INFINITY),
-		half_towards_zero_53: (NAN, -1, NAN),
-		half_towards_zero_54: (NEG_INFINITY, -1, NEG_INFINITY),
+		half_towards_zero_52: (f64::INFINITY, -1, f64::INFINITY),
+		half_towards_zero_53: (f64::NAN, -1, f64::NAN),
+		half_towards_zero_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
 	]}
 
 	test_round! { super::half_up [
@@ -803,9 +2323,9 @@ mod tests {
 		half_up_22: (1.33, 1, 1.3),
 		half_up_23: (1.35, 1, 1.4),
 		half_up_24: (1.37, 1, 1.4),
-		half_up_25: (INFINITY, 1, INFINITY),
-		half_up_26: (NAN, 1, NAN),
-		half_up_27: (NEG_INFINITY, 1, NEG_INFINITY),
+		half_up_25: (f64::INFINITY, 1, f64::INFINITY),
+		half_up_26: (f64::NAN, 1, f64::NAN),
+		half_up_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
 		half_up_28: (-103., -1, -100.),
 		half_up_29: (-105., -1, -100.),
 		half_up_30: (-107., -1, -110.),
@@ -830,9 +2350,640 @@ mod tests {
 		half_up_49: (133., -1, 130.),
 		half_up_50: (135., -1, 140.),
 		half_up_51: (137., -1, 140.),
-		half_up_52: (INFINITY, -1, INFINITY),
-		half_up_53: (NAN, -1, NAN),
-		half_up_54: (NEG_INFINITY, -1, NEG_INFINITY),
+		half_up_52: (f64::INFINITY, -1, f64::INFINITY),
+		half_up_53: (f64::NAN, -1, f64::NAN),
+		half_up_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
+	]}
+
+	test_round! { super::truncate [
+		truncate_1: (-1.03, 1, -1.),
+		truncate_2: (-1.05, 1, -1.),
+		truncate_3: (-1.07, 1, -1.),
+		truncate_4: (-1.13, 1, -1.1),
+		truncate_5: (-1.15, 1, -1.1),
+		truncate_6: (-1.17, 1, -1.1),
+		truncate_7: (-1.23, 1, -1.2),
+		truncate_8: (-1.25, 1, -1.2),
+		truncate_9: (-1.27, 1, -1.2),
+		truncate_10: (-1.33, 1, -1.3),
+		truncate_11: (-1.35, 1, -1.3),
+		truncate_12: (-1.37, 1, -1.3),
+		truncate_13: (1.03, 1, 1.),
+		truncate_14: (1.05, 1, 1.),
+		truncate_15: (1.07, 1, 1.),
+		truncate_16: (1.13, 1, 1.1),
+		truncate_17: (1.15, 1, 1.1),
+		truncate_18: (1.17, 1, 1.1),
+		truncate_19: (1.23, 1, 1.2),
+		truncate_20: (1.25, 1, 1.2),
+		truncate_21: (1.27, 1, 1.2),
+		truncate_22: (1.33, 1, 1.3),
+		truncate_23: (1.35, 1, 1.3),
+		truncate_24: (1.37, 1, 1.3),
+		truncate_25: (f64::INFINITY, 1, f64::INFINITY),
+		truncate_26: (f64::NAN, 1, f64::NAN),
+		truncate_27: (f64::NEG_INFINITY, 1, f64::NEG_INFINITY),
+		truncate_28: (-103., -1, -100.),
+		truncate_29: (-105., -1, -100.),
+		truncate_30: (-107., -1, -100.),
+		truncate_31: (-113., -1, -110.),
+		truncate_32: (-115., -1, -110.),
+		truncate_33: (-117., -1, -110.),
+		truncate_34: (-123., -1, -120.),
+		truncate_35: (-125., -1, -120.),
+		truncate_36: (-127., -1, -120.),
+		truncate_37: (-133., -1, -130.),
+		truncate_38: (-135., -1, -130.),
+		truncate_39: (-137., -1, -130.),
+		truncate_40: (103., -1, 100.),
+		truncate_41: (105., -1, 100.),
+		truncate_42: (107., -1, 100.),
+		truncate_43: (113., -1, 110.),
+		truncate_44: (115., -1, 110.),
+		truncate_45: (117., -1, 110.),
+		truncate_46: (123., -1, 120.),
+		truncate_47: (125., -1, 120.),
+		truncate_48: (127., -1, 120.),
+		truncate_49: (133., -1, 130.),
+		truncate_50: (135., -1, 130.),
+		truncate_51: (137., -1, 130.),
+		truncate_52: (f64::INFINITY, -1, f64::INFINITY),
+		truncate_53: (f64::NAN, -1, f64::NAN),
+		truncate_54: (f64::NEG_INFINITY, -1, f64::NEG_INFINITY),
+	]}
+
+	test_round! { super::ceil [
+		ceil_high_scale_1: (1.23, 18, 1.23),
+		ceil_high_scale_2: (-1.23, 18, -1.23),
+		ceil_high_scale_3: (1.25e-19, 18, 1e-18),
+		ceil_high_scale_4: (-1.25e-19, 18, -0.),
+		ceil_high_scale_5: (1.23, 19, 1.23),
+		ceil_high_scale_6: (-1.23, 19, -1.23),
+		ceil_high_scale_7: (1.25e-19, 19, 2e-19),
+		ceil_high_scale_8: (-1.25e-19, 19, -1e-19),
+		ceil_high_scale_9: (1.23, 25, 1.23),
+		ceil_high_scale_10: (-1.23, 25, -1.23),
+		ceil_high_scale_11: (123456.789, 25, 123456.789),
+		ceil_high_scale_12: (5e15, 25, 5e15),
+	]}
+
+	test_round! { super::floor [
+		floor_high_scale_1: (1.23, 18, 1.23),
+		floor_high_scale_2: (-1.23, 18, -1.23),
+		floor_high_scale_3: (1.25e-19, 18, 0.),
+		floor_high_scale_4: (-1.25e-19, 18, -1e-18),
+		floor_high_scale_5: (1.23, 19, 1.23),
+		floor_high_scale_6: (-1.23, 19, -1.23),
+		floor_high_scale_7: (1.25e-19, 19, 1e-19),
+		floor_high_scale_8: (-1.25e-19, 19, -2e-19),
+		floor_high_scale_9: (1.23, 25, 1.23),
+		floor_high_scale_10: (-1.23, 25, -1.23),
+		floor_high_scale_11: (123456.789, 25, 123456.789),
+		floor_high_scale_12: (5e15, 25, 5e15),
+	]}
+
+	test_round! { super::half_up [
+		half_up_high_scale_1: (1.23, 18, 1.23),
+		half_up_high_scale_2: (-1.23, 18, -1.23),
+		half_up_high_scale_3: (1.25e-19, 18, 0.),
+		half_up_high_scale_4: (-1.25e-19, 18, -0.),
+		half_up_high_scale_5: (1.23, 19, 1.23),
+		half_up_high_scale_6: (-1.23, 19, -1.23),
+		half_up_high_scale_7: (1.25e-19, 19, 1e-19),
+		half_up_high_scale_8: (-1.25e-19, 19, -1e-19),
+		half_up_high_scale_9: (1.23, 25, 1.23),
+		half_up_high_scale_10: (-1.23, 25, -1.23),
+		half_up_high_scale_11: (123456.789, 25, 123456.789),
+		half_up_high_scale_12: (5e15, 25, 5e15),
+	]}
+
+	test_round! { super::half_to_even [
+		half_to_even_high_scale_1: (1.23, 18, 1.23),
+		half_to_even_high_scale_2: (-1.23, 18, -1.23),
+		half_to_even_high_scale_3: (1.25e-19, 18, 0.),
+		half_to_even_high_scale_4: (-1.25e-19, 18, -0.),
+		half_to_even_high_scale_5: (1.23, 19, 1.23),
+		half_to_even_high_scale_6: (-1.23, 19, -1.23),
+		half_to_even_high_scale_7: (1.25e-19, 19, 1e-19),
+		half_to_even_high_scale_8: (-1.25e-19, 19, -1e-19),
+		half_to_even_high_scale_9: (1.23, 25, 1.23),
+		half_to_even_high_scale_10: (-1.23, 25, -1.23),
+		half_to_even_high_scale_11: (123456.789, 25, 123456.789),
+		half_to_even_high_scale_12: (5e15, 25, 5e15),
+	]}
+
+	test_round_f32! { ceil [
+		ceil_f32_1: (-1.03, 1, -1.),
+		ceil_f32_2: (-1.05, 1, -1.),
+		ceil_f32_3: (-1.07, 1, -1.),
+		ceil_f32_4: (-1.13, 1, -1.1),
+		ceil_f32_5: (-1.15, 1, -1.1),
+		ceil_f32_6: (-1.17, 1, -1.1),
+		ceil_f32_7: (-1.23, 1, -1.2),
+		ceil_f32_8: (-1.25, 1, -1.2),
+		ceil_f32_9: (-1.27, 1, -1.2),
+		ceil_f32_10: (-1.33, 1, -1.3),
+		ceil_f32_11: (-1.35, 1, -1.3),
+		ceil_f32_12: (-1.37, 1, -1.3),
+		ceil_f32_13: (1.03, 1, 1.1),
+		ceil_f32_14: (1.05, 1, 1.1),
+		ceil_f32_15: (1.07, 1, 1.1),
+		ceil_f32_16: (1.13, 1, 1.2),
+		ceil_f32_17: (1.15, 1, 1.2),
+		ceil_f32_18: (1.17, 1, 1.2),
+		ceil_f32_19: (1.23, 1, 1.3),
+		ceil_f32_20: (1.25, 1, 1.3),
+		ceil_f32_21: (1.27, 1, 1.3),
+		ceil_f32_22: (1.33, 1, 1.4),
+		ceil_f32_23: (1.35, 1, 1.4),
+		ceil_f32_24: (1.37, 1, 1.4),
+		ceil_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		ceil_f32_26: (f32::NAN, 1, f32::NAN),
+		ceil_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		ceil_f32_28: (-103., -1, -100.),
+		ceil_f32_29: (-105., -1, -100.),
+		ceil_f32_30: (-107., -1, -100.),
+		ceil_f32_31: (-113., -1, -110.),
+		ceil_f32_32: (-115., -1, -110.),
+		ceil_f32_33: (-117., -1, -110.),
+		ceil_f32_34: (-123., -1, -120.),
+		ceil_f32_35: (-125., -1, -120.),
+		ceil_f32_36: (-127., -1, -120.),
+		ceil_f32_37: (-133., -1, -130.),
+		ceil_f32_38: (-135., -1, -130.),
+		ceil_f32_39: (-137., -1, -130.),
+		ceil_f32_40: (103., -1, 110.),
+		ceil_f32_41: (105., -1, 110.),
+		ceil_f32_42: (107., -1, 110.),
+		ceil_f32_43: (113., -1, 120.),
+		ceil_f32_44: (115., -1, 120.),
+		ceil_f32_45: (117., -1, 120.),
+		ceil_f32_46: (123., -1, 130.),
+		ceil_f32_47: (125., -1, 130.),
+		ceil_f32_48: (127., -1, 130.),
+		ceil_f32_49: (133., -1, 140.),
+		ceil_f32_50: (135., -1, 140.),
+		ceil_f32_51: (137., -1, 140.),
+		ceil_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		ceil_f32_53: (f32::NAN, -1, f32::NAN),
+		ceil_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { floor [
+		floor_f32_1: (-1.03, 1, -1.1),
+		floor_f32_2: (-1.05, 1, -1.1),
+		floor_f32_3: (-1.07, 1, -1.1),
+		floor_f32_4: (-1.13, 1, -1.2),
+		floor_f32_5: (-1.15, 1, -1.2),
+		floor_f32_6: (-1.17, 1, -1.2),
+		floor_f32_7: (-1.23, 1, -1.3),
+		floor_f32_8: (-1.25, 1, -1.3),
+		floor_f32_9: (-1.27, 1, -1.3),
+		floor_f32_10: (-1.33, 1, -1.4),
+		floor_f32_11: (-1.35, 1, -1.4),
+		floor_f32_12: (-1.37, 1, -1.4),
+		floor_f32_13: (1.03, 1, 1.),
+		floor_f32_14: (1.05, 1, 1.),
+		floor_f32_15: (1.07, 1, 1.),
+		floor_f32_16: (1.13, 1, 1.1),
+		floor_f32_17: (1.15, 1, 1.1),
+		floor_f32_18: (1.17, 1, 1.1),
+		floor_f32_19: (1.23, 1, 1.2),
+		floor_f32_20: (1.25, 1, 1.2),
+		floor_f32_21: (1.27, 1, 1.2),
+		floor_f32_22: (1.33, 1, 1.3),
+		floor_f32_23: (1.35, 1, 1.3),
+		floor_f32_24: (1.37, 1, 1.3),
+		floor_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		floor_f32_26: (f32::NAN, 1, f32::NAN),
+		floor_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		floor_f32_28: (-103., -1, -110.),
+		floor_f32_29: (-105., -1, -110.),
+		floor_f32_30: (-107., -1, -110.),
+		floor_f32_31: (-113., -1, -120.),
+		floor_f32_32: (-115., -1, -120.),
+		floor_f32_33: (-117., -1, -120.),
+		floor_f32_34: (-123., -1, -130.),
+		floor_f32_35: (-125., -1, -130.),
+		floor_f32_36: (-127., -1, -130.),
+		floor_f32_37: (-133., -1, -140.),
+		floor_f32_38: (-135., -1, -140.),
+		floor_f32_39: (-137., -1, -140.),
+		floor_f32_40: (103., -1, 100.),
+		floor_f32_41: (105., -1, 100.),
+		floor_f32_42: (107., -1, 100.),
+		floor_f32_43: (113., -1, 110.),
+		floor_f32_44: (115., -1, 110.),
+		floor_f32_45: (117., -1, 110.),
+		floor_f32_46: (123., -1, 120.),
+		floor_f32_47: (125., -1, 120.),
+		floor_f32_48: (127., -1, 120.),
+		floor_f32_49: (133., -1, 130.),
+		floor_f32_50: (135., -1, 130.),
+		floor_f32_51: (137., -1, 130.),
+		floor_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		floor_f32_53: (f32::NAN, -1, f32::NAN),
+		floor_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { half_away_from_zero [
+		half_away_from_zero_f32_1: (-1.03, 1, -1.),
+		half_away_from_zero_f32_2: (-1.05, 1, -1.1),
+		half_away_from_zero_f32_3: (-1.07, 1, -1.1),
+		half_away_from_zero_f32_4: (-1.13, 1, -1.1),
+		half_away_from_zero_f32_5: (-1.15, 1, -1.2),
+		half_away_from_zero_f32_6: (-1.17, 1, -1.2),
+		half_away_from_zero_f32_7: (-1.23, 1, -1.2),
+		half_away_from_zero_f32_8: (-1.25, 1, -1.3),
+		half_away_from_zero_f32_9: (-1.27, 1, -1.3),
+		half_away_from_zero_f32_10: (-1.33, 1, -1.3),
+		half_away_from_zero_f32_11: (-1.35, 1, -1.4),
+		half_away_from_zero_f32_12: (-1.37, 1, -1.4),
+		half_away_from_zero_f32_13: (1.03, 1, 1.),
+		half_away_from_zero_f32_14: (1.05, 1, 1.1),
+		half_away_from_zero_f32_15: (1.07, 1, 1.1),
+		half_away_from_zero_f32_16: (1.13, 1, 1.1),
+		half_away_from_zero_f32_17: (1.15, 1, 1.2),
+		half_away_from_zero_f32_18: (1.17, 1, 1.2),
+		half_away_from_zero_f32_19: (1.23, 1, 1.2),
+		half_away_from_zero_f32_20: (1.25, 1, 1.3),
+		half_away_from_zero_f32_21: (1.27, 1, 1.3),
+		half_away_from_zero_f32_22: (1.33, 1, 1.3),
+		half_away_from_zero_f32_23: (1.35, 1, 1.4),
+		half_away_from_zero_f32_24: (1.37, 1, 1.4),
+		half_away_from_zero_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		half_away_from_zero_f32_26: (f32::NAN, 1, f32::NAN),
+		half_away_from_zero_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		half_away_from_zero_f32_28: (-103., -1, -100.),
+		half_away_from_zero_f32_29: (-105., -1, -110.),
+		half_away_from_zero_f32_30: (-107., -1, -110.),
+		half_away_from_zero_f32_31: (-113., -1, -110.),
+		half_away_from_zero_f32_32: (-115., -1, -120.),
+		half_away_from_zero_f32_33: (-117., -1, -120.),
+		half_away_from_zero_f32_34: (-123., -1, -120.),
+		half_away_from_zero_f32_35: (-125., -1, -130.),
+		half_away_from_zero_f32_36: (-127., -1, -130.),
+		half_away_from_zero_f32_37: (-133., -1, -130.),
+		half_away_from_zero_f32_38: (-135., -1, -140.),
+		half_away_from_zero_f32_39: (-137., -1, -140.),
+		half_away_from_zero_f32_40: (103., -1, 100.),
+		half_away_from_zero_f32_41: (105., -1, 110.),
+		half_away_from_zero_f32_42: (107., -1, 110.),
+		half_away_from_zero_f32_43: (113., -1, 110.),
+		half_away_from_zero_f32_44: (115., -1, 120.),
+		half_away_from_zero_f32_45: (117., -1, 120.),
+		half_away_from_zero_f32_46: (123., -1, 120.),
+		half_away_from_zero_f32_47: (125., -1, 130.),
+		half_away_from_zero_f32_48: (127., -1, 130.),
+		half_away_from_zero_f32_49: (133., -1, 130.),
+		half_away_from_zero_f32_50: (135., -1, 140.),
+		half_away_from_zero_f32_51: (137., -1, 140.),
+		half_away_from_zero_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		half_away_from_zero_f32_53: (f32::NAN, -1, f32::NAN),
+		half_away_from_zero_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { half_down [
+		half_down_f32_1: (-1.03, 1, -1.),
+		half_down_f32_2: (-1.05, 1, -1.1),
+		half_down_f32_3: (-1.07, 1, -1.1),
+		half_down_f32_4: (-1.13, 1, -1.1),
+		half_down_f32_5: (-1.15, 1, -1.2),
+		half_down_f32_6: (-1.17, 1, -1.2),
+		half_down_f32_7: (-1.23, 1, -1.2),
+		half_down_f32_8: (-1.25, 1, -1.3),
+		half_down_f32_9: (-1.27, 1, -1.3),
+		half_down_f32_10: (-1.33, 1, -1.3),
+		half_down_f32_11: (-1.35, 1, -1.4),
+		half_down_f32_12: (-1.37, 1, -1.4),
+		half_down_f32_13: (1.03, 1, 1.),
+		half_down_f32_14: (1.05, 1, 1.),
+		half_down_f32_15: (1.07, 1, 1.1),
+		half_down_f32_16: (1.13, 1, 1.1),
+		half_down_f32_17: (1.15, 1, 1.1),
+		half_down_f32_18: (1.17, 1, 1.2),
+		half_down_f32_19: (1.23, 1, 1.2),
+		half_down_f32_20: (1.25, 1, 1.2),
+		half_down_f32_21: (1.27, 1, 1.3),
+		half_down_f32_22: (1.33, 1, 1.3),
+		half_down_f32_23: (1.35, 1, 1.3),
+		half_down_f32_24: (1.37, 1, 1.4),
+		half_down_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		half_down_f32_26: (f32::NAN, 1, f32::NAN),
+		half_down_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		half_down_f32_28: (-103., -1, -100.),
+		half_down_f32_29: (-105., -1, -110.),
+		half_down_f32_30: (-107., -1, -110.),
+		half_down_f32_31: (-113., -1, -110.),
+		half_down_f32_32: (-115., -1, -120.),
+		half_down_f32_33: (-117., -1, -120.),
+		half_down_f32_34: (-123., -1, -120.),
+		half_down_f32_35: (-125., -1, -130.),
+		half_down_f32_36: (-127., -1, -130.),
+		half_down_f32_37: (-133., -1, -130.),
+		half_down_f32_38: (-135., -1, -140.),
+		half_down_f32_39: (-137., -1, -140.),
+		half_down_f32_40: (103., -1, 100.),
+		half_down_f32_41: (105., -1, 100.),
+		half_down_f32_42: (107., -1, 110.),
+		half_down_f32_43: (113., -1, 110.),
+		half_down_f32_44: (115., -1, 110.),
+		half_down_f32_45: (117., -1, 120.),
+		half_down_f32_46: (123., -1, 120.),
+		half_down_f32_47: (125., -1, 120.),
+		half_down_f32_48: (127., -1, 130.),
+		half_down_f32_49: (133., -1, 130.),
+		half_down_f32_50: (135., -1, 130.),
+		half_down_f32_51: (137., -1, 140.),
+		half_down_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		half_down_f32_53: (f32::NAN, -1, f32::NAN),
+		half_down_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { half_to_even [
+		half_to_even_f32_1: (-1.03, 1, -1.),
+		half_to_even_f32_2: (-1.05, 1, -1.),
+		half_to_even_f32_3: (-1.07, 1, -1.1),
+		half_to_even_f32_4: (-1.13, 1, -1.1),
+		half_to_even_f32_5: (-1.15, 1, -1.2),
+		half_to_even_f32_6: (-1.17, 1, -1.2),
+		half_to_even_f32_7: (-1.23, 1, -1.2),
+		half_to_even_f32_8: (-1.25, 1, -1.2),
+		half_to_even_f32_9: (-1.27, 1, -1.3),
+		half_to_even_f32_10: (-1.33, 1, -1.3),
+		half_to_even_f32_11: (-1.35, 1, -1.4),
+		half_to_even_f32_12: (-1.37, 1, -1.4),
+		half_to_even_f32_13: (1.03, 1, 1.),
+		half_to_even_f32_14: (1.05, 1, 1.),
+		half_to_even_f32_15: (1.07, 1, 1.1),
+		half_to_even_f32_16: (1.13, 1, 1.1),
+		half_to_even_f32_17: (1.15, 1, 1.2),
+		half_to_even_f32_18: (1.17, 1, 1.2),
+		half_to_even_f32_19: (1.23, 1, 1.2),
+		half_to_even_f32_20: (1.25, 1, 1.2),
+		half_to_even_f32_21: (1.27, 1, 1.3),
+		half_to_even_f32_22: (1.33, 1, 1.3),
+		half_to_even_f32_23: (1.35, 1, 1.4),
+		half_to_even_f32_24: (1.37, 1, 1.4),
+		half_to_even_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		half_to_even_f32_26: (f32::NAN, 1, f32::NAN),
+		half_to_even_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		half_to_even_f32_28: (2.221, 3, 2.221),
+		half_to_even_f32_29: (-103., -1, -100.),
+		half_to_even_f32_30: (-105., -1, -100.),
+		half_to_even_f32_31: (-107., -1, -110.),
+		half_to_even_f32_32: (-113., -1, -110.),
+		half_to_even_f32_33: (-115., -1, -120.),
+		half_to_even_f32_34: (-117., -1, -120.),
+		half_to_even_f32_35: (-123., -1, -120.),
+		half_to_even_f32_36: (-125., -1, -120.),
+		half_to_even_f32_37: (-127., -1, -130.),
+		half_to_even_f32_38: (-133., -1, -130.),
+		half_to_even_f32_39: (-135., -1, -140.),
+		half_to_even_f32_40: (-137., -1, -140.),
+		half_to_even_f32_41: (103., -1, 100.),
+		half_to_even_f32_42: (105., -1, 100.),
+		half_to_even_f32_43: (107., -1, 110.),
+		half_to_even_f32_44: (113., -1, 110.),
+		half_to_even_f32_45: (115., -1, 120.),
+		half_to_even_f32_46: (117., -1, 120.),
+		half_to_even_f32_47: (123., -1, 120.),
+		half_to_even_f32_48: (125., -1, 120.),
+		half_to_even_f32_49: (127., -1, 130.),
+		half_to_even_f32_50: (133., -1, 130.),
+		half_to_even_f32_51: (135., -1, 140.),
+		half_to_even_f32_52: (137., -1, 140.),
+		half_to_even_f32_53: (f32::INFINITY, -1, f32::INFINITY),
+		half_to_even_f32_54: (f32::NAN, -1, f32::NAN),
+		half_to_even_f32_55: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { half_to_odd [
+		half_to_odd_f32_1: (-1.03, 1, -1.),
+		half_to_odd_f32_2: (-1.05, 1, -1.1),
+		half_to_odd_f32_3: (-1.07, 1, -1.1),
+		half_to_odd_f32_4: (-1.13, 1, -1.1),
+		half_to_odd_f32_5: (-1.15, 1, -1.1),
+		half_to_odd_f32_6: (-1.17, 1, -1.2),
+		half_to_odd_f32_7: (-1.23, 1, -1.2),
+		half_to_odd_f32_8: (-1.25, 1, -1.3),
+		half_to_odd_f32_9: (-1.27, 1, -1.3),
+		half_to_odd_f32_10: (-1.33, 1, -1.3),
+		half_to_odd_f32_11: (-1.35, 1, -1.3),
+		half_to_odd_f32_12: (-1.37, 1, -1.4),
+		half_to_odd_f32_13: (1.03, 1, 1.),
+		half_to_odd_f32_14: (1.05, 1, 1.1),
+		half_to_odd_f32_15: (1.07, 1, 1.1),
+		half_to_odd_f32_16: (1.13, 1, 1.1),
+		half_to_odd_f32_17: (1.15, 1, 1.1),
+		half_to_odd_f32_18: (1.17, 1, 1.2),
+		half_to_odd_f32_19: (1.23, 1, 1.2),
+		half_to_odd_f32_20: (1.25, 1, 1.3),
+		half_to_odd_f32_21: (1.27, 1, 1.3),
+		half_to_odd_f32_22: (1.33, 1, 1.3),
+		half_to_odd_f32_23: (1.35, 1, 1.3),
+		half_to_odd_f32_24: (1.37, 1, 1.4),
+		half_to_odd_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		half_to_odd_f32_26: (f32::NAN, 1, f32::NAN),
+		half_to_odd_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		half_to_odd_f32_28: (-103., -1, -100.),
+		half_to_odd_f32_29: (-105., -1, -110.),
+		half_to_odd_f32_30: (-107., -1, -110.),
+		half_to_odd_f32_31: (-113., -1, -110.),
+		half_to_odd_f32_32: (-115., -1, -110.),
+		half_to_odd_f32_33: (-117., -1, -120.),
+		half_to_odd_f32_34: (-123., -1, -120.),
+		half_to_odd_f32_35: (-125., -1, -130.),
+		half_to_odd_f32_36: (-127., -1, -130.),
+		half_to_odd_f32_37: (-133., -1, -130.),
+		half_to_odd_f32_38: (-135., -1, -130.),
+		half_to_odd_f32_39: (-137., -1, -140.),
+		half_to_odd_f32_40: (103., -1, 100.),
+		half_to_odd_f32_41: (105., -1, 110.),
+		half_to_odd_f32_42: (107., -1, 110.),
+		half_to_odd_f32_43: (113., -1, 110.),
+		half_to_odd_f32_44: (115., -1, 110.),
+		half_to_odd_f32_45: (117., -1, 120.),
+		half_to_odd_f32_46: (123., -1, 120.),
+		half_to_odd_f32_47: (125., -1, 130.),
+		half_to_odd_f32_48: (127., -1, 130.),
+		half_to_odd_f32_49: (133., -1, 130.),
+		half_to_odd_f32_50: (135., -1, 130.),
+		half_to_odd_f32_51: (137., -1, 140.),
+		half_to_odd_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		half_to_odd_f32_53: (f32::NAN, -1, f32::NAN),
+		half_to_odd_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { half_towards_zero [
+		half_towards_zero_f32_1: (-1.03, 1, -1.),
+		half_towards_zero_f32_2: (-1.05, 1, -1.),
+		half_towards_zero_f32_3: (-1.07, 1, -1.1),
+		half_towards_zero_f32_4: (-1.13, 1, -1.1),
+		half_towards_zero_f32_5: (-1.15, 1, -1.1),
+		half_towards_zero_f32_6: (-1.17, 1, -1.2),
+		half_towards_zero_f32_7: (-1.23, 1, -1.2),
+		half_towards_zero_f32_8: (-1.25, 1, -1.2),
+		half_towards_zero_f32_9: (-1.27, 1, -1.3),
+		half_towards_zero_f32_10: (-1.33, 1, -1.3),
+		half_towards_zero_f32_11: (-1.35, 1, -1.3),
+		half_towards_zero_f32_12: (-1.37, 1, -1.4),
+		half_towards_zero_f32_13: (1.03, 1, 1.),
+		half_towards_zero_f32_14: (1.05, 1, 1.),
+		half_towards_zero_f32_15: (1.07, 1, 1.1),
+		half_towards_zero_f32_16: (1.13, 1, 1.1),
+		half_towards_zero_f32_17: (1.15, 1, 1.1),
+		half_towards_zero_f32_18: (1.17, 1, 1.2),
+		half_towards_zero_f32_19: (1.23, 1, 1.2),
+		half_towards_zero_f32_20: (1.25, 1, 1.2),
+		half_towards_zero_f32_21: (1.27, 1, 1.3),
+		half_towards_zero_f32_22: (1.33, 1, 1.3),
+		half_towards_zero_f32_23: (1.35, 1, 1.3),
+		half_towards_zero_f32_24: (1.37, 1, 1.4),
+		half_towards_zero_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		half_towards_zero_f32_26: (f32::NAN, 1, f32::NAN),
+		half_towards_zero_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		half_towards_zero_f32_28: (-103., -1, -100.),
+		half_towards_zero_f32_29: (-105., -1, -100.),
+		half_towards_zero_f32_30: (-107., -1, -110.),
+		half_towards_zero_f32_31: (-113., -1, -110.),
+		half_towards_zero_f32_32: (-115., -1, -110.),
+		half_towards_zero_f32_33: (-117., -1, -120.),
+		half_towards_zero_f32_34: (-123., -1, -120.),
+		half_towards_zero_f32_35: (-125., -1, -120.),
+		half_towards_zero_f32_36: (-127., -1, -130.),
+		half_towards_zero_f32_37: (-133., -1, -130.),
+		half_towards_zero_f32_38: (-135., -1, -130.),
+		half_towards_zero_f32_39: (-137., -1, -140.),
+		half_towards_zero_f32_40: (103., -1, 100.),
+		half_towards_zero_f32_41: (105., -1, 100.),
+		half_towards_zero_f32_42: (107., -1, 110.),
+		half_towards_zero_f32_43: (113., -1, 110.),
+		half_towards_zero_f32_44: (115., -1, 110.),
+		half_towards_zero_f32_45: (117., -1, 120.),
+		half_towards_zero_f32_46: (123., -1, 120.),
+		half_towards_zero_f32_47: (125., -1, 120.),
+		half_towards_zero_f32_48: (127., -1, 130.),
+		half_towards_zero_f32_49: (133., -1, 130.),
+		half_towards_zero_f32_50: (135., -1, 130.),
+		half_towards_zero_f32_51: (137., -1, 140.),
+		half_towards_zero_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		half_towards_zero_f32_53: (f32::NAN, -1, f32::NAN),
+		half_towards_zero_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { half_up [
+		half_up_f32_1: (-1.03, 1, -1.),
+		half_up_f32_2: (-1.05, 1, -1.),
+		half_up_f32_3: (-1.07, 1, -1.1),
+		half_up_f32_4: (-1.13, 1, -1.1),
+		half_up_f32_5: (-1.15, 1, -1.1),
+		half_up_f32_6: (-1.17, 1, -1.2),
+		half_up_f32_7: (-1.23, 1, -1.2),
+		half_up_f32_8: (-1.25, 1, -1.2),
+		half_up_f32_9: (-1.27, 1, -1.3),
+		half_up_f32_10: (-1.33, 1, -1.3),
+		half_up_f32_11: (-1.35, 1, -1.3),
+		half_up_f32_12: (-1.37, 1, -1.4),
+		half_up_f32_13: (1.03, 1, 1.),
+		half_up_f32_14: (1.05, 1, 1.1),
+		half_up_f32_15: (1.07, 1, 1.1),
+		half_up_f32_16: (1.13, 1, 1.1),
+		half_up_f32_17: (1.15, 1, 1.2),
+		half_up_f32_18: (1.17, 1, 1.2),
+		half_up_f32_19: (1.23, 1, 1.2),
+		half_up_f32_20: (1.25, 1, 1.3),
+		half_up_f32_21: (1.27, 1, 1.3),
+		half_up_f32_22: (1.33, 1, 1.3),
+		half_up_f32_23: (1.35, 1, 1.4),
+		half_up_f32_24: (1.37, 1, 1.4),
+		half_up_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		half_up_f32_26: (f32::NAN, 1, f32::NAN),
+		half_up_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		half_up_f32_28: (-103., -1, -100.),
+		half_up_f32_29: (-105., -1, -100.),
+		half_up_f32_30: (-107., -1, -110.),
+		half_up_f32_31: (-113., -1, -110.),
+		half_up_f32_32: (-115., -1, -110.),
+		half_up_f32_33: (-117., -1, -120.),
+		half_up_f32_34: (-123., -1, -120.),
+		half_up_f32_35: (-125., -1, -120.),
+		half_up_f32_36: (-127., -1, -130.),
+		half_up_f32_37: (-133., -1, -130.),
+		half_up_f32_38: (-135., -1, -130.),
+		half_up_f32_39: (-137., -1, -140.),
+		half_up_f32_40: (103., -1, 100.),
+		half_up_f32_41: (105., -1, 110.),
+		half_up_f32_42: (107., -1, 110.),
+		half_up_f32_43: (113., -1, 110.),
+		half_up_f32_44: (115., -1, 120.),
+		half_up_f32_45: (117., -1, 120.),
+		half_up_f32_46: (123., -1, 120.),
+		half_up_f32_47: (125., -1, 130.),
+		half_up_f32_48: (127., -1, 130.),
+		half_up_f32_49: (133., -1, 130.),
+		half_up_f32_50: (135., -1, 140.),
+		half_up_f32_51: (137., -1, 140.),
+		half_up_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		half_up_f32_53: (f32::NAN, -1, f32::NAN),
+		half_up_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
+	]}
+
+	test_round_f32! { truncate [
+		truncate_f32_1: (-1.03, 1, -1.),
+		truncate_f32_2: (-1.05, 1, -1.),
+		truncate_f32_3: (-1.07, 1, -1.),
+		truncate_f32_4: (-1.13, 1, -1.1),
+		truncate_f32_5: (-1.15, 1, -1.1),
+		truncate_f32_6: (-1.17, 1, -1.1),
+		truncate_f32_7: (-1.23, 1, -1.2),
+		truncate_f32_8: (-1.25, 1, -1.2),
+		truncate_f32_9: (-1.27, 1, -1.2),
+		truncate_f32_10: (-1.33, 1, -1.3),
+		truncate_f32_11: (-1.35, 1, -1.3),
+		truncate_f32_12: (-1.37, 1, -1.3),
+		truncate_f32_13: (1.03, 1, 1.),
+		truncate_f32_14: (1.05, 1, 1.),
+		truncate_f32_15: (1.07, 1, 1.),
+		truncate_f32_16: (1.13, 1, 1.1),
+		truncate_f32_17: (1.15, 1, 1.1),
+		truncate_f32_18: (1.17, 1, 1.1),
+		truncate_f32_19: (1.23, 1, 1.2),
+		truncate_f32_20: (1.25, 1, 1.2),
+		truncate_f32_21: (1.27, 1, 1.2),
+		truncate_f32_22: (1.33, 1, 1.3),
+		truncate_f32_23: (1.35, 1, 1.3),
+		truncate_f32_24: (1.37, 1, 1.3),
+		truncate_f32_25: (f32::INFINITY, 1, f32::INFINITY),
+		truncate_f32_26: (f32::NAN, 1, f32::NAN),
+		truncate_f32_27: (f32::NEG_INFINITY, 1, f32::NEG_INFINITY),
+		truncate_f32_28: (-103., -1, -100.),
+		truncate_f32_29: (-105., -1, -100.),
+		truncate_f32_30: (-107., -1, -100.),
+		truncate_f32_31: (-113., -1, -110.),
+		truncate_f32_32: (-115., -1, -110.),
+		truncate_f32_33: (-117., -1, -110.),
+		truncate_f32_34: (-123., -1, -120.),
+		truncate_f32_35: (-125., -1, -120.),
+		truncate_f32_36: (-127., -1, -120.),
+		truncate_f32_37: (-133., -1, -130.),
+		truncate_f32_38: (-135., -1, -130.),
+		truncate_f32_39: (-137., -1, -130.),
+		truncate_f32_40: (103., -1, 100.),
+		truncate_f32_41: (105., -1, 100.),
+		truncate_f32_42: (107., -1, 100.),
+		truncate_f32_43: (113., -1, 110.),
+		truncate_f32_44: (115., -1, 110.),
+		truncate_f32_45: (117., -1, 110.),
+		truncate_f32_46: (123., -1, 120.),
+		truncate_f32_47: (125., -1, 120.),
+		truncate_f32_48: (127., -1, 120.),
+		truncate_f32_49: (133., -1, 130.),
+		truncate_f32_50: (135., -1, 130.),
+		truncate_f32_51: (137., -1, 130.),
+		truncate_f32_52: (f32::INFINITY, -1, f32::INFINITY),
+		truncate_f32_53: (f32::NAN, -1, f32::NAN),
+		truncate_f32_54: (f32::NEG_INFINITY, -1, f32::NEG_INFINITY),
 	]}
 
 	test_digits! { super::significant_digits [
@@ -859,9 +3010,9 @@ mod tests {
 		significant_digits_21: (-1.15, 1, (1, 5)),
 		significant_digits_22: (1.15, 1, (1, 5)),
 		significant_digits_23: (1.9999, 3, (9, 9)),
-		significant_digits_24: (INFINITY, 1, (0, 0)),
-		significant_digits_25: (NAN, 1, (0, 0)),
-		significant_digits_26: (NEG_INFINITY, 1, (0, 0)),
+		significant_digits_24: (f64::INFINITY, 1, (0, 0)),
+		significant_digits_25: (f64::NAN, 1, (0, 0)),
+		significant_digits_26: (f64::NEG_INFINITY, 1, (0, 0)),
 		significant_digits_27: (-1234567890., 0, (0, 0)),
 		significant_digits_28: (-1234567890., -1, (9, 0)),
 		significant_digits_29: (-1234567890., -2, (8, 9)),
@@ -882,9 +3033,9 @@ mod tests {
 		significant_digits_44: (1234567890., -7, (3, 4)),
 		significant_digits_45: (1234567890., -8, (2, 3)),
 		significant_digits_46: (1234567890., -9, (1, 2)),
-		significant_digits_47: (INFINITY, -1, (0, 0)),
-		significant_digits_48: (NAN, -1, (0, 0)),
-		significant_digits_49: (NEG_INFINITY, -1, (0, 0)),
+		significant_digits_47: (f64::INFINITY, -1, (0, 0)),
+		significant_digits_48: (f64::NAN, -1, (0, 0)),
+		significant_digits_49: (f64::NEG_INFINITY, -1, (0, 0)),
 		significant_digits_50: (0.011, 2, (1, 1)),
 		significant_digits_51: (0.014, 2, (1, 4)),
 		significant_digits_52: (0.017, 2, (1, 7)),
@@ -902,4 +3053,964 @@ mod tests {
 		significant_digits_64: (-1.017, 2, (1, 7)),
 		significant_digits_65: (-1.019, 2, (1, 9)),
 	]}
+
+	#[test]
+	fn compare_modes_tie() {
+		let result = super::compare_modes(1.25, 1, RoundingMode::HalfUp, RoundingMode::HalfToEven);
+		assert_eq!(result, (1.3, 1.2, true));
+	}
+
+	#[test]
+	fn compare_modes_no_tie() {
+		let result = super::compare_modes(1.27, 1, RoundingMode::HalfUp, RoundingMode::HalfToEven);
+		assert_eq!(result, (1.3, 1.3, false));
+	}
+
+	#[test]
+	fn compare_modes_non_finite() {
+		let result = super::compare_modes(f64::NAN, 1, RoundingMode::HalfUp, RoundingMode::HalfToEven);
+		assert!(result.0.is_nan() && result.1.is_nan() && !result.2);
+		let result = super::compare_modes(f64::INFINITY, 1, RoundingMode::Ceil, RoundingMode::Floor);
+		assert_eq!(result, (f64::INFINITY, f64::INFINITY, false));
+		let result = super::compare_modes(f64::NEG_INFINITY, 1, RoundingMode::Ceil, RoundingMode::Floor);
+		assert_eq!(result, (f64::NEG_INFINITY, f64::NEG_INFINITY, false));
+	}
+
+	#[test]
+	fn snap_to_grid_between_lines() {
+		assert_eq!(super::snap_to_grid(7.3, 4.9, 2.5, 2.), (7.5, 4.));
+		assert_eq!(super::snap_to_grid(1.25, 3., 0.5, 2.), (1.5, 4.));
+	}
+
+	#[test]
+	fn snap_to_grid_on_line() {
+		assert_eq!(super::snap_to_grid(5., 4., 2.5, 2.), (5., 4.));
+	}
+
+	#[test]
+	fn snap_to_grid_negative() {
+		assert_eq!(super::snap_to_grid(-7.3, -1.1, 2.5, 2.), (-7.5, -2.));
+	}
+
+	#[test]
+	fn snap_to_grid_spacing() {
+		assert_eq!(super::snap_to_grid(13., 13., 10., 4.), (10., 12.));
+		let snapped = super::snap_to_grid(1., 1., 0., 1.);
+		assert!(snapped.0.is_nan() && snapped.1.is_nan());
+		let snapped = super::snap_to_grid(1., 1., 1., -1.);
+		assert!(snapped.0.is_nan() && snapped.1.is_nan());
+	}
+
+	#[test]
+	fn half_to_even_ties() {
+		for n in -51..51 {
+			let value = n as f64 + 0.5;
+			let result = super::half_to_even(value, 0);
+			assert_eq!((result - value).abs(), 0.5);
+			assert_eq!(result % 2., 0.);
+			assert_eq!(super::half_to_even(-value, 0), -result);
+		}
+	}
+
+	#[test]
+	fn half_to_odd_ties() {
+		for n in -51..51 {
+			let value = n as f64 + 0.5;
+			let result = super::half_to_odd(value, 0);
+			assert_eq!((result - value).abs(), 0.5);
+			assert_eq!(result.abs() % 2., 1.);
+			assert_eq!(super::half_to_odd(-value, 0), -result);
+		}
+	}
+
+	#[test]
+	fn half_to_even_no_tie() {
+		assert_eq!(super::half_to_even(4.6, 0), 5.);
+		assert_eq!(super::half_to_even(-4.6, 0), -5.);
+		assert_eq!(super::half_to_odd(4.6, 0), 5.);
+		assert_eq!(super::half_to_odd(-4.6, 0), -5.);
+	}
+
+	#[test]
+	fn to_nearest_representation() {
+		// 4.56 * 100 and 1.13 * 100 land just below and above an integer
+		assert_eq!(super::half_up(4.56, 2), 4.56);
+		assert_eq!(super::half_down(4.56, 2), 4.56);
+		assert_eq!(super::half_to_even(-4.56, 2), -4.56);
+		assert_eq!(super::half_away_from_zero(1.13, 2), 1.13);
+		assert_eq!(super::half_towards_zero(-1.13, 2), -1.13);
+	}
+
+	#[test]
+	fn half_to_even_large() {
+		assert_eq!(super::half_to_even(1e17 + 16., -1), 1e17 + 20.);
+		assert_eq!(super::half_to_even(1e12 + 6.5, 0), 1e12 + 6.);
+		assert_eq!(super::half_to_odd(1e12 + 6.5, 0), 1e12 + 7.);
+	}
+
+	#[test]
+	fn reconcile_sum_discrepancy() {
+		let (rounded, sum, total) = super::reconcile_sum(&[0.335, 0.335, 0.335], 2, RoundingMode::HalfUp);
+		assert_eq!(rounded, vec![0.34, 0.34, 0.34]);
+		assert_eq!(sum, 1.02);
+		assert_eq!(total, 1.01);
+	}
+
+	#[test]
+	fn reconcile_sum_agreement() {
+		let (rounded, sum, total) = super::reconcile_sum(&[1.25, 2.5, 0.1, 0.2], 1, RoundingMode::HalfUp);
+		assert_eq!(rounded, vec![1.3, 2.5, 0.1, 0.2]);
+		assert_eq!(sum, 4.1);
+		assert_eq!(total, 4.1);
+	}
+
+	#[test]
+	fn reconcile_sum_empty() {
+		assert_eq!(super::reconcile_sum(&[], 2, RoundingMode::HalfUp), (vec![], 0., 0.));
+	}
+
+	#[test]
+	fn max_exact_scale() {
+		assert_eq!(super::max_exact_scale(0.), 22);
+		assert_eq!(super::max_exact_scale(1e-10), 22);
+		assert_eq!(super::max_exact_scale(1.), 15);
+		assert_eq!(super::max_exact_scale(-1.), 15);
+		assert_eq!(super::max_exact_scale(1000.), 12);
+		assert_eq!(super::max_exact_scale(1e15), 0);
+		assert_eq!(super::max_exact_scale(1e300), 0);
+		assert_eq!(super::max_exact_scale(f64::NAN), 0);
+		assert_eq!(super::max_exact_scale(f64::INFINITY), 0);
+	}
+
+	#[test]
+	fn max_exact_scale_shrinks() {
+		let mut previous = super::max_exact_scale(1e-3);
+		for e in -2..16 {
+			let scale = super::max_exact_scale(10f64.powi(e));
+			assert!(scale < previous);
+			previous = scale;
+		}
+	}
+
+	#[test]
+	fn ulp() {
+		assert_eq!(super::ulp(1.), f64::EPSILON);
+		assert_eq!(super::ulp(-1.), f64::EPSILON);
+		assert_eq!(super::ulp(2.), 2. * f64::EPSILON);
+		assert_eq!(super::ulp(0.), f64::from_bits(1));
+		assert_eq!(super::ulp(2f64.powi(53)), 2.);
+		assert!(super::ulp(f64::MAX).is_finite());
+		assert!(super::ulp(f64::NAN).is_nan());
+		assert_eq!(super::ulp(f64::NEG_INFINITY), f64::INFINITY);
+	}
+
+	#[test]
+	fn ulp_grows() {
+		let mut previous = super::ulp(1e-300);
+		for e in -299..300 {
+			let ulp = super::ulp(10f64.powi(e));
+			assert!(ulp >= previous);
+			previous = ulp;
+		}
+		assert!(super::ulp(1e10) > super::ulp(1.));
+	}
+
+	#[test]
+	fn floor_to_bucket() {
+		assert_eq!(super::floor_to_bucket(1000, 60), 960);
+		assert_eq!(super::floor_to_bucket(960, 60), 960);
+		assert_eq!(super::floor_to_bucket(0, 60), 0);
+		assert_eq!(super::floor_to_bucket(-1, 60), -60);
+		assert_eq!(super::floor_to_bucket(-1000, 60), -1020);
+		assert_eq!(super::floor_to_bucket(-960, 60), -960);
+	}
+
+	#[test]
+	fn ceil_to_bucket() {
+		assert_eq!(super::ceil_to_bucket(1000, 60), 1020);
+		assert_eq!(super::ceil_to_bucket(960, 60), 960);
+		assert_eq!(super::ceil_to_bucket(0, 60), 0);
+		assert_eq!(super::ceil_to_bucket(-1, 60), 0);
+		assert_eq!(super::ceil_to_bucket(-1000, 60), -960);
+		assert_eq!(super::ceil_to_bucket(-960, 60), -960);
+	}
+
+	#[test]
+	fn to_bucket_bounds() {
+		assert_eq!(super::floor_to_bucket(i64::MIN, 1000), i64::MIN);
+		assert_eq!(super::floor_to_bucket(i64::MIN + 1000, 1000), i64::MIN + 808);
+		assert_eq!(super::floor_to_bucket(i64::MAX, 1000), i64::MAX - 807);
+		assert_eq!(super::floor_to_bucket(i64::MIN, 2), i64::MIN);
+		assert_eq!(super::ceil_to_bucket(i64::MAX, 1000), i64::MAX);
+		assert_eq!(super::ceil_to_bucket(i64::MAX - 807, 1000), i64::MAX - 807);
+		assert_eq!(super::ceil_to_bucket(i64::MIN, 1000), i64::MIN + 808);
+		assert_eq!(super::ceil_to_bucket(i64::MAX, i64::MAX), i64::MAX);
+		assert_eq!(super::ceil_to_bucket(1, i64::MAX), i64::MAX);
+	}
+
+	#[test]
+	#[should_panic]
+	fn floor_to_bucket_zero() {
+		super::floor_to_bucket(1000, 0);
+	}
+
+	#[test]
+	#[should_panic]
+	fn ceil_to_bucket_negative() {
+		super::ceil_to_bucket(1000, -60);
+	}
+
+	#[test]
+	fn round_bits() {
+		assert_eq!(super::round_bits(0.1, 4, RoundingMode::HalfUp), 0.125);
+		assert_eq!(super::round_bits(0.3, 2, RoundingMode::HalfUp), 0.25);
+		assert_eq!(super::round_bits(0.3, 2, RoundingMode::Ceil), 0.5);
+		assert_eq!(super::round_bits(0.7, 0, RoundingMode::Floor), 0.);
+		assert_eq!(super::round_bits(2.71, 8, RoundingMode::HalfUp), 694. / 256.);
+	}
+
+	#[test]
+	fn round_bits_tie() {
+		assert_eq!(super::round_bits(0.03125, 4, RoundingMode::HalfUp), 0.0625);
+		assert_eq!(super::round_bits(0.03125, 4, RoundingMode::HalfDown), 0.);
+		assert_eq!(super::round_bits(0.09375, 4, RoundingMode::HalfToEven), 0.125);
+		assert_eq!(super::round_bits(0.09375, 4, RoundingMode::HalfToOdd), 0.0625);
+	}
+
+	#[test]
+	fn round_bits_negative() {
+		assert_eq!(super::round_bits(-0.1, 4, RoundingMode::HalfUp), -0.125);
+		assert_eq!(super::round_bits(-0.03125, 4, RoundingMode::HalfUp), 0.);
+		assert_eq!(super::round_bits(-0.03125, 4, RoundingMode::HalfAwayFromZero), -0.0625);
+	}
+
+	#[test]
+	fn round_bits_non_finite() {
+		assert!(super::round_bits(f64::NAN, 4, RoundingMode::HalfUp).is_nan());
+		assert_eq!(super::round_bits(f64::INFINITY, 4, RoundingMode::HalfUp), f64::INFINITY);
+		assert_eq!(super::round_bits(1e300, 255, RoundingMode::HalfUp), 1e300);
+	}
+
+	#[test]
+	fn truncate_bits() {
+		assert_eq!(super::truncate_bits(0.3, 2), 0.25);
+		assert_eq!(super::truncate_bits(-0.3, 2), -0.25);
+		assert_eq!(super::truncate_bits(0.9999, 3), 0.875);
+		assert_eq!(super::truncate_bits(-0.9999, 0), 0.);
+		assert!(super::truncate_bits(f64::NAN, 2).is_nan());
+		assert_eq!(super::truncate_bits(f64::NEG_INFINITY, 2), f64::NEG_INFINITY);
+	}
+
+	#[test]
+	fn sig_figs_checked_carry() {
+		assert_eq!(super::sig_figs_checked(9.96, 2), (10., true));
+		assert_eq!(super::sig_figs_checked(999.5, 3), (1000., true));
+		assert_eq!(super::sig_figs_checked(-9.96, 2), (-10., true));
+		assert_eq!(super::sig_figs_checked(0.0999, 2), (0.1, true));
+	}
+
+	#[test]
+	fn sig_figs_checked_no_carry() {
+		assert_eq!(super::sig_figs_checked(1234.5, 2), (1200., false));
+		assert_eq!(super::sig_figs_checked(9.94, 2), (9.9, false));
+		assert_eq!(super::sig_figs_checked(0.012345, 3), (0.0123, false));
+		assert_eq!(super::sig_figs_checked(-56789., 1), (-60000., false));
+	}
+
+	#[test]
+	fn sig_figs_checked_special() {
+		assert_eq!(super::sig_figs_checked(0., 3), (0., false));
+		assert_eq!(super::sig_figs_checked(f64::INFINITY, 3), (f64::INFINITY, false));
+		assert!(super::sig_figs_checked(f64::NAN, 3).0.is_nan());
+		assert!(super::sig_figs_checked(1., 0).0.is_nan());
+	}
+
+	#[test]
+	fn midpoint() {
+		assert_eq!(super::midpoint(2., 4.), 3.);
+		assert_eq!(super::midpoint(1., 2.), 1.5);
+		assert_eq!(super::midpoint(-3., 8.), 2.5);
+		assert_eq!(super::midpoint(-7., 7.), 0.);
+		assert_eq!(super::midpoint(5.5, 5.5), 5.5);
+		assert_eq!(super::midpoint(f64::MIN_POSITIVE, 0.), f64::MIN_POSITIVE / 2.);
+		assert!(super::midpoint(f64::NAN, 1.).is_nan());
+	}
+
+	#[test]
+	fn midpoint_no_overflow() {
+		let a = f64::MAX;
+		let b = f64::MAX / 2.;
+		assert_eq!(a + b, f64::INFINITY);
+		assert_eq!(super::midpoint(a, b), f64::MAX * 0.75);
+		assert_eq!(super::midpoint(f64::MAX, f64::MAX), f64::MAX);
+		assert_eq!(super::midpoint(-f64::MAX, -f64::MAX), -f64::MAX);
+		assert_eq!(super::midpoint(f64::MAX, -f64::MAX), 0.);
+	}
+
+	#[test]
+	fn to_engineering() {
+		assert_eq!(super::to_engineering(12345., 3), (12.3, 3));
+		assert_eq!(super::to_engineering(0.0022, 2), (2.2, -3));
+		assert_eq!(super::to_engineering(999.7, 3), (1., 3));
+		assert_eq!(super::to_engineering(-4.7e-8, 2), (-47., -9));
+		assert_eq!(super::to_engineering(123456789., 4), (123.5, 6));
+		assert_eq!(super::to_engineering(5., 1), (5., 0));
+		assert_eq!(super::to_engineering(0.5, 1), (500., -3));
+		assert_eq!(super::to_engineering(0., 3), (0., 0));
+		assert_eq!(super::to_engineering(f64::INFINITY, 3), (f64::INFINITY, 0));
+		assert!(super::to_engineering(1., 0).0.is_nan());
+	}
+
+	#[test]
+	fn to_engineering_range() {
+		for &x in [1.5e-20, 7e-13, 3.3e-4, 0.25, 42., 6.02e23, 9.9e100].iter() {
+			let (mantissa, exponent) = super::to_engineering(x, 6);
+			assert!((1. ..1000.).contains(&mantissa));
+			assert_eq!(exponent % 3, 0);
+			assert!(((mantissa * 10f64.powi(exponent) - x) / x).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn to_si_string() {
+		assert_eq!(super::to_si_string(12345., 3), "12.3k");
+		assert_eq!(super::to_si_string(0.0022, 2), "2.2m");
+		assert_eq!(super::to_si_string(1000., 3), "1.00k");
+		assert_eq!(super::to_si_string(-4.7e-8, 2), "-47n");
+		assert_eq!(super::to_si_string(3.3e-6, 2), "3.3µ");
+		assert_eq!(super::to_si_string(250., 2), "250");
+		assert_eq!(super::to_si_string(1.2e24, 2), "1.2Y");
+		assert_eq!(super::to_si_string(4.56e-24, 3), "4.56y");
+	}
+
+	#[test]
+	fn to_si_string_scientific() {
+		assert_eq!(super::to_si_string(1.5e30, 2), "1.5e30");
+		assert_eq!(super::to_si_string(-2e-30, 3), "-2.00e-30");
+		assert_eq!(super::to_si_string(999.9e24, 3), "1.00e27");
+	}
+
+	#[test]
+	fn to_si_string_special() {
+		assert_eq!(super::to_si_string(0., 3), "0");
+		assert_eq!(super::to_si_string(f64::INFINITY, 3), "inf");
+		assert_eq!(super::to_si_string(f64::NAN, 3), "NaN");
+		assert_eq!(super::to_si_string(1., 0), "NaN");
+	}
+
+	#[test]
+	fn shortest_round_trip_scale() {
+		assert_eq!(super::shortest_round_trip_scale(0.1), 1);
+		assert_eq!(super::shortest_round_trip_scale(-2.75), 2);
+		assert_eq!(super::shortest_round_trip_scale(0.3333333333333333), 16);
+		assert_eq!(super::shortest_round_trip_scale(1. / 3.), 16);
+		assert_eq!(super::shortest_round_trip_scale(0.1 + 0.2), 17);
+		assert_eq!(super::shortest_round_trip_scale(1e-5), 5);
+		assert_eq!(super::shortest_round_trip_scale(1.5e-20), 21);
+		assert_eq!(super::shortest_round_trip_scale(f64::MIN_POSITIVE), u8::MAX);
+	}
+
+	#[test]
+	fn shortest_round_trip_scale_integer() {
+		assert_eq!(super::shortest_round_trip_scale(0.), 0);
+		assert_eq!(super::shortest_round_trip_scale(42.), 0);
+		assert_eq!(super::shortest_round_trip_scale(-1e20), 0);
+		assert_eq!(super::shortest_round_trip_scale(f64::MAX), 0);
+		assert_eq!(super::shortest_round_trip_scale(f64::NAN), 0);
+		assert_eq!(super::shortest_round_trip_scale(f64::NEG_INFINITY), 0);
+	}
+
+	#[test]
+	fn shortest_round_trip_scale_property() {
+		let values = [0.7, 1.15, 2.675, 1e-7 / 3., 123456.789, 9.999999999999999, -0.000123];
+		for &value in values.iter() {
+			let scale = super::shortest_round_trip_scale(value) as usize;
+			assert_eq!(format!("{:.*}", scale, value).parse::<f64>(), Ok(value));
+			assert!(scale == 0 || format!("{:.*}", scale - 1, value).parse::<f64>() != Ok(value));
+		}
+	}
+
+	#[test]
+	fn error_feedback_rounder() {
+		let mut rounder = super::ErrorFeedbackRounder::new(0);
+		let mut total = 0.;
+		let mut naive = 0.;
+		for _ in 0..1000 {
+			total += rounder.round(0.333);
+			naive += super::half_away_from_zero(0.333, 0);
+		}
+		assert_eq!(naive, 0.);
+		assert!((total - 333.).abs() <= 1.);
+		assert!(rounder.residual().abs() <= 0.5);
+	}
+
+	#[test]
+	fn error_feedback_rounder_stream() {
+		let mut rounder = super::ErrorFeedbackRounder::new(2);
+		let mut total = 0.;
+		let mut rounded_total = 0.;
+		for i in 0..10_000 {
+			let value = (i as f64 * 0.7).sin() * 3. + 0.0049;
+			total += value;
+			rounded_total += rounder.round(value);
+			assert!((rounded_total - total).abs() <= 0.01);
+		}
+	}
+
+	#[test]
+	fn error_feedback_rounder_exact() {
+		let mut rounder = super::ErrorFeedbackRounder::new(-1);
+		assert_eq!(rounder.round(30.), 30.);
+		assert_eq!(rounder.round(-20.), -20.);
+		assert_eq!(rounder.residual(), 0.);
+		assert_eq!(rounder.round(14.), 10.);
+		assert_eq!(rounder.round(14.), 20.);
+	}
+
+	#[test]
+	fn round_matrix() {
+		let m = Matrix::from_rows(&[vec![1.2345, -2.5, 0.125], vec![9.999, -0.0061, 3.]]).unwrap();
+		let modes = [RoundingMode::Ceil, RoundingMode::Floor, RoundingMode::HalfUp, RoundingMode::HalfDown,
+			RoundingMode::HalfAwayFromZero, RoundingMode::HalfTowardsZero, RoundingMode::HalfToEven, RoundingMode::HalfToOdd];
+		for &mode in modes.iter() {
+			for scale in -1..4 {
+				let rounded = super::round_matrix(&m, scale, mode);
+				assert_eq!((rounded.rows(), rounded.cols()), (2, 3));
+				for i in 0..2 {
+					for j in 0..3 {
+						assert_eq!(rounded[(i, j)], super::round_with(m[(i, j)], scale, mode));
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn round_matrix_mut() {
+		let m = Matrix::from_rows(&[vec![1.2345, -2.5], vec![9.999, -0.0061]]).unwrap();
+		let mut rounded = m.clone();
+		super::round_matrix_mut(&mut rounded, 2, RoundingMode::HalfToEven);
+		assert_eq!(rounded, super::round_matrix(&m, 2, RoundingMode::HalfToEven));
+		assert_eq!(rounded, Matrix::from_rows(&[vec![1.23, -2.5], vec![10., -0.01]]).unwrap());
+	}
+
+	#[test]
+	fn round_matrix_non_finite() {
+		let m = Matrix::from_rows(&[vec![f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.5]]).unwrap();
+		let rounded = super::round_matrix(&m, 0, RoundingMode::HalfUp);
+		assert!(rounded[(0, 0)].is_nan());
+		assert_eq!(rounded[(0, 1)], f64::INFINITY);
+		assert_eq!(rounded[(0, 2)], f64::NEG_INFINITY);
+		assert_eq!(rounded[(0, 3)], 1.);
+		assert_eq!(super::round_matrix(&Matrix::zeros(0, 0), 2, RoundingMode::HalfUp), Matrix::zeros(0, 0));
+	}
+
+	#[test]
+	fn round_to_multiple() {
+		assert_eq!(super::round_to_multiple(2.4, 0.25, RoundingMode::HalfUp), 2.5);
+		assert_eq!(super::round_to_multiple(2.37, 0.25, RoundingMode::HalfUp), 2.25);
+		assert_eq!(super::round_to_multiple(0.1234, 0.05, RoundingMode::HalfUp), 0.1);
+		assert_eq!(super::round_to_multiple(6.8, 2.5, RoundingMode::HalfUp), 7.5);
+		assert_eq!(super::round_to_multiple(-6.8, -2.5, RoundingMode::HalfUp), -7.5);
+		assert_eq!(super::round_to_multiple(2.3, 0.25, RoundingMode::Floor), 2.25);
+		assert_eq!(super::round_to_multiple(2.3, 0.25, RoundingMode::Ceil), 2.5);
+	}
+
+	#[test]
+	fn round_to_multiple_exact() {
+		// 0.3 / 0.1 and 2.3 / 0.1 are just below 3 and 23
+		assert_eq!(super::round_to_multiple(0.3, 0.1, RoundingMode::Floor), 0.3);
+		assert_eq!(super::round_to_multiple(2.3, 0.1, RoundingMode::Floor), 2.3);
+		assert_eq!(super::round_to_multiple(0.7, 0.1, RoundingMode::Ceil), 0.7);
+		assert_eq!(super::round_to_multiple(-2.3, 0.1, RoundingMode::Ceil), -2.3);
+		assert_eq!(super::round_to_multiple(0.3, 0.1, RoundingMode::HalfUp), 0.3);
+		assert_eq!(super::round_to_multiple(0.15, 0.05, RoundingMode::Floor), 0.15);
+		assert_eq!(super::round_to_multiple(0.35, 0.05, RoundingMode::Ceil), 0.35);
+		assert_eq!(super::round_to_multiple(1.15, 0.05, RoundingMode::Floor), 1.15);
+		assert_eq!(super::round_to_multiple(0.31, 0.1, RoundingMode::Floor), 0.3);
+		assert_eq!(super::round_to_multiple(0.31, 0.1, RoundingMode::Ceil), 0.4);
+		assert_eq!(super::round_to_multiple(0.16, 0.05, RoundingMode::Ceil), 0.2);
+		for i in 0..200 {
+			let value = i as f64 / 20.;
+			assert_eq!(super::round_to_multiple(value, 0.05, RoundingMode::Floor), value);
+			assert_eq!(super::round_to_multiple(value, 0.05, RoundingMode::Ceil), value);
+			let value = i as f64 / 10.;
+			assert_eq!(super::round_to_multiple(value, 0.1, RoundingMode::Floor), value);
+			assert_eq!(super::round_to_multiple(value, 0.1, RoundingMode::Ceil), value);
+		}
+	}
+
+	#[test]
+	fn round_to_multiple_ties() {
+		assert_eq!(super::round_to_multiple(2.375, 0.25, RoundingMode::HalfUp), 2.5);
+		assert_eq!(super::round_to_multiple(2.375, 0.25, RoundingMode::HalfDown), 2.25);
+		assert_eq!(super::round_to_multiple(2.375, 0.25, RoundingMode::HalfToEven), 2.5);
+		assert_eq!(super::round_to_multiple(2.625, 0.25, RoundingMode::HalfToEven), 2.5);
+		assert_eq!(super::round_to_multiple(-3.75, 2.5, RoundingMode::HalfAwayFromZero), -5.);
+		assert_eq!(super::round_to_multiple(-3.75, 2.5, RoundingMode::HalfTowardsZero), -2.5);
+	}
+
+	#[test]
+	fn round_to_multiple_special() {
+		assert!(super::round_to_multiple(2.4, 0., RoundingMode::HalfUp).is_nan());
+		assert!(super::round_to_multiple(f64::NAN, 0.25, RoundingMode::HalfUp).is_nan());
+		assert_eq!(super::round_to_multiple(f64::INFINITY, 0.25, RoundingMode::Floor), f64::INFINITY);
+		assert_eq!(super::round_to_multiple(f64::NEG_INFINITY, 0.25, RoundingMode::Ceil), f64::NEG_INFINITY);
+	}
+
+	#[test]
+	fn round_with() {
+		type RoundFn = fn(f64, i8) -> f64;
+		let modes: [(RoundingMode, RoundFn); 8] = [
+			(RoundingMode::Ceil, super::ceil),
+			(RoundingMode::Floor, super::floor),
+			(RoundingMode::HalfUp, super::half_up),
+			(RoundingMode::HalfDown, super::half_down),
+			(RoundingMode::HalfAwayFromZero, super::half_away_from_zero),
+			(RoundingMode::HalfTowardsZero, super::half_towards_zero),
+			(RoundingMode::HalfToEven, super::half_to_even),
+			(RoundingMode::HalfToOdd, super::half_to_odd),
+		];
+		for &(mode, func) in modes.iter() {
+			for &value in [-2.5, -1.25, -0.37, 0.37, 1.25, 2.5].iter() {
+				for &scale in [-1, 0, 1].iter() {
+					assert_eq!(super::round_with(value, scale, mode), func(value, scale));
+				}
+			}
+		}
+		// ties of the sample values are resolved differently by every mode
+		let results: Vec<f64> = modes.iter().map(|m| super::round_with(-1.25, 1, m.0)).collect();
+		assert_eq!(results, vec![-1.2, -1.3, -1.2, -1.3, -1.3, -1.2, -1.2, -1.3]);
+	}
+
+	#[test]
+	fn round_with_stochastic() {
+		for _ in 0..20 {
+			let rounded = super::round_with(1.25, 1, RoundingMode::Stochastic);
+			assert!(rounded == 1.2 || rounded == 1.3);
+		}
+		assert_eq!(super::round_with(1.27, 1, RoundingMode::Stochastic), 1.3);
+	}
+
+	#[test]
+	fn to_place() {
+		assert_eq!(super::to_place(12345., -2, RoundingMode::HalfUp), 12300.);
+		assert_eq!(super::to_place(12345., -3, RoundingMode::HalfUp), 12000.);
+		assert_eq!(super::to_place(12345., 0, RoundingMode::HalfUp), 12345.);
+		assert_eq!(super::to_place(2.5, 0, RoundingMode::HalfToEven), 2.);
+		assert_eq!(super::to_place(1.23456, 3, RoundingMode::HalfUp), 1.235);
+		assert_eq!(super::to_place(-12355., -1, RoundingMode::HalfUp), -12350.);
+		assert_eq!(super::to_place(-12355., -1, RoundingMode::HalfDown), -12360.);
+	}
+
+	#[test]
+	fn to_place_directed() {
+		assert_eq!(super::to_place(12345., -2, RoundingMode::Ceil), 12400.);
+		assert_eq!(super::to_place(12345., -2, RoundingMode::Floor), 12300.);
+		assert_eq!(super::to_place(-12345., -2, RoundingMode::Ceil), -12300.);
+		assert_eq!(super::to_place(-12345., -2, RoundingMode::Floor), -12400.);
+		assert_eq!(super::to_place(-1.23456, 3, RoundingMode::Ceil), -1.234);
+		assert_eq!(super::to_place(-1.23456, 3, RoundingMode::Floor), -1.235);
+	}
+
+	#[test]
+	fn to_place_extreme() {
+		assert_eq!(super::to_place(5., -400, RoundingMode::Ceil), f64::INFINITY);
+		assert_eq!(super::to_place(5., -400, RoundingMode::Floor), 0.);
+		assert_eq!(super::to_place(5., -400, RoundingMode::HalfUp), 0.);
+		assert_eq!(super::to_place(-5., -400, RoundingMode::Floor), f64::NEG_INFINITY);
+		assert_eq!(super::to_place(1.5, 400, RoundingMode::Floor), 1.5);
+		assert_eq!(super::to_place(1.5, i32::MAX, RoundingMode::Ceil), 1.5);
+		assert_eq!(super::to_place(1.5, i32::MIN, RoundingMode::HalfUp), 0.);
+		assert!(super::to_place(f64::NAN, -2, RoundingMode::HalfUp).is_nan());
+		assert_eq!(super::to_place(f64::INFINITY, -2, RoundingMode::Floor), f64::INFINITY);
+	}
+
+	#[test]
+	fn round_slice() {
+		let values = [1.25, -1.25, 3.17, f64::INFINITY, -2.73, f64::NEG_INFINITY, 0.];
+		let rounded = super::round_slice(&values, 1, RoundingMode::HalfToEven);
+		assert_eq!(rounded.len(), values.len());
+		for (r, v) in rounded.iter().zip(values.iter()) {
+			assert_eq!(*r, super::half_to_even(*v, 1));
+		}
+		assert_eq!(rounded, vec![1.2, -1.2, 3.2, f64::INFINITY, -2.7, f64::NEG_INFINITY, 0.]);
+	}
+
+	#[test]
+	fn round_slice_mut() {
+		let mut values = [1.25, f64::NAN, -1.25];
+		super::round_slice_mut(&mut values, 1, RoundingMode::HalfUp);
+		assert_eq!(values[0], 1.3);
+		assert!(values[1].is_nan());
+		assert_eq!(values[2], -1.2);
+	}
+
+	#[test]
+	fn round_slice_empty() {
+		assert_eq!(super::round_slice(&[], 2, RoundingMode::Ceil), vec![]);
+		let mut values: [f64; 0] = [];
+		super::round_slice_mut(&mut values, 2, RoundingMode::Ceil);
+	}
+
+	#[test]
+	fn rounded() {
+		use super::RoundIterExt;
+		let values = [1.234, -1.235, 2.5];
+		let rounded = values.iter()
+			.map(|x| x * 2.)
+			.rounded(2, RoundingMode::HalfUp)
+			.collect::<Vec<_>>();
+		assert_eq!(rounded, vec![2.47, -2.47, 5.]);
+	}
+
+	#[test]
+	fn rounded_size_hint() {
+		use super::RoundIterExt;
+		let mut rounded = [1., 2., 3.].iter().cloned().rounded(0, RoundingMode::Floor);
+		assert_eq!(rounded.size_hint(), (3, Some(3)));
+		rounded.next();
+		assert_eq!(rounded.size_hint(), (2, Some(2)));
+		let filtered = [1., 2., 3.].iter().cloned().filter(|x| *x > 1.).rounded(0, RoundingMode::Floor);
+		assert_eq!(filtered.size_hint(), (0, Some(3)));
+	}
+
+	#[test]
+	fn significant_figures() {
+		assert_eq!(super::significant_figures(0.00012345, 3, RoundingMode::HalfUp), 0.000123);
+		assert_eq!(super::significant_figures(12345., 3, RoundingMode::HalfUp), 12300.);
+		assert_eq!(super::significant_figures(-12345., 3, RoundingMode::HalfUp), -12300.);
+		assert_eq!(super::significant_figures(12355., 3, RoundingMode::Ceil), 12400.);
+		assert_eq!(super::significant_figures(9.96, 2, RoundingMode::HalfUp), 10.);
+		assert_eq!(super::significant_figures(999.4, 3, RoundingMode::HalfUp), 999.);
+		assert_eq!(super::significant_figures(1234.5, 1, RoundingMode::Floor), 1000.);
+	}
+
+	#[test]
+	fn significant_figures_magnitudes() {
+		assert_eq!(super::significant_figures(1.23456e-200, 3, RoundingMode::HalfUp), 1.23e-200);
+		assert_eq!(super::significant_figures(-9.87654e-300, 2, RoundingMode::HalfUp), -9.9e-300);
+		assert_eq!(super::significant_figures(1.23456e200, 3, RoundingMode::HalfUp), 1.23e200);
+		assert_eq!(super::significant_figures(6.02214076e23, 4, RoundingMode::HalfUp), 6.022e23);
+		assert_eq!(super::significant_figures(1.24e308, 2, RoundingMode::HalfUp), 1.2e308);
+		assert_eq!(super::significant_figures(1.23, 30, RoundingMode::HalfUp), 1.23);
+	}
+
+	#[test]
+	fn significant_figures_exact_decimals() {
+		assert_eq!(super::significant_figures(0.3, 1, RoundingMode::Floor), 0.3);
+		assert_eq!(super::significant_figures(0.07, 1, RoundingMode::Ceil), 0.07);
+		assert_eq!(super::significant_figures(1e-28, 1, RoundingMode::Floor), 1e-28);
+		assert_eq!(super::significant_figures(1.13, 3, RoundingMode::Floor), 1.13);
+		assert_eq!(super::significant_figures(-1.13, 2, RoundingMode::Floor), -1.2);
+		assert_eq!(super::significant_figures(4.56e-250, 3, RoundingMode::Ceil), 4.56e-250);
+		for exponent in -22..23 {
+			for digit in 1..10 {
+				let value: f64 = format!("{}e{}", digit, exponent).parse().unwrap();
+				assert_eq!(super::significant_figures(value, 1, RoundingMode::Floor), value);
+				assert_eq!(super::significant_figures(value, 1, RoundingMode::Ceil), value);
+				assert_eq!(super::significant_figures(-value, 2, RoundingMode::Floor), -value);
+			}
+		}
+	}
+
+	#[test]
+	fn significant_figures_ties() {
+		assert_eq!(super::significant_figures(1.25e-300, 2, RoundingMode::HalfToEven), 1.2e-300);
+		assert_eq!(super::significant_figures(1.35e-300, 2, RoundingMode::HalfToEven), 1.4e-300);
+		assert_eq!(super::significant_figures(2.675, 3, RoundingMode::HalfToEven), 2.68);
+		assert_eq!(super::significant_figures(0.125, 2, RoundingMode::HalfToOdd), 0.13);
+		assert_eq!(super::significant_figures(3450., 2, RoundingMode::HalfDown), 3400.);
+	}
+
+	#[test]
+	fn significant_figures_special() {
+		assert_eq!(super::significant_figures(0., 3, RoundingMode::HalfUp), 0.);
+		assert!(super::significant_figures(1.5, 0, RoundingMode::HalfUp).is_nan());
+		assert!(super::significant_figures(f64::NAN, 3, RoundingMode::HalfUp).is_nan());
+		assert_eq!(super::significant_figures(f64::INFINITY, 3, RoundingMode::Floor), f64::INFINITY);
+		assert_eq!(super::significant_figures(f64::NEG_INFINITY, 3, RoundingMode::Ceil), f64::NEG_INFINITY);
+	}
+
+	#[test]
+	fn stochastic_with_seeded() {
+		let values = [1.25, -1.25, 3.45, 0.15, 2.5, -7.5];
+		let mut a = StdRng::from_seed(&[1, 2, 3][..]);
+		let mut b = StdRng::from_seed(&[1, 2, 3][..]);
+		for _ in 0..10 {
+			for &value in values.iter() {
+				let x = super::stochastic_with(value, 1, &mut a);
+				assert_eq!(x, super::stochastic_with(value, 1, &mut b));
+				assert!(x == super::floor(value, 1) || x == super::ceil(value, 1));
+			}
+		}
+	}
+
+	#[test]
+	fn stochastic_with_ties() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let rounded: Vec<f64> = (0..100).map(|_| super::stochastic_with(2.5, 0, &mut rng)).collect();
+		assert!(rounded.contains(&2.) && rounded.contains(&3.));
+		assert!(rounded.iter().all(|x| *x == 2. || *x == 3.));
+		assert_eq!(super::stochastic_with(2.4, 0, &mut rng), 2.);
+		assert!(super::stochastic_with(f64::NAN, 0, &mut rng).is_nan());
+	}
+
+	#[test]
+	fn stochastic_proportional() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let rounded: Vec<f64> = (0..10000)
+			.map(|_| super::stochastic_proportional(2.4617, 3, &mut rng))
+			.collect();
+		assert!(rounded.iter().all(|x| *x == 2.461 || *x == 2.462));
+		let up = rounded.iter().filter(|x| **x == 2.462).count();
+		assert!(up > 6800 && up < 7200);
+		let mean = rounded.iter().sum::<f64>() / rounded.len() as f64;
+		assert!((mean - 2.4617).abs() < 2e-5);
+	}
+
+	#[test]
+	fn stochastic_proportional_negative() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let sum: f64 = (0..10000).map(|_| super::stochastic_proportional(-26., -1, &mut rng)).sum();
+		assert!((sum / 10000. + 26.).abs() < 0.2);
+	}
+
+	#[test]
+	fn stochastic_proportional_exact() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		for _ in 0..100 {
+			assert_eq!(super::stochastic_proportional(2.5, 1, &mut rng), 2.5);
+			assert_eq!(super::stochastic_proportional(-300., -2, &mut rng), -300.);
+		}
+		assert!(super::stochastic_proportional(f64::NAN, 1, &mut rng).is_nan());
+		assert_eq!(super::stochastic_proportional(f64::INFINITY, 1, &mut rng), f64::INFINITY);
+	}
+
+	#[test]
+	fn fraction() {
+		let cases = [
+			(0.3, 8, RoundingMode::HalfUp, 0.25),
+			(0.33, 8, RoundingMode::HalfUp, 0.375),
+			(-0.33, 8, RoundingMode::HalfUp, -0.375),
+			(0.3, 8, RoundingMode::Ceil, 0.375),
+			(-0.3, 8, RoundingMode::Ceil, -0.25),
+			(1.53, 16, RoundingMode::HalfUp, 1.5),
+			(-1.53, 16, RoundingMode::Floor, -1.5625),
+			(2.4, 3, RoundingMode::HalfUp, 7. / 3.),
+			(-2.4, 3, RoundingMode::HalfUp, -7. / 3.),
+			(0.0625, 8, RoundingMode::HalfToEven, 0.),
+			(0.1875, 8, RoundingMode::HalfToEven, 0.25),
+			(-0.1875, 8, RoundingMode::HalfTowardsZero, -0.125),
+			(7.3, 1, RoundingMode::HalfUp, 7.),
+		];
+		for &(value, denominator, mode, expected) in cases.iter() {
+			assert_eq!(super::fraction(value, denominator, mode), expected);
+		}
+	}
+
+	#[test]
+	fn fraction_special() {
+		assert!(super::fraction(0.3, 0, RoundingMode::HalfUp).is_nan());
+		assert!(super::fraction(f64::NAN, 8, RoundingMode::HalfUp).is_nan());
+		assert_eq!(super::fraction(f64::INFINITY, 8, RoundingMode::Floor), f64::INFINITY);
+		assert_eq!(super::fraction(f64::NEG_INFINITY, 8, RoundingMode::Ceil), f64::NEG_INFINITY);
+	}
+
+	#[test]
+	fn round_tracked_delta() {
+		let up = super::round_tracked(2.5, 0, RoundingMode::HalfUp);
+		assert_eq!((up.value, up.delta), (3., 0.5));
+		let down = super::round_tracked(-2.5, 0, RoundingMode::HalfUp);
+		assert_eq!((down.value, down.delta), (-2., 0.5));
+		let down = super::round_tracked(2.5, 0, RoundingMode::HalfDown);
+		assert_eq!((down.value, down.delta), (2., -0.5));
+		let ceil = super::round_tracked(1.21, 1, RoundingMode::Ceil);
+		assert_eq!(ceil.value, 1.3);
+		assert!((ceil.delta - 0.09).abs() < 1e-12);
+		let floor = super::round_tracked(-1.21, 1, RoundingMode::Floor);
+		assert_eq!(floor.value, -1.3);
+		assert!((floor.delta + 0.09).abs() < 1e-12);
+		let exact = super::round_tracked(1.25, 2, RoundingMode::HalfUp);
+		assert_eq!((exact.value, exact.delta), (1.25, 0.));
+	}
+
+	#[test]
+	fn round_tracked_accumulated() {
+		let values = [0.125, 2.374, 9.995, 4.001, 7.5];
+		let rounded: Vec<super::Rounded> = values.iter().map(|x| super::round_tracked(*x, 2, RoundingMode::HalfToEven)).collect();
+		let total = rounded.iter().fold(0., |a, r| a + r.value) - values.iter().fold(0., |a, x| a + x);
+		let deltas = rounded.iter().fold(0., |a, r| a + r.delta);
+		assert!((total - deltas).abs() < 1e-12);
+		assert!(rounded.iter().all(|r| r.delta.abs() <= 0.005 + 1e-12));
+	}
+
+	#[test]
+	fn round_tracked_special() {
+		let infinite = super::round_tracked(f64::INFINITY, 2, RoundingMode::HalfUp);
+		assert_eq!((infinite.value, infinite.delta), (f64::INFINITY, 0.));
+		let nan = super::round_tracked(f64::NAN, 2, RoundingMode::HalfUp);
+		assert!(nan.value.is_nan() && nan.delta.is_nan());
+	}
+
+	#[test]
+	fn rounding_mode_display() {
+		assert_eq!(RoundingMode::HalfAwayFromZero.to_string(), "half-away-from-zero");
+		assert_eq!(RoundingMode::Ceil.to_string(), "ceil");
+		assert_eq!(format!("{}", RoundingMode::HalfToEven), "half-to-even");
+	}
+
+	#[test]
+	fn rounding_mode_parse_round_trip() {
+		let modes = [
+			RoundingMode::Ceil,
+			RoundingMode::Floor,
+			RoundingMode::HalfUp,
+			RoundingMode::HalfDown,
+			RoundingMode::HalfAwayFromZero,
+			RoundingMode::HalfTowardsZero,
+			RoundingMode::HalfToEven,
+			RoundingMode::HalfToOdd,
+			RoundingMode::Stochastic,
+		];
+		for mode in modes.iter() {
+			assert_eq!(mode.to_string().parse(), Ok(*mode));
+		}
+	}
+
+	#[test]
+	fn rounding_mode_parse_aliases() {
+		assert_eq!("half-even".parse(), Ok(RoundingMode::HalfToEven));
+		assert_eq!("half-odd".parse(), Ok(RoundingMode::HalfToOdd));
+		assert_eq!(RoundingMode::HalfToEven.to_string(), "half-to-even");
+		assert_eq!(RoundingMode::HalfToOdd.to_string(), "half-to-odd");
+	}
+
+	#[test]
+	fn rounding_mode_parse_invalid() {
+		assert_eq!("".parse::<RoundingMode>(), Err(super::ParseRoundingModeError::Empty));
+		assert_eq!("half-evn".parse::<RoundingMode>(), Err(super::ParseRoundingModeError::Unknown("half-evn".to_string())));
+		assert_eq!("HalfUp".parse::<RoundingMode>(), Err(super::ParseRoundingModeError::Unknown("HalfUp".to_string())));
+		assert_eq!("half_up".parse::<RoundingMode>(), Err(super::ParseRoundingModeError::Unknown("half_up".to_string())));
+		let message = "half_up".parse::<RoundingMode>().unwrap_err().to_string();
+		assert!(message.starts_with("unknown rounding mode \"half_up\", expected one of ceil, floor, half-up,"));
+		let names = message.trim_start_matches("unknown rounding mode \"half_up\", expected one of ");
+		for name in names.split([',', ' ']).filter(|n| !n.is_empty() && *n != "or") {
+			assert!(name.parse::<RoundingMode>().is_ok(), "{}", name);
+		}
+	}
+
+	#[test]
+	fn ieee_aliases() {
+		type RoundFn = fn(f64, i8) -> f64;
+		let aliases: [(RoundFn, RoundFn); 5] = [
+			(super::round_ties_to_even, super::half_to_even),
+			(super::round_ties_to_away, super::half_away_from_zero),
+			(super::round_toward_positive, super::ceil),
+			(super::round_toward_negative, super::floor),
+			(super::round_toward_zero, super::truncate),
+		];
+		let inputs = [(2.5, 0), (-2.5, 0), (3.5, 0), (0.125, 2), (-1.2461, 3), (3450., -2), (7.77, 1), (f64::NAN, 1)];
+		for &(alias, function) in aliases.iter() {
+			for &(value, scale) in inputs.iter() {
+				let (a, b) = (alias(value, scale), function(value, scale));
+				assert!(a == b || (a.is_nan() && b.is_nan()));
+			}
+		}
+	}
+
+	#[test]
+	fn ieee_aliases_directions() {
+		assert_eq!(super::round_ties_to_even(2.5, 0), 2.);
+		assert_eq!(super::round_ties_to_even(-3.5, 0), -4.);
+		assert_eq!(super::round_ties_to_away(2.5, 0), 3.);
+		assert_eq!(super::round_ties_to_away(-2.5, 0), -3.);
+		assert_eq!(super::round_toward_positive(-2.9, 0), -2.);
+		assert_eq!(super::round_toward_negative(2.9, 0), 2.);
+		assert_eq!(super::round_toward_zero(-2.9, 0), -2.);
+	}
+
+	#[test]
+	fn clamp_then_round_boundary() {
+		// rounding the unclamped value first would give 1.01 and 0.99
+		assert_eq!(super::clamp_then_round(1.004, 0., 1., 2, RoundingMode::Ceil), 1.);
+		assert_eq!(super::clamp_then_round(-0.004, 0., 1., 2, RoundingMode::Floor), 0.);
+		assert_eq!(super::clamp_then_round(1.0001, -1., 1., 0, RoundingMode::HalfUp), 1.);
+		// bounds with more digits than the scale are rounded as well
+		assert_eq!(super::clamp_then_round(5., 0., 2.46, 1, RoundingMode::HalfUp), 2.5);
+		assert_eq!(super::clamp_then_round(5., 0., 2.46, 1, RoundingMode::Floor), 2.4);
+		assert_eq!(super::clamp_then_round(0.456, 0., 1., 1, RoundingMode::HalfUp), 0.5);
+		assert_eq!(super::clamp_then_round(2., 2., 2., 3, RoundingMode::HalfUp), 2.);
+	}
+
+	#[test]
+	fn clamp_then_round_invalid() {
+		assert!(super::clamp_then_round(0.5, 1., 0., 2, RoundingMode::HalfUp).is_nan());
+		assert!(super::clamp_then_round(0.5, f64::NAN, 1., 2, RoundingMode::HalfUp).is_nan());
+		assert!(super::clamp_then_round(0.5, 0., f64::NAN, 2, RoundingMode::HalfUp).is_nan());
+		assert!(super::clamp_then_round(f64::NAN, 0., 1., 2, RoundingMode::HalfUp).is_nan());
+		assert_eq!(super::clamp_then_round(f64::INFINITY, 0., 1., 2, RoundingMode::HalfUp), 1.);
+	}
+
+	#[test]
+	fn ties_scale_12() {
+		assert_eq!(super::half_to_even(-78.4284252050825, 12), -78.428425205082);
+		assert_eq!(super::half_to_even(70.0390499688475, 12), 70.039049968848);
+		assert_eq!(super::half_to_odd(70.0390499688475, 12), 70.039049968847);
+		assert_eq!(super::half_down(79.0325486509495, 12), 79.032548650949);
+		assert_eq!(super::half_up(2.0000000000025, 12), 2.000000000003);
+		assert_eq!(super::half_to_even(2.0000000000025, 12), 2.000000000002);
+		assert_eq!(super::half_towards_zero(-2.0000000000025, 12), -2.000000000002);
+	}
+
+	#[test]
+	fn ties_scale_15() {
+		assert_eq!(super::half_to_even(0.1234567890123455, 15), 0.123456789012346);
+		assert_eq!(super::half_to_odd(0.1234567890123455, 15), 0.123456789012345);
+		assert_eq!(super::half_to_even(2.5e-15, 15), 2e-15);
+		assert_eq!(super::half_to_even(3.5e-15, 15), 4e-15);
+		assert_eq!(super::half_to_even(-3.5e-15, 15), -4e-15);
+		assert_eq!(super::half_down(0.1234567890123455, 15), 0.123456789012345);
+		// values already at the scale are not rounded
+		assert_eq!(super::half_to_even(4.058824227058985, 15), 4.058824227058985);
+		assert_eq!(super::half_to_odd(0.314791882748195, 15), 0.314791882748195);
+	}
+
+	#[test]
+	fn ties_not_halfway() {
+		// a five followed by nonzero digits is above the halfway point
+		assert_eq!(super::half_down(0.0056, 2), 0.01);
+		assert_eq!(super::half_to_even(-0.0051, 2), -0.01);
+		assert_eq!(super::half_towards_zero(2.59, 0), 3.);
+		assert_eq!(super::half_towards_zero(-2.52, 0), -3.);
+		assert_eq!(super::half_to_even(3456., -2), 3500.);
+		assert_eq!(super::half_down(9.99499999, 2), 9.99);
+		assert_eq!(super::half_up(-0.815, 2), -0.81);
+		assert_eq!(super::half_to_even(0.314791882748195, 14), 0.3147918827482);
+	}
+
+	#[test]
+	fn ties_match_exact() {
+		type RoundFn = fn(f64, i8) -> f64;
+		let functions: [(RoundFn, RoundFn); 5] = [
+			(super::half_down, super::exact::half_down),
+			(super::half_to_even, super::exact::half_to_even),
+			(super::half_to_odd, super::exact::half_to_odd),
+			(super::half_towards_zero, super::exact::half_towards_zero),
+			(super::half_up, super::exact::half_up),
+		];
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		for scale in 10..18 {
+			for _ in 0..200 {
+				// a tie with at most 15 significant digits, the most an f64 holds
+				let digits = rng.gen_range(1, 15);
+				let kept = rng.gen_range(0u64, 10u64.pow(digits));
+				let value: f64 = format!("{}5e{}", kept, -(scale as i32) - 1).parse().unwrap();
+				let value = match rng.gen() { true => -value, false => value };
+				for &(function, exact) in functions.iter() {
+					assert_eq!(function(value, scale), exact(value, scale));
+				}
+			}
+		}
+	}
 }
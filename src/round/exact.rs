@@ -0,0 +1,343 @@
+//! Rounding functions working on decimal digits
+//!
+//! The functions of the parent module scale `value` by a power of ten,
+//! which can reintroduce binary representation error before the value is
+//! rounded. The functions of this module round the shortest decimal
+//! representation that converts back to `value`, i.e. the digits printed
+//! by `{}`, and parse the rounded digits back to the nearest `f64`. This is
+//! slower, but the result is what rounding the printed value by hand gives.
+extern crate rand;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Remainder {
+	Below,
+	Half,
+	Above,
+}
+
+/// Round up.
+///
+/// See `round::ceil`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::ceil(4.35, 2), 4.35);
+/// assert_eq!(exact::ceil(-4.351, 2), -4.35);
+/// ```
+pub fn ceil(value: f64, scale: i8) -> f64 {
+	round(value, scale, |_, negative, _| !negative)
+}
+
+/// Round down.
+///
+/// See `round::floor`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::floor(9999.199999999999, 3), 9999.199);
+/// assert_eq!(exact::floor(-4.351, 2), -4.36);
+/// ```
+pub fn floor(value: f64, scale: i8) -> f64 {
+	round(value, scale, |_, negative, _| negative)
+}
+
+/// Round half away from zero.
+///
+/// See `round::half_away_from_zero`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::half_away_from_zero(-1.005, 2), -1.01);
+/// ```
+pub fn half_away_from_zero(value: f64, scale: i8) -> f64 {
+	round(value, scale, |remainder, _, _| remainder != Remainder::Below)
+}
+
+/// Round half down.
+///
+/// See `round::half_down`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::half_down(1.005, 2), 1.);
+/// assert_eq!(exact::half_down(-1.005, 2), -1.01);
+/// ```
+pub fn half_down(value: f64, scale: i8) -> f64 {
+	round(value, scale, |remainder, negative, _| match remainder {
+		Remainder::Half => negative,
+		_ => remainder == Remainder::Above,
+	})
+}
+
+/// Round half to nearest even number.
+///
+/// See `round::half_to_even`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::half_to_even(2.675, 2), 2.68);
+/// assert_eq!(exact::half_to_even(2.665, 2), 2.66);
+/// ```
+pub fn half_to_even(value: f64, scale: i8) -> f64 {
+	round(value, scale, |remainder, _, odd| match remainder {
+		Remainder::Half => odd,
+		_ => remainder == Remainder::Above,
+	})
+}
+
+/// Round half to nearest odd number.
+///
+/// See `round::half_to_odd`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::half_to_odd(2.675, 2), 2.67);
+/// assert_eq!(exact::half_to_odd(2.665, 2), 2.67);
+/// ```
+pub fn half_to_odd(value: f64, scale: i8) -> f64 {
+	round(value, scale, |remainder, _, odd| match remainder {
+		Remainder::Half => !odd,
+		_ => remainder == Remainder::Above,
+	})
+}
+
+/// Round half towards zero.
+///
+/// See `round::half_towards_zero`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::half_towards_zero(-1.005, 2), -1.);
+/// ```
+pub fn half_towards_zero(value: f64, scale: i8) -> f64 {
+	round(value, scale, |remainder, _, _| remainder == Remainder::Above)
+}
+
+/// Round half up.
+///
+/// See `round::half_up`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::half_up(1.005, 2), 1.01);
+/// assert_eq!(exact::half_up(-1.005, 2), -1.);
+/// ```
+pub fn half_up(value: f64, scale: i8) -> f64 {
+	round(value, scale, |remainder, negative, _| match remainder {
+		Remainder::Half => !negative,
+		_ => remainder == Remainder::Above,
+	})
+}
+
+/// Round half randomly up or down.
+///
+/// See `round::stochastic`.
+///
+/// # Arguments
+///
+/// * `value` - value to round
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// let rounded = exact::stochastic(1.005, 2);
+/// assert!(rounded == 1. || rounded == 1.01);
+/// ```
+pub fn stochastic(value: f64, scale: i8) -> f64 {
+	round(value, scale, |remainder, _, _| match remainder {
+		Remainder::Half => rand::random::<bool>(),
+		_ => remainder == Remainder::Above,
+	})
+}
+
+/// Truncate.
+///
+/// See `round::truncate`.
+///
+/// # Arguments
+///
+/// * `value` - value to truncate
+/// * `scale` - result accuracy
+///
+/// # Example
+///
+/// ```
+/// use math::round::exact;
+///
+/// assert_eq!(exact::truncate(-9999.199999999999, 3), -9999.199);
+/// ```
+pub fn truncate(value: f64, scale: i8) -> f64 {
+	round(value, scale, |_, _, _| false)
+}
+
+// `away` decides from the dropped digits, the sign of `value` and the
+// parity of the last kept digit whether the kept digits are incremented,
+// i.e. whether `value` is rounded away from zero
+fn round<F: Fn(Remainder, bool, bool) -> bool>(value: f64, scale: i8, away: F) -> f64 {
+	if value == 0. || !value.is_finite() {
+		return value;
+	}
+	let formatted = format!("{:e}", value.abs());
+	let mut parts = formatted.split('e');
+	let digits: Vec<u8> = parts.next().unwrap().bytes()
+		.filter(|b| *b != b'.')
+		.map(|b| b - b'0')
+		.collect();
+	let exponent: i32 = parts.next().unwrap().parse().unwrap();
+	// number of leading digits at or above the rounding position
+	let keep = exponent + 1 + scale as i32;
+	if keep >= digits.len() as i32 {
+		return value;
+	}
+	let (kept, dropped) = match keep > 0 {
+		true => digits.split_at(keep as usize),
+		false => (&digits[..0], &digits[..]),
+	};
+	let remainder = match keep < 0 || dropped[0] < 5 {
+		true => Remainder::Below,
+		false => match dropped[0] == 5 && dropped[1..].iter().all(|d| *d == 0) {
+			true => Remainder::Half,
+			false => Remainder::Above,
+		},
+	};
+	let odd = kept.last().is_some_and(|d| d % 2 == 1);
+	let mut mantissa = kept.iter().fold(0u64, |a, d| a * 10 + *d as u64);
+	if away(remainder, value < 0., odd) {
+		mantissa += 1;
+	}
+	let sign = match value < 0. {
+		true => "-",
+		false => "",
+	};
+	format!("{}{}e{}", sign, mantissa, -(scale as i32)).parse().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn regressions() {
+		assert_eq!(super::half_up(9999.199999999999, 3), 9999.2);
+		assert_eq!(super::floor(9999.199999999999, 3), 9999.199);
+		assert_eq!(super::ceil(9999.199999999999, 3), 9999.2);
+		assert_eq!(super::half_up(4.6, 0), 5.);
+		assert_eq!(super::half_down(4.6, 0), 5.);
+		assert_eq!(super::half_to_even(4.6, 0), 5.);
+		assert_eq!(super::truncate(4.6, 0), 4.);
+	}
+
+	#[test]
+	fn printed_digits() {
+		// the f64 closest to 1.005 is below it and 0.1 + 0.2 is above 0.3
+		assert_eq!(super::half_up(1.005, 2), 1.01);
+		assert_eq!(super::half_to_even(0.125, 2), 0.12);
+		assert_eq!(super::half_to_odd(0.125, 2), 0.13);
+		assert_eq!(super::ceil(0.1 + 0.2, 16), 0.3000000000000001);
+		assert_eq!(super::floor(0.1 + 0.2, 15), 0.3);
+		assert_eq!(super::ceil(1.1, 1), 1.1);
+		assert_eq!(super::floor(-1.1, 1), -1.1);
+	}
+
+	#[test]
+	fn ties() {
+		let cases = [(1.25, 1.3, 1.2), (1.35, 1.4, 1.3), (-1.25, -1.2, -1.3), (-1.35, -1.3, -1.4)];
+		for &(value, up, down) in cases.iter() {
+			assert_eq!(super::half_up(value, 1), up);
+			assert_eq!(super::half_down(value, 1), down);
+		}
+		assert_eq!(super::half_to_even(-1.25, 1), -1.2);
+		assert_eq!(super::half_to_even(-1.35, 1), -1.4);
+		assert_eq!(super::half_to_odd(-1.25, 1), -1.3);
+		assert_eq!(super::half_away_from_zero(-1.25, 1), -1.3);
+		assert_eq!(super::half_towards_zero(-1.25, 1), -1.2);
+	}
+
+	#[test]
+	fn negative_scale() {
+		assert_eq!(super::half_up(3456., -2), 3500.);
+		assert_eq!(super::half_to_even(3450., -2), 3400.);
+		assert_eq!(super::ceil(3401., -2), 3500.);
+		assert_eq!(super::floor(-3401., -2), -3500.);
+		assert_eq!(super::half_up(49., -2), 0.);
+		assert_eq!(super::ceil(49., -3), 1000.);
+		assert_eq!(super::half_up(50., -2), 100.);
+	}
+
+	#[test]
+	fn special() {
+		assert!(super::half_up(f64::NAN, 2).is_nan());
+		assert_eq!(super::floor(f64::INFINITY, 2), f64::INFINITY);
+		assert_eq!(super::ceil(f64::NEG_INFINITY, 2), f64::NEG_INFINITY);
+		assert_eq!(super::half_up(0., 2), 0.);
+		assert_eq!(super::half_up(1e-300, 2), 0.);
+		assert_eq!(super::ceil(1e-300, 2), 0.01);
+		assert_eq!(super::half_up(1.23, 127), 1.23);
+		assert_eq!(super::half_up(1.7e308, -128), 1.7e308);
+	}
+}
@@ -0,0 +1,188 @@
+//! Integer sequences
+use std::collections::HashSet;
+
+/// Calculate Fibonacci number `n`.
+///
+/// Starts from `F(0) = 0` and `F(1) = 1`.
+/// Returns `None` if the result would overflow `u64`.
+///
+/// # Arguments
+///
+/// * `n` - index of the number
+///
+/// # Example
+///
+/// ```
+/// use math::sequence;
+///
+/// assert_eq!(sequence::fibonacci(10), Some(55));
+/// ```
+pub fn fibonacci(n: u64) -> Option<u64> {
+	additive(0, 1, n)
+}
+
+/// Calculate term `n` of linear recurrence `x(n) = p * x(n - 1) + q * x(n - 2)`.
+///
+/// The recurrence is seeded with `x(0) = a` and `x(1) = b`.
+/// Fibonacci numbers are given by seeds `0, 1` and Lucas numbers by seeds
+/// `2, 1`, both with `p = q = 1`.
+/// Returns `None` if a term would overflow `i64`.
+///
+/// # Arguments
+///
+/// * `a` - first seed
+/// * `b` - second seed
+/// * `p` - coefficient of the previous term
+/// * `q` - coefficient of the term before the previous one
+/// * `n` - index of the term
+///
+/// # Example
+///
+/// ```
+/// use math::sequence;
+///
+/// // Pell numbers
+/// assert_eq!(sequence::linear_recurrence(0, 1, 2, 1, 6), Some(70));
+/// ```
+pub fn linear_recurrence(a: u64, b: u64, p: i64, q: i64, n: u64) -> Option<i64> {
+	let mut x = (a as i64, b as i64);
+	if x.0 < 0 || x.1 < 0 {
+		return None;
+	}
+	if n == 0 {
+		return Some(x.0);
+	}
+	for _ in 1..n {
+		let next = p.checked_mul(x.1)?.checked_add(q.checked_mul(x.0)?)?;
+		x = (x.1, next);
+	}
+	Some(x.1)
+}
+
+/// Calculate Lucas number `n`.
+///
+/// Starts from `L(0) = 2` and `L(1) = 1`.
+/// Returns `None` if the result would overflow `u64`.
+///
+/// # Arguments
+///
+/// * `n` - index of the number
+///
+/// # Example
+///
+/// ```
+/// use math::sequence;
+///
+/// assert_eq!(sequence::lucas(10), Some(123));
+/// ```
+pub fn lucas(n: u64) -> Option<u64> {
+	additive(2, 1, n)
+}
+
+/// Calculate first `n` terms of Recamán's sequence.
+///
+/// Starts from `a(0) = 0`. Each following term `a(k)` is `a(k - 1) - k`
+/// if that is positive and not already in the sequence, and
+/// `a(k - 1) + k` otherwise.
+///
+/// # Arguments
+///
+/// * `n` - number of terms
+///
+/// # Example
+///
+/// ```
+/// use math::sequence;
+///
+/// assert_eq!(sequence::recaman(8), vec![0, 1, 3, 6, 2, 7, 13, 20]);
+/// ```
+pub fn recaman(n: usize) -> Vec<u64> {
+	let mut terms = Vec::with_capacity(n);
+	let mut seen = HashSet::new();
+	for k in 0..n as u64 {
+		let next = match terms.last() {
+			None => 0,
+			Some(&last) if last > k && !seen.contains(&(last - k)) => last - k,
+			Some(&last) => last + k,
+		};
+		seen.insert(next);
+		terms.push(next);
+	}
+	terms
+}
+
+fn additive(a: u64, b: u64, n: u64) -> Option<u64> {
+	if n == 0 {
+		return Some(a);
+	}
+	let mut x = (a, b);
+	for _ in 1..n {
+		x = (x.1, x.0.checked_add(x.1)?);
+	}
+	Some(x.1)
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn fibonacci() {
+		let expected = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55];
+		for (n, f) in expected.iter().enumerate() {
+			assert_eq!(super::fibonacci(n as u64), Some(*f));
+		}
+		assert_eq!(super::fibonacci(93), Some(12200160415121876738));
+		assert_eq!(super::fibonacci(94), None);
+	}
+
+	#[test]
+	fn lucas() {
+		let expected = [2, 1, 3, 4, 7, 11, 18, 29, 47, 76, 123];
+		for (n, l) in expected.iter().enumerate() {
+			assert_eq!(super::lucas(n as u64), Some(*l));
+		}
+		assert_eq!(super::lucas(200), None);
+	}
+
+	#[test]
+	fn lucas_fibonacci_identity() {
+		for n in 1..80 {
+			let f = super::fibonacci(n - 1).unwrap() + super::fibonacci(n + 1).unwrap();
+			assert_eq!(super::lucas(n), Some(f));
+		}
+	}
+
+	#[test]
+	fn linear_recurrence() {
+		for n in 0..90 {
+			let f = super::linear_recurrence(0, 1, 1, 1, n);
+			assert_eq!(f, super::fibonacci(n).map(|f| f as i64));
+			let l = super::linear_recurrence(2, 1, 1, 1, n);
+			assert_eq!(l, super::lucas(n).map(|l| l as i64));
+		}
+		assert_eq!(super::linear_recurrence(1, 1, 2, -1, 10), Some(1));
+		assert_eq!(super::linear_recurrence(0, 1, 1, 1, 93), None);
+		assert_eq!(super::linear_recurrence(u64::MAX, 1, 1, 1, 0), None);
+	}
+
+	#[test]
+	fn recaman() {
+		assert_eq!(super::recaman(0), vec![]);
+		assert_eq!(super::recaman(1), vec![0]);
+		assert_eq!(super::recaman(11), vec![0, 1, 3, 6, 2, 7, 13, 20, 12, 21, 11]);
+	}
+
+	#[test]
+	fn recaman_steps() {
+		let terms = super::recaman(1000);
+		for k in 1..terms.len() {
+			let step = k as u64;
+			match terms[k] < terms[k - 1] {
+				true => {
+					assert_eq!(terms[k], terms[k - 1] - step);
+					assert!(!terms[..k].contains(&terms[k]));
+				},
+				false => assert_eq!(terms[k], terms[k - 1] + step),
+			}
+		}
+	}
+}
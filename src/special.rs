@@ -0,0 +1,306 @@
+//! Special functions
+use std::f64::consts::PI;
+
+const LANCZOS_G: f64 = 7.;
+const LANCZOS: [f64; 9] = [
+	0.999_999_999_999_809_9,
+	676.520_368_121_885_1,
+	-1_259.139_216_722_402_8,
+	771.323_428_777_653_1,
+	-176.615_029_162_140_6,
+	12.507_343_278_686_905,
+	-0.138_571_095_265_720_12,
+	9.984_369_578_019_572e-6,
+	1.505_632_735_149_311_6e-7,
+];
+
+/// Calculate error function of `x`.
+///
+/// Uses the Maclaurin series for small arguments and a continued fraction
+/// for the complementary function otherwise. Accurate to about 13
+/// significant digits.
+///
+/// # Arguments
+///
+/// * `x` - argument
+///
+/// # Example
+///
+/// ```
+/// use math::special;
+///
+/// assert!((special::erf(1.) - 0.842_700_792_949_715).abs() < 1e-13);
+/// ```
+pub fn erf(x: f64) -> f64 {
+	if x.is_nan() {
+		return f64::NAN;
+	}
+	if x < 0. {
+		return -erf(-x);
+	}
+	if x >= 6. {
+		// erfc(6) is below half the machine epsilon
+		return 1.;
+	}
+	if x < 2.5 {
+		let mut term = x;
+		let mut sum = x;
+		let mut n = 0.;
+		while term.abs() > 1e-17 * sum.abs() {
+			n += 1.;
+			term *= -x * x / n;
+			sum += term / (2. * n + 1.);
+		}
+		return 2. / PI.sqrt() * sum;
+	}
+	// modified Lentz's method for erfc(x) = exp(-x^2) / sqrt(pi) / (x + 1/2 / (x + 1 / (x + 3/2 / ...)))
+	let mut f = x;
+	let mut c = x;
+	let mut d = 0.;
+	for k in 1..100 {
+		let a = k as f64 / 2.;
+		d = 1. / (x + a * d);
+		c = x + a / c;
+		let delta = c * d;
+		f *= delta;
+		if (delta - 1.).abs() < 1e-16 {
+			break;
+		}
+	}
+	1. - (-x * x).exp() / PI.sqrt() / f
+}
+
+/// Calculate factorial of real number `x`.
+///
+/// Defined as `gamma(x + 1)`, so it agrees with the integer factorial at
+/// non-negative integers and extends it to fractional arguments.
+/// Returns `INFINITY` at -1 and `NAN` at other negative integers, the
+/// poles of the gamma function.
+///
+/// # Arguments
+///
+/// * `x` - argument
+///
+/// # Example
+///
+/// ```
+/// use math::special;
+///
+/// assert!((special::factorial_real(0.5) - std::f64::consts::PI.sqrt() / 2.).abs() < 1e-15);
+/// ```
+pub fn factorial_real(x: f64) -> f64 {
+	gamma(x + 1.)
+}
+
+/// Calculate gamma function of `x`.
+///
+/// Uses the Lanczos approximation, with the reflection formula for
+/// arguments below one half. Accurate to about 15 significant digits.
+/// Returns `INFINITY` with the sign of zero at zero and `NAN` at negative
+/// integers, where the function has poles. Returns `INFINITY` above about
+/// 171.62, where the result exceeds `f64::MAX`.
+///
+/// # Arguments
+///
+/// * `x` - argument
+///
+/// # Example
+///
+/// ```
+/// use math::special;
+///
+/// assert!((special::gamma(5.) - 24.).abs() < 1e-12);
+/// ```
+pub fn gamma(x: f64) -> f64 {
+	if x == 0. {
+		return 1. / x;
+	}
+	if x < 0. && x == x.floor() {
+		return f64::NAN;
+	}
+	if x < 0.5 {
+		return PI / ((PI * x).sin() * gamma(1. - x));
+	}
+	// the power and the exponential below would give infinity times zero
+	if x > 171.7 {
+		return f64::INFINITY;
+	}
+	let x = x - 1.;
+	let a = (1..LANCZOS.len()).fold(LANCZOS[0], |a, i| a + LANCZOS[i] / (x + i as f64));
+	let t = x + LANCZOS_G + 0.5;
+	// split the power in two so that it does not overflow before exp(-t)
+	let p = t.powf((x + 0.5) / 2.);
+	(2. * PI).sqrt() * p * ((-t).exp() * p) * a
+}
+
+/// Calculate natural logarithm of gamma function of `x`.
+///
+/// Evaluates the Lanczos approximation in log space, so unlike `gamma` it
+/// does not overflow for large arguments. Returns `NAN` if `x` is not
+/// positive.
+///
+/// # Arguments
+///
+/// * `x` - argument
+///
+/// # Example
+///
+/// ```
+/// use math::special;
+///
+/// assert!((special::ln_gamma(5.) - 24f64.ln()).abs() < 1e-12);
+/// ```
+pub fn ln_gamma(x: f64) -> f64 {
+	if x.is_nan() || x <= 0. {
+		return f64::NAN;
+	}
+	if x < 0.5 {
+		return (PI / (PI * x).sin()).ln() - ln_gamma(1. - x);
+	}
+	let x = x - 1.;
+	let a = (1..LANCZOS.len()).fold(LANCZOS[0], |a, i| a + LANCZOS[i] / (x + i as f64));
+	let t = x + LANCZOS_G + 0.5;
+	0.5 * (2. * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+/// Calculate logarithm of the sum of exponentials of `slice`.
+///
+/// Returns `ln(exp(x1) + exp(x2) + ...)` computed by factoring out the
+/// maximum, so it neither overflows for large values nor underflows for
+/// small ones. Returns `NEG_INFINITY` for an empty slice.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::special;
+///
+/// let slice = [1000., 1000.];
+/// assert_eq!(special::logsumexp(&slice), 1000. + 2f64.ln());
+/// ```
+pub fn logsumexp(slice: &[f64]) -> f64 {
+	let max = slice.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+	if max.is_infinite() {
+		return max;
+	}
+	max + slice.iter().fold(0., |a, x| a + (x - max).exp()).ln()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64::consts::PI;
+
+	fn factorial(n: u64) -> f64 {
+		(1..=n).fold(1., |a, k| a * k as f64)
+	}
+
+	fn assert_relative(a: f64, b: f64) {
+		assert!(((a - b) / b).abs() < 1e-13, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn erf() {
+		let expected = [
+			(0., 0.),
+			(0.1, 0.112_462_916_018_284_9),
+			(0.5, 0.520_499_877_813_046_5),
+			(1., 0.842_700_792_949_714_9),
+			(2., 0.995_322_265_018_952_7),
+			(2.5, 0.999_593_047_982_555),
+			(3., 0.999_977_909_503_001_4),
+			(4., 0.999_999_984_582_742_1),
+		];
+		for &(x, y) in expected.iter() {
+			assert!((super::erf(x) - y).abs() < 1e-13, "erf({}) = {} != {}", x, super::erf(x), y);
+			assert_eq!(super::erf(-x), -super::erf(x));
+		}
+		assert_eq!(super::erf(10.), 1.);
+		assert_eq!(super::erf(f64::INFINITY), 1.);
+		assert_eq!(super::erf(f64::NEG_INFINITY), -1.);
+		assert!(super::erf(f64::NAN).is_nan());
+	}
+
+	#[test]
+	fn factorial_real() {
+		assert_relative(super::factorial_real(5.), 120.);
+		assert_relative(super::factorial_real(0.5), PI.sqrt() / 2.);
+		assert_relative(super::factorial_real(-0.5), PI.sqrt());
+		for n in 0..25 {
+			assert_relative(super::factorial_real(n as f64), factorial(n));
+		}
+	}
+
+	#[test]
+	fn factorial_real_poles() {
+		assert_eq!(super::factorial_real(-1.), f64::INFINITY);
+		assert!(super::factorial_real(-2.).is_nan());
+		assert!(super::factorial_real(-7.).is_nan());
+		assert!(super::factorial_real(f64::NAN).is_nan());
+	}
+
+	#[test]
+	fn gamma() {
+		assert_relative(super::gamma(1.), 1.);
+		assert_relative(super::gamma(0.5), PI.sqrt());
+		assert_relative(super::gamma(-1.5), 4. * PI.sqrt() / 3.);
+		assert!((super::gamma(171.) / factorial(170) - 1.).abs() < 1e-12);
+		assert_eq!(super::gamma(0.), f64::INFINITY);
+		assert_eq!(super::gamma(-0.), f64::NEG_INFINITY);
+		assert_eq!(super::gamma(172.), f64::INFINITY);
+	}
+
+	#[test]
+	fn gamma_large() {
+		assert!(super::gamma(171.62).is_finite());
+		for &x in [171.63, 171.7, 180., 1000., 1e300, f64::INFINITY].iter() {
+			assert_eq!(super::gamma(x), f64::INFINITY);
+			assert_eq!(super::factorial_real(x), f64::INFINITY);
+		}
+		assert_eq!(super::factorial_real(999.), f64::INFINITY);
+		assert!(super::gamma(-1000.5).abs() < 1e-300);
+	}
+
+	#[test]
+	fn ln_gamma() {
+		assert!(super::ln_gamma(1.).abs() < 1e-14);
+		assert!(super::ln_gamma(2.).abs() < 1e-14);
+		assert_relative(super::ln_gamma(0.5), PI.sqrt().ln());
+		assert_relative(super::ln_gamma(0.1), super::gamma(0.1).ln());
+		for n in 3..171 {
+			assert_relative(super::ln_gamma(n as f64), factorial(n - 1).ln());
+		}
+		assert!(super::ln_gamma(1000.).is_finite());
+		assert!(super::ln_gamma(0.).is_nan());
+		assert!(super::ln_gamma(-1.5).is_nan());
+	}
+
+	#[test]
+	fn logsumexp() {
+		assert_eq!(super::logsumexp(&[0., 0.]), 2f64.ln());
+		assert_eq!(super::logsumexp(&[5.]), 5.);
+	}
+
+	#[test]
+	fn logsumexp_naive() {
+		let slice = [0.5, -1.25, 2., 3.5];
+		let naive = slice.iter().fold(0., |a, x: &f64| a + x.exp()).ln();
+		assert!((super::logsumexp(&slice) - naive).abs() < 1e-12);
+	}
+
+	#[test]
+	fn logsumexp_extreme() {
+		assert_eq!(super::logsumexp(&[1000., 1000.]), 1000. + 2f64.ln());
+		assert_eq!(super::logsumexp(&[-1000., -1000.]), -1000. + 2f64.ln());
+		assert_eq!(super::logsumexp(&[f64::NEG_INFINITY, 0.]), 0.);
+		assert_eq!(super::logsumexp(&[f64::INFINITY, 0.]), f64::INFINITY);
+	}
+
+	#[test]
+	fn logsumexp_empty() {
+		assert_eq!(super::logsumexp(&[]), f64::NEG_INFINITY);
+		assert_eq!(super::logsumexp(&[f64::NEG_INFINITY]), f64::NEG_INFINITY);
+	}
+}
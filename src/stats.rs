@@ -0,0 +1,2265 @@
+//! Statistical functions
+extern crate rand;
+
+use distributions;
+use interpolate;
+use matrix::Matrix;
+use mean;
+use special;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use variance;
+
+/// Quantile method.
+///
+/// Selects how a quantile falling between two data points is computed,
+/// named as in NumPy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuantileMethod {
+	/// Interpolate linearly between the data points, R type 7.
+	Linear,
+	/// Take the lower data point.
+	Lower,
+	/// Take the higher data point.
+	Higher,
+	/// Take the nearest data point, the even-ranked one on ties.
+	Nearest,
+	/// Take the average of the data points.
+	Midpoint,
+}
+
+/// Calculate autocorrelation function of data set `slice`.
+///
+/// Returns the sample autocorrelations at lags `0` to `max_lag`, the
+/// autocovariance at each lag divided by the variance, both normalized by
+/// `n`. The lag is capped at `n - 1`.
+/// Returns an empty vector if `slice` is empty or has zero variance.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `max_lag` - largest lag
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 2., 3., 4.];
+/// assert_eq!(stats::acf(&slice, 2), vec![1., 0.25, -0.3]);
+/// ```
+pub fn acf(slice: &[f64], max_lag: usize) -> Vec<f64> {
+	match deviations(slice) {
+		Some((deviations, variance)) => (0..=max_lag.min(slice.len() - 1))
+			.map(|k| autocovariance(&deviations, k) / variance)
+			.collect(),
+		None => vec![],
+	}
+}
+
+/// Calculate statistic of `values` binned by `x`.
+///
+/// Splits the range of `x` into `bins` equal-width bins and applies
+/// `reducer` to the values falling into each bin. The last bin includes
+/// the maximum of `x`. Pairs whose `x` is not finite are skipped. Returns
+/// the per-bin statistic, `NAN` for empty bins, together with the
+/// `bins + 1` bin edges.
+/// Returns `None` if `x` and `values` differ in length, `x` has no finite
+/// values or `bins` is zero.
+///
+/// # Arguments
+///
+/// * `x` - collection of values to bin by
+/// * `values` - collection of values to reduce
+/// * `bins` - number of bins
+/// * `reducer` - statistic to calculate
+///
+/// # Example
+///
+/// ```
+/// use math::{ mean, stats };
+///
+/// let x = [0., 1., 2., 3., 4.];
+/// let values = [1., 2., 3., 4., 5.];
+/// let (means, edges) = stats::binned_statistic(&x, &values, 2, mean::arithmetic).unwrap();
+/// assert_eq!(means, vec![1.5, 4.]);
+/// assert_eq!(edges, vec![0., 2., 4.]);
+/// ```
+pub fn binned_statistic<F: Fn(&[f64]) -> f64>(x: &[f64], values: &[f64], bins: usize, reducer: F) -> Option<(Vec<f64>, Vec<f64>)> {
+	if x.len() != values.len() || bins == 0 || !x.iter().any(|xi| xi.is_finite()) {
+		return None;
+	}
+	let min = x.iter().cloned().filter(|xi| xi.is_finite()).fold(f64::INFINITY, f64::min);
+	let max = x.iter().cloned().filter(|xi| xi.is_finite()).fold(f64::NEG_INFINITY, f64::max);
+	let edges = bin_edges(min, max, bins);
+	let mut binned = vec![vec![]; bins];
+	for (xi, value) in x.iter().zip(values).filter(|pair| pair.0.is_finite()) {
+		binned[bin_index(&edges, *xi)].push(*value);
+	}
+	let statistic = binned.iter().map(|bin| match bin.is_empty() {
+		true => f64::NAN,
+		false => reducer(bin),
+	}).collect();
+	Some((statistic, edges))
+}
+
+/// Calculate arithmetic mean of every column of matrix `m`.
+///
+/// # Arguments
+///
+/// * `m` - matrix
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+/// use math::stats;
+///
+/// let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 6.]]).unwrap();
+/// assert_eq!(stats::column_means(&m), vec![2., 4.]);
+/// ```
+pub fn column_means(m: &Matrix) -> Vec<f64> {
+	(0..m.cols()).map(|j| mean::arithmetic(&m.column(j))).collect()
+}
+
+/// Calculate median of every column of matrix `m`.
+///
+/// # Arguments
+///
+/// * `m` - matrix
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+/// use math::stats;
+///
+/// let m = Matrix::from_rows(&[vec![1., 9.], vec![3., 6.], vec![2., 7.]]).unwrap();
+/// assert_eq!(stats::column_medians(&m), vec![2., 7.]);
+/// ```
+pub fn column_medians(m: &Matrix) -> Vec<f64> {
+	(0..m.cols()).map(|j| median(&m.column(j))).collect()
+}
+
+/// Calculate sample variance of every column of matrix `m`.
+///
+/// The variances are `NAN` if the matrix has fewer than two rows.
+///
+/// # Arguments
+///
+/// * `m` - matrix
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+/// use math::stats;
+///
+/// let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 6.]]).unwrap();
+/// assert_eq!(stats::column_variances(&m), vec![2., 8.]);
+/// ```
+pub fn column_variances(m: &Matrix) -> Vec<f64> {
+	(0..m.cols()).map(|j| variance::sample(&m.column(j))).collect()
+}
+
+/// Calculate Pearson correlation coefficient of paired data sets `xs` and `ys`.
+///
+/// The result is clamped to `[-1, 1]` to absorb floating point error.
+/// Returns `NAN` if the data sets differ in length, are empty or either of
+/// them has zero variance.
+///
+/// # Arguments
+///
+/// * `xs` - first collection of values
+/// * `ys` - second collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let xs = [1., 2., 3., 4.];
+/// let ys = [8., 6., 4., 2.];
+/// assert_eq!(stats::correlation(&xs, &ys), -1.);
+/// ```
+pub fn correlation(xs: &[f64], ys: &[f64]) -> f64 {
+	if xs.len() != ys.len() || xs.is_empty() {
+		return f64::NAN;
+	}
+	let mx = mean::arithmetic(xs);
+	let my = mean::arithmetic(ys);
+	let (sxy, sxx, syy) = xs.iter().zip(ys).fold((0., 0., 0.), |a, (x, y)| {
+		(a.0 + (x - mx) * (y - my), a.1 + (x - mx).powi(2), a.2 + (y - my).powi(2))
+	});
+	match sxx == 0. || syy == 0. {
+		true => f64::NAN,
+		false => (sxy / (sxx * syy).sqrt()).clamp(-1., 1.),
+	}
+}
+
+/// Convert covariance matrix `cov` to correlation matrix.
+///
+/// Divides every entry `(i, j)` by `sqrt(cov[(i, i)] * cov[(j, j)])`, so
+/// the result has a unit diagonal. Off-diagonal entries are clamped to
+/// `[-1, 1]` to absorb floating point error.
+/// Returns `None` if `cov` is not square or any diagonal entry is not
+/// positive.
+///
+/// # Arguments
+///
+/// * `cov` - covariance matrix
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+/// use math::stats;
+///
+/// let cov = Matrix::from_rows(&[vec![4., 3.], vec![3., 9.]]).unwrap();
+/// let corr = stats::cov_to_corr(&cov).unwrap();
+/// assert_eq!((corr[(0, 0)], corr[(0, 1)], corr[(1, 1)]), (1., 0.5, 1.));
+/// ```
+pub fn cov_to_corr(cov: &Matrix) -> Option<Matrix> {
+	let p = cov.rows();
+	if cov.cols() != p || !(0..p).all(|i| cov[(i, i)] > 0.) {
+		return None;
+	}
+	let std_devs: Vec<f64> = (0..p).map(|i| cov[(i, i)].sqrt()).collect();
+	let mut corr = Matrix::identity(p);
+	for i in 0..p {
+		for j in 0..p {
+			if i != j {
+				corr[(i, j)] = (cov[(i, j)] / (std_devs[i] * std_devs[j])).clamp(-1., 1.);
+			}
+		}
+	}
+	Some(corr)
+}
+
+/// Calculate covariance of paired data sets `xs` and `ys`.
+///
+/// The sum of products of the deviations from the means is divided by `n`,
+/// or by `n - 1` if `sample` is `true`.
+/// Returns `NAN` if the data sets differ in length or are empty, or if
+/// `sample` is `true` and there are fewer than two pairs.
+///
+/// # Arguments
+///
+/// * `xs` - first collection of values
+/// * `ys` - second collection of values
+/// * `sample` - whether to calculate the sample covariance
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let xs = [1., 2., 3., 4.];
+/// let ys = [2., 4., 6., 8.];
+/// assert_eq!(stats::covariance(&xs, &ys, false), 2.5);
+/// assert_eq!(stats::covariance(&xs, &ys, true), 10. / 3.);
+/// ```
+pub fn covariance(xs: &[f64], ys: &[f64], sample: bool) -> f64 {
+	let n = xs.len();
+	if n != ys.len() || n == 0 || (sample && n < 2) {
+		return f64::NAN;
+	}
+	let mx = mean::arithmetic(xs);
+	let my = mean::arithmetic(ys);
+	let products: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| (x - mx) * (y - my)).collect();
+	let population = mean::arithmetic(&products);
+	match sample {
+		true => population * n as f64 / (n - 1) as f64,
+		false => population,
+	}
+}
+
+/// Calculate sample covariance matrix of data set `data`.
+///
+/// Each row of `data` is an observation and each column a variable.
+/// Returns `None` if the rows differ in length or there are fewer than
+/// two of them.
+///
+/// # Arguments
+///
+/// * `data` - collection of observations
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let data = [vec![1., 2.], vec![2., 4.], vec![3., 6.]];
+/// let cov = stats::covariance_matrix(&data).unwrap();
+/// assert_eq!((cov[(0, 0)], cov[(0, 1)], cov[(1, 1)]), (1., 2., 4.));
+/// ```
+pub fn covariance_matrix(data: &[Vec<f64>]) -> Option<Matrix> {
+	let m = Matrix::from_rows(data)?;
+	let (n, p) = (m.rows(), m.cols());
+	if n < 2 {
+		return None;
+	}
+	let means: Vec<f64> = (0..p)
+		.map(|j| (0..n).fold(0., |a, i| a + m[(i, j)]) / n as f64)
+		.collect();
+	let mut cov = Matrix::zeros(p, p);
+	for j in 0..p {
+		for k in 0..p {
+			let sum = (0..n).fold(0., |a, i| a + (m[(i, j)] - means[j]) * (m[(i, k)] - means[k]));
+			cov[(j, k)] = sum / (n - 1) as f64;
+		}
+	}
+	Some(cov)
+}
+
+/// Calculate shrunk covariance matrix of data set `data`.
+///
+/// Blends the sample covariance matrix `S` with a scaled identity target,
+/// `(1 - intensity) * S + intensity * (trace(S) / p) * I`, which keeps the
+/// estimate well-conditioned when there are few observations compared to
+/// the number of variables `p`.
+/// Returns `None` if the rows differ in length, there are fewer than two of
+/// them or `intensity` is outside of `[0, 1]`.
+///
+/// # Arguments
+///
+/// * `data` - collection of observations
+/// * `intensity` - shrinkage intensity
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let data = [vec![1., 2.], vec![2., 4.], vec![3., 6.]];
+/// let cov = stats::covariance_shrinkage(&data, 0.5).unwrap();
+/// assert_eq!((cov[(0, 0)], cov[(0, 1)], cov[(1, 1)]), (1.75, 1., 3.25));
+/// ```
+pub fn covariance_shrinkage(data: &[Vec<f64>], intensity: f64) -> Option<Matrix> {
+	if !(0. ..=1.).contains(&intensity) {
+		return None;
+	}
+	let mut cov = covariance_matrix(data)?;
+	let p = cov.rows();
+	let target = (0..p).fold(0., |a, i| a + cov[(i, i)]) / p as f64;
+	for i in 0..p {
+		for j in 0..p {
+			cov[(i, j)] *= 1. - intensity;
+			if i == j {
+				cov[(i, j)] += intensity * target;
+			}
+		}
+	}
+	Some(cov)
+}
+
+/// Calculate Durbin-Watson statistic of regression residuals `residuals`.
+///
+/// Returns `Σ(e_i - e_{i-1})^2 / Σe_i^2`, which ranges from 0 to 4. Values
+/// near 2 indicate no first-order autocorrelation, values towards 0
+/// positive and values towards 4 negative autocorrelation.
+/// Returns `NAN` if there are fewer than two residuals or all of them
+/// are zero.
+///
+/// # Arguments
+///
+/// * `residuals` - collection of residuals
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let residuals = [1., -1., 1., -1.];
+/// assert_eq!(stats::durbin_watson(&residuals), 3.);
+/// ```
+pub fn durbin_watson(residuals: &[f64]) -> f64 {
+	let squares = residuals.iter().fold(0., |a, e| a + e * e);
+	if residuals.len() < 2 || squares == 0. {
+		return f64::NAN;
+	}
+	residuals.windows(2).fold(0., |a, pair| a + (pair[1] - pair[0]).powi(2)) / squares
+}
+
+/// Calculate exponential moving average of data set `slice`.
+///
+/// The first average is the first value of `slice` and every following
+/// one is `alpha * x + (1 - alpha) * previous`, see `mean::ewma_update`.
+/// Returns an empty `Vec` if `slice` is empty or `alpha` is outside of
+/// `(0, 1]`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `alpha` - smoothing factor
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [8., 16., 4.];
+/// assert_eq!(stats::ema(&slice, 0.5), vec![8., 12., 8.]);
+/// ```
+pub fn ema(slice: &[f64], alpha: f64) -> Vec<f64> {
+	if slice.is_empty() || !(alpha > 0. && alpha <= 1.) {
+		return vec![];
+	}
+	let mut averages = Vec::with_capacity(slice.len());
+	averages.push(slice[0]);
+	for x in slice[1..].iter() {
+		let previous = averages[averages.len() - 1];
+		averages.push(mean::ewma_update(previous, *x, alpha));
+	}
+	averages
+}
+
+/// Calculate excess kurtosis of data set `slice`.
+///
+/// Returns `kurtosis` minus `3`, which makes the excess kurtosis of
+/// normally distributed data zero.
+/// Returns `NAN` if `slice` has fewer than four values or zero variance.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 2., 3., 4., 5.];
+/// assert_eq!(stats::excess_kurtosis(&slice), -1.3);
+/// ```
+pub fn excess_kurtosis(slice: &[f64]) -> f64 {
+	kurtosis(slice) - 3.
+}
+
+/// Estimate effective sample size of autocorrelated data set `slice`.
+///
+/// Returns `n / (1 + 2 * Σρ_k)` where `ρ_k` are the autocorrelations from
+/// lag 1 up to, but not including, the first non-positive one (the
+/// initial positive sequence estimator). Independent data give a value
+/// close to `n`, strongly autocorrelated data a much smaller one.
+/// Returns `NAN` if there are fewer than two values or they have zero
+/// variance.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., -1., 1., -1., 1., -1.];
+/// assert_eq!(stats::effective_sample_size(&slice), 6.);
+/// ```
+pub fn effective_sample_size(slice: &[f64]) -> f64 {
+	let (deviations, variance) = match deviations(slice) {
+		Some(deviations) if slice.len() > 1 => deviations,
+		_ => return f64::NAN,
+	};
+	// the autocorrelations are computed lazily as most series decorrelate
+	// long before the largest lag
+	let sum = (1..slice.len())
+		.map(|k| autocovariance(&deviations, k) / variance)
+		.take_while(|&r| r > 0.)
+		.fold(0., |a, r| a + r);
+	slice.len() as f64 / (1. + 2. * sum)
+}
+
+/// Build histogram of data set `slice`.
+///
+/// Splits the range from the minimum to the maximum of `slice` into `bins`
+/// equal-width bins and returns the left edge of every bin together with
+/// the number of values falling into it. The last bin includes the
+/// maximum. Values that are not finite are skipped, and if all values are
+/// equal they are counted in the first bin.
+/// Returns an empty `Vec` if `bins` is zero or there are no finite values.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `bins` - number of bins
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [0., 1., 1.5, 3., 4.];
+/// assert_eq!(stats::histogram(&slice, 2), vec![(0., 3), (2., 2)]);
+/// ```
+pub fn histogram(slice: &[f64], bins: usize) -> Vec<(f64, usize)> {
+	let finite: Vec<f64> = slice.iter().cloned().filter(|x| x.is_finite()).collect();
+	if finite.is_empty() || bins == 0 {
+		return vec![];
+	}
+	let min = finite.iter().cloned().fold(f64::INFINITY, f64::min);
+	let max = finite.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+	let edges = bin_edges(min, max, bins);
+	let mut counts = vec![0; bins];
+	for x in finite.iter() {
+		counts[bin_index(&edges, *x)] += 1;
+	}
+	edges.into_iter().zip(counts).collect()
+}
+
+/// Calculate interquartile range (IQR) of data set `slice`.
+///
+/// The interquartile range is the difference between the third and the
+/// first quartile. Returns `NAN` if `slice` has fewer than two values.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 2., 3., 4., 5.];
+/// assert_eq!(stats::iqr(&slice), 2.);
+/// ```
+pub fn iqr(slice: &[f64]) -> f64 {
+	match slice.len() < 2 {
+		true => f64::NAN,
+		false => quantile(slice, 0.75) - quantile(slice, 0.25),
+	}
+}
+
+/// Calculate kurtosis of data set `slice`.
+///
+/// The kurtosis is the fourth standardized moment `m4 / m2²` of the
+/// population central moments, `3` for normally distributed data.
+/// Returns `NAN` if `slice` has fewer than four values or zero variance.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 2., 3., 10.];
+/// assert_eq!(stats::kurtosis(&slice), 2.2304);
+/// ```
+pub fn kurtosis(slice: &[f64]) -> f64 {
+	match slice.len() < 4 || is_constant(slice) {
+		true => f64::NAN,
+		false => central_moment(slice, 4) / central_moment(slice, 2).powi(2),
+	}
+}
+
+/// Fit a line to paired data sets `xs` and `ys` by least squares.
+///
+/// Returns the slope and the intercept of the line `y = slope * x +
+/// intercept` minimizing the sum of squared residuals, the slope being the
+/// covariance of `xs` and `ys` divided by the variance of `xs`.
+/// Returns `None` if the data sets differ in length, have fewer than two
+/// points or `xs` has zero variance.
+///
+/// # Arguments
+///
+/// * `xs` - collection of explanatory values
+/// * `ys` - collection of response values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let xs = [0., 1., 2., 3.];
+/// let ys = [-2., 1., 4., 7.];
+/// assert_eq!(stats::linear_fit(&xs, &ys), Some((3., -2.)));
+/// ```
+pub fn linear_fit(xs: &[f64], ys: &[f64]) -> Option<(f64, f64)> {
+	if xs.len() != ys.len() || xs.len() < 2 || is_constant(xs) {
+		return None;
+	}
+	let slope = covariance(xs, ys, false) / variance::population(xs);
+	Some((slope, mean::arithmetic(ys) - slope * mean::arithmetic(xs)))
+}
+
+/// Calculate median absolute deviation (MAD) of data set `slice`.
+///
+/// The median absolute deviation is the median of the absolute deviations
+/// from the median, a measure of spread that is barely affected by
+/// outliers. Returns `NAN` if `slice` is empty.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 1., 2., 2., 4., 6., 9.];
+/// assert_eq!(stats::mad(&slice), 1.);
+/// ```
+pub fn mad(slice: &[f64]) -> f64 {
+	let center = median(slice);
+	let deviations: Vec<f64> = slice.iter().map(|x| (x - center).abs()).collect();
+	median(&deviations)
+}
+
+/// Calculate normalized median absolute deviation of data set `slice`.
+///
+/// Scales `mad` by `1.4826`, which makes it a consistent estimator of the
+/// standard deviation of normally distributed data.
+/// Returns `NAN` if `slice` is empty.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 1., 2., 2., 4., 6., 9.];
+/// assert_eq!(stats::mad_normalized(&slice), 1.4826);
+/// ```
+pub fn mad_normalized(slice: &[f64]) -> f64 {
+	mad(slice) * 1.4826
+}
+
+/// Calculate median of data set `slice`.
+///
+/// Returns `NAN` if `slice` is empty.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [4., 1., 3., 2.];
+/// assert_eq!(stats::median(&slice), 2.5);
+/// ```
+pub fn median(slice: &[f64]) -> f64 {
+	quantile(slice, 0.5)
+}
+
+/// Calculate mode of continuous data set `slice`.
+///
+/// Buckets the values into bins `[k * bin_width, (k + 1) * bin_width)` and
+/// returns the center of the most populated bin, the lowest one if several
+/// are equally populated. Values that are not finite are skipped.
+/// Returns `NAN` if `bin_width` is not positive, there are no finite
+/// values or a value is 2^53 or more bin widths from zero, where bins can
+/// no longer be told apart.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `bin_width` - width of the bins
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1.02, 2.51, 2.67, 2.93, 4.1];
+/// assert_eq!(stats::mode_binned(&slice, 0.5), 2.75);
+/// ```
+pub fn mode_binned(slice: &[f64], bin_width: f64) -> f64 {
+	if bin_width <= 0. || bin_width.is_nan() {
+		return f64::NAN;
+	}
+	let mut counts: HashMap<i64, usize> = HashMap::new();
+	for x in slice.iter().filter(|x| x.is_finite()) {
+		let bin = (x / bin_width).floor();
+		if bin.abs() >= 9007199254740992. {
+			return f64::NAN;
+		}
+		*counts.entry(bin as i64).or_insert(0) += 1;
+	}
+	counts.iter()
+		.max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+		.map_or(f64::NAN, |(bin, _)| (*bin as f64 + 0.5) * bin_width)
+}
+
+/// Calculate moving average of data set `slice`.
+///
+/// Returns the arithmetic mean of every window of `window` consecutive
+/// values, `slice.len() - window + 1` averages in total. The sum of the
+/// window is updated as it slides, so the cost does not depend on `window`.
+/// The sum is compensated like in `mean::arithmetic` and recomputed while
+/// it is not finite, so that a large or infinite value leaving the window
+/// does not affect later averages.
+/// Returns an empty `Vec` if `window` is zero or longer than `slice`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `window` - number of values per average
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 3., 5., 7., 9.];
+/// assert_eq!(stats::moving_average(&slice, 3), vec![3., 5., 7.]);
+/// ```
+pub fn moving_average(slice: &[f64], window: usize) -> Vec<f64> {
+	if window == 0 || window > slice.len() {
+		return vec![];
+	}
+	let total = |sum: f64, compensation: f64| match sum.is_finite() {
+		true => sum + compensation,
+		false => sum,
+	};
+	let (mut sum, mut compensation) = (0., 0.);
+	for x in slice[..window].iter() {
+		compensated_add(&mut sum, &mut compensation, *x);
+	}
+	let mut averages = vec![total(sum, compensation) / window as f64];
+	for i in window..slice.len() {
+		match sum.is_finite() && compensation.is_finite() {
+			true => {
+				compensated_add(&mut sum, &mut compensation, slice[i]);
+				compensated_add(&mut sum, &mut compensation, -slice[i - window]);
+			},
+			false => {
+				sum = 0.;
+				compensation = 0.;
+				for x in slice[i + 1 - window..=i].iter() {
+					compensated_add(&mut sum, &mut compensation, *x);
+				}
+			},
+		}
+		averages.push(total(sum, compensation) / window as f64);
+	}
+	averages
+}
+
+/// Calculate partial correlation of `xs` and `ys` controlling for `zs`.
+///
+/// The partial correlation is the correlation of `xs` and `ys` after the
+/// linear effect of `zs` has been removed from both.
+/// Returns `NAN` if the data sets differ in length, are empty or the
+/// correlation of `zs` with either of them is perfect.
+///
+/// # Arguments
+///
+/// * `xs` - first collection of values
+/// * `ys` - second collection of values
+/// * `zs` - collection of controlled values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let zs = [-3., -1., 1., 3.];
+/// let xs = [-2., -2., 0., 4.];
+/// let ys = [-4., 2., -2., 4.];
+/// assert!(stats::partial_correlation(&xs, &ys, &zs).abs() < 1e-12);
+/// ```
+pub fn partial_correlation(xs: &[f64], ys: &[f64], zs: &[f64]) -> f64 {
+	let rxy = correlation(xs, ys);
+	let rxz = correlation(xs, zs);
+	let ryz = correlation(ys, zs);
+	let denominator = ((1. - rxz * rxz) * (1. - ryz * ryz)).sqrt();
+	match denominator == 0. {
+		true => f64::NAN,
+		false => (rxy - rxz * ryz) / denominator,
+	}
+}
+
+/// Test significance of Pearson correlation of paired data sets `xs` and `ys`.
+///
+/// Returns the correlation coefficient `r` and the two-sided p-value of
+/// the t-statistic `r * sqrt((n - 2) / (1 - r^2))` under Student's
+/// t-distribution with `n - 2` degrees of freedom.
+/// Returns `None` if the data sets differ in length, have fewer than three
+/// points, either of them has zero variance or the correlation is perfect.
+///
+/// # Arguments
+///
+/// * `xs` - first collection of values
+/// * `ys` - second collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let xs = [1., 2., 3., 4., 5., 6.];
+/// let ys = [1.2, 1.9, 3.4, 3.8, 5.3, 5.9];
+/// let (r, p) = stats::pearson_test(&xs, &ys).unwrap();
+/// assert!(r > 0.99 && p < 0.001);
+/// ```
+pub fn pearson_test(xs: &[f64], ys: &[f64]) -> Option<(f64, f64)> {
+	if xs.len() != ys.len() || xs.len() < 3 {
+		return None;
+	}
+	let r = correlation(xs, ys);
+	if r.is_nan() || r.abs() == 1. {
+		return None;
+	}
+	let df = xs.len() as f64 - 2.;
+	let t = r * (df / (1. - r * r)).sqrt();
+	Some((r, 2. * distributions::t_cdf(-t.abs(), df)))
+}
+
+/// Test whether data sets `a` and `b` differ in mean by permutation.
+///
+/// Pools both data sets, shuffles and splits them `permutations` times
+/// and returns the two-sided p-value, i.e. the fraction of splits whose
+/// absolute difference in means is at least the observed one.
+/// Returns `None` if either data set is empty or `permutations` is zero.
+///
+/// # Arguments
+///
+/// * `a` - first collection of values
+/// * `b` - second collection of values
+/// * `permutations` - number of permutations
+/// * `rng` - random number generator
+///
+/// # Example
+///
+/// ```
+/// extern crate rand;
+/// extern crate math;
+///
+/// use math::stats;
+///
+/// # fn main() {
+/// let a = [1., 2., 3., 4., 5.];
+/// let b = [11., 12., 13., 14., 15.];
+/// let p = stats::permutation_test(&a, &b, 1000, &mut rand::thread_rng()).unwrap();
+/// assert!(p < 0.05);
+/// # }
+/// ```
+pub fn permutation_test<R: rand::Rng>(a: &[f64], b: &[f64], permutations: usize, rng: &mut R) -> Option<f64> {
+	if a.is_empty() || b.is_empty() || permutations == 0 {
+		return None;
+	}
+	let observed = (mean::arithmetic(a) - mean::arithmetic(b)).abs();
+	let mut pooled = [a, b].concat();
+	let mut count = 0;
+	for _ in 0..permutations {
+		rng.shuffle(&mut pooled);
+		let (x, y) = pooled.split_at(a.len());
+		if (mean::arithmetic(x) - mean::arithmetic(y)).abs() >= observed {
+			count += 1;
+		}
+	}
+	Some(count as f64 / permutations as f64)
+}
+
+/// Calculate quantile `q` of data set `slice`.
+///
+/// Interpolates linearly between the closest ranks, which is the default
+/// method of both R (type 7) and NumPy.
+/// Returns `NAN` if `slice` is empty or `q` is outside of `[0, 1]`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `q` - quantile to calculate
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 2., 3., 4., 5.];
+/// assert_eq!(stats::quantile(&slice, 0.25), 2.);
+/// ```
+pub fn quantile(slice: &[f64], q: f64) -> f64 {
+	quantile_with(slice, q, QuantileMethod::Linear)
+}
+
+/// Calculate quantile `q` of data set `slice` using method `method`.
+///
+/// The quantile is located at position `q * (n - 1)` of the sorted data
+/// and `method` decides how a position between two data points is
+/// resolved. Returns `NAN` if `slice` is empty or `q` is outside of
+/// `[0, 1]`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `q` - quantile to calculate
+/// * `method` - quantile method
+///
+/// # Example
+///
+/// ```
+/// use math::stats::{ self, QuantileMethod };
+///
+/// let slice = [1., 2., 3., 4.];
+/// assert_eq!(stats::quantile_with(&slice, 0.5, QuantileMethod::Linear), 2.5);
+/// assert_eq!(stats::quantile_with(&slice, 0.5, QuantileMethod::Lower), 2.);
+/// ```
+pub fn quantile_with(slice: &[f64], q: f64, method: QuantileMethod) -> f64 {
+	if slice.is_empty() || !(0. ..=1.).contains(&q) {
+		return f64::NAN;
+	}
+	let sorted = sorted(slice);
+	let position = q * (sorted.len() - 1) as f64;
+	let lower = sorted[position.floor() as usize];
+	let upper = sorted[position.ceil() as usize];
+	match method {
+		QuantileMethod::Linear => lower + (upper - lower) * position.fract(),
+		QuantileMethod::Lower => lower,
+		QuantileMethod::Higher => upper,
+		QuantileMethod::Nearest => sorted[position.round_ties_even() as usize],
+		QuantileMethod::Midpoint => (lower + upper) / 2.,
+	}
+}
+
+/// Scale data set `slice` robustly.
+///
+/// Transforms each value `x` to `(x - median) / IQR`, which unlike
+/// standard scores is barely affected by outliers.
+/// If the interquartile range is zero or undefined, i.e. `slice` has a
+/// single value, all values are scaled to zero.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 2., 3., 4., 5.];
+/// assert_eq!(stats::robust_scale(&slice), vec![-1., -0.5, 0., 0.5, 1.]);
+/// ```
+pub fn robust_scale(slice: &[f64]) -> Vec<f64> {
+	let median = median(slice);
+	let iqr = iqr(slice);
+	slice.iter().map(|x| match iqr == 0. || iqr.is_nan() {
+		true => 0.,
+		false => (x - median) / iqr,
+	}).collect()
+}
+
+/// Calculate arithmetic mean of every row of matrix `m`.
+///
+/// # Arguments
+///
+/// * `m` - matrix
+///
+/// # Example
+///
+/// ```
+/// use math::matrix::Matrix;
+/// use math::stats;
+///
+/// let m = Matrix::from_rows(&[vec![1., 2.], vec![3., 6.]]).unwrap();
+/// assert_eq!(stats::row_means(&m), vec![1.5, 4.5]);
+/// ```
+pub fn row_means(m: &Matrix) -> Vec<f64> {
+	(0..m.rows()).map(|i| mean::arithmetic(m.row(i))).collect()
+}
+
+/// Decompose time series `slice` into trend, seasonal and residual parts.
+///
+/// Returns `(trend, seasonal, residual)` of an additive model where the
+/// three components sum to `slice`. The trend is a moving average over
+/// `period` centered on each value; an even period averages two adjacent
+/// windows. The seasonal component repeats the mean detrended value of
+/// each position within the period, shifted to sum to zero over a period,
+/// and the residual is what remains. The trend and residual are `NAN` for
+/// the first and last `period / 2` values, which have no full window.
+/// Returns `None` if `period` is less than 2 or `slice` is shorter than
+/// two periods.
+///
+/// # Arguments
+///
+/// * `slice` - time series
+/// * `period` - number of values in a season
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 3., 1., 3., 1., 3.];
+/// let (trend, seasonal, residual) = stats::seasonal_decompose(&slice, 2).unwrap();
+/// assert_eq!(trend[1..5].to_vec(), vec![2.; 4]);
+/// assert_eq!(seasonal, vec![-1., 1., -1., 1., -1., 1.]);
+/// assert_eq!(residual[1..5].to_vec(), vec![0.; 4]);
+/// ```
+pub fn seasonal_decompose(slice: &[f64], period: usize) -> Option<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+	if period < 2 || slice.len() < 2 * period {
+		return None;
+	}
+	let n = slice.len();
+	let half = period / 2;
+	let weights: Vec<f64> = match period.is_multiple_of(2) {
+		true => (0..=period).map(|i| match i == 0 || i == period {
+			true => 0.5 / period as f64,
+			false => 1. / period as f64,
+		}).collect(),
+		false => vec![1. / period as f64; period],
+	};
+	let mut trend = vec![f64::NAN; n];
+	for (i, t) in trend.iter_mut().enumerate().take(n - half).skip(half) {
+		*t = weights.iter().zip(&slice[i - half..]).map(|(w, x)| w * x).sum();
+	}
+	let mut sums = vec![0.; period];
+	let mut counts = vec![0.; period];
+	for i in half..n - half {
+		sums[i % period] += slice[i] - trend[i];
+		counts[i % period] += 1.;
+	}
+	let means: Vec<f64> = sums.iter().zip(&counts).map(|(s, c)| s / c).collect();
+	let offset = mean::arithmetic(&means);
+	let seasonal: Vec<f64> = (0..n).map(|i| means[i % period] - offset).collect();
+	let residual = slice.iter().zip(&trend).zip(&seasonal)
+		.map(|((x, t), s)| x - t - s)
+		.collect();
+	Some((trend, seasonal, residual))
+}
+
+/// Calculate mean of data set `slice` shrunk towards a prior.
+///
+/// Returns `(prior_weight * prior_mean + Σx) / (prior_weight + n)`, so the
+/// prior acts as `prior_weight` pseudo-observations. Small samples stay
+/// close to `prior_mean` while large samples approach their own mean, as
+/// in Bayesian average ratings.
+/// Returns `prior_mean` if `slice` is empty and `NAN` if `prior_weight`
+/// is negative.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+/// * `prior_mean` - mean to shrink towards
+/// * `prior_weight` - number of pseudo-observations of the prior
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let ratings = [5., 5., 4.];
+/// assert_eq!(stats::shrunk_mean(&ratings, 3., 7.), 3.5);
+/// ```
+pub fn shrunk_mean(slice: &[f64], prior_mean: f64, prior_weight: f64) -> f64 {
+	if prior_weight.is_nan() || prior_weight < 0. {
+		return f64::NAN;
+	}
+	if slice.is_empty() {
+		return prior_mean;
+	}
+	let sum = slice.iter().fold(0., |a, x| a + x);
+	(prior_weight * prior_mean + sum) / (prior_weight + slice.len() as f64)
+}
+
+/// Calculate kernel density bandwidth of data set `slice` by Silverman's rule.
+///
+/// Returns `0.9 * min(std_dev, IQR / 1.34) * n^(-1/5)`, the usual rule of
+/// thumb for Gaussian kernels. The standard deviation alone is used if the
+/// interquartile range is zero.
+/// Returns `NAN` if `slice` has fewer than two values.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [2.1, 3.4, 1.9, 5.6, 4.4, 3.3, 2.8, 4.9, 3.7, 3.1];
+/// assert!((stats::silverman_bandwidth(&slice) - 0.572099).abs() < 1e-6);
+/// ```
+pub fn silverman_bandwidth(slice: &[f64]) -> f64 {
+	if slice.len() < 2 {
+		return f64::NAN;
+	}
+	let std_dev = variance::sample(slice).sqrt();
+	let spread = match iqr(slice) {
+		iqr if iqr > 0. => std_dev.min(iqr / 1.34),
+		_ => std_dev,
+	};
+	0.9 * spread * (slice.len() as f64).powf(-0.2)
+}
+
+/// Calculate skewness of data set `slice`.
+///
+/// The skewness is the third standardized moment `m3 / m2^1.5` of the
+/// population central moments, positive when the right tail is longer.
+/// Returns `NAN` if `slice` has fewer than three values or zero variance.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let slice = [1., 2., 3., 4., 5.];
+/// assert_eq!(stats::skewness(&slice), 0.);
+/// assert!(stats::skewness(&[1., 2., 3., 10.]) > 1.);
+/// ```
+pub fn skewness(slice: &[f64]) -> f64 {
+	match slice.len() < 3 || is_constant(slice) {
+		true => f64::NAN,
+		false => central_moment(slice, 3) / central_moment(slice, 2).powf(1.5),
+	}
+}
+
+/// Calculate 1-Wasserstein distance between data sets `a` and `b`.
+///
+/// Also known as the earth mover's distance, it is the area between the
+/// empirical cumulative distribution functions of the data sets.
+/// Returns `NAN` if either data set is empty.
+///
+/// # Arguments
+///
+/// * `a` - first collection of values
+/// * `b` - second collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::stats;
+///
+/// let a = [0., 1., 3.];
+/// let b = [5., 6., 8.];
+/// assert_eq!(stats::wasserstein_1d(&a, &b), 5.);
+/// ```
+pub fn wasserstein_1d(a: &[f64], b: &[f64]) -> f64 {
+	if a.is_empty() || b.is_empty() {
+		return f64::NAN;
+	}
+	let a = sorted(a);
+	let b = sorted(b);
+	let support = sorted(&[&a[..], &b[..]].concat());
+	support.windows(2).fold(0., |sum, pair| {
+		let fa = a.partition_point(|x| *x <= pair[0]) as f64 / a.len() as f64;
+		let fb = b.partition_point(|x| *x <= pair[0]) as f64 / b.len() as f64;
+		sum + (fa - fb).abs() * (pair[1] - pair[0])
+	})
+}
+
+/// Gaussian kernel density estimate.
+///
+/// Estimates the probability density of a data set as the average of
+/// normal distributions centred on the data points, with standard
+/// deviation equal to the bandwidth.
+///
+/// # Example
+///
+/// ```
+/// use math::stats::GaussianKde;
+///
+/// let kde = GaussianKde::new(&[1., 2., 2.5, 4.], Some(0.5)).unwrap();
+/// assert!(kde.pdf(2.2) > kde.pdf(6.));
+/// assert_eq!(kde.cdf(f64::INFINITY), 1.);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussianKde {
+	data: Vec<f64>,
+	bandwidth: f64,
+}
+
+impl GaussianKde {
+	/// Create density estimate of data set `data`.
+	///
+	/// If `bandwidth` is `None` it is chosen by Silverman's rule of thumb.
+	/// Returns `None` if `data` is empty, the given bandwidth is not positive
+	/// or, without a given bandwidth, `data` has fewer than two distinct
+	/// values.
+	///
+	/// # Arguments
+	///
+	/// * `data` - collection of values
+	/// * `bandwidth` - kernel standard deviation
+	pub fn new(data: &[f64], bandwidth: Option<f64>) -> Option<GaussianKde> {
+		let bandwidth = bandwidth.unwrap_or_else(|| silverman_bandwidth(data));
+		match !data.is_empty() && bandwidth > 0. && bandwidth.is_finite() {
+			true => Some(GaussianKde { data: data.to_vec(), bandwidth }),
+			false => None,
+		}
+	}
+
+	/// Kernel bandwidth.
+	pub fn bandwidth(&self) -> f64 {
+		self.bandwidth
+	}
+
+	/// Evaluate cumulative distribution function at `x`.
+	///
+	/// # Arguments
+	///
+	/// * `x` - point to evaluate at
+	pub fn cdf(&self, x: f64) -> f64 {
+		let sum = self.data.iter().fold(0., |a, d| {
+			a + 0.5 * (1. + special::erf((x - d) / (self.bandwidth * 2f64.sqrt())))
+		});
+		sum / self.data.len() as f64
+	}
+
+	/// Evaluate probability density function at `x`.
+	///
+	/// # Arguments
+	///
+	/// * `x` - point to evaluate at
+	pub fn pdf(&self, x: f64) -> f64 {
+		let sum = self.data.iter().fold(0., |a, d| a + (-((x - d) / self.bandwidth).powi(2) / 2.).exp());
+		sum / (self.data.len() as f64 * self.bandwidth * (2. * PI).sqrt())
+	}
+
+	/// Draw random value from the estimated distribution.
+	///
+	/// Picks a data point uniformly at random and adds normally distributed
+	/// noise with the bandwidth as standard deviation.
+	///
+	/// # Arguments
+	///
+	/// * `rng` - random number generator
+	pub fn sample<R: rand::Rng>(&self, rng: &mut R) -> f64 {
+		let point = self.data[rng.gen_range(0, self.data.len())];
+		let rand::distributions::normal::StandardNormal(noise) = rng.gen();
+		point + noise * self.bandwidth
+	}
+}
+
+fn autocovariance(deviations: &[f64], lag: usize) -> f64 {
+	deviations.iter().zip(&deviations[lag..]).fold(0., |a, (x, y)| a + x * y)
+}
+
+fn bin_edges(min: f64, max: f64, bins: usize) -> Vec<f64> {
+	// halving keeps the range finite for values near the float limits
+	match (max - min).is_finite() {
+		true => interpolate::linspace(min, max, bins + 1),
+		false => interpolate::linspace(min / 2., max / 2., bins + 1).iter().map(|e| e * 2.).collect(),
+	}
+}
+
+fn bin_index(edges: &[f64], x: f64) -> usize {
+	// searched in the returned edges so that every value lies within the
+	// edges of its bin; equal edges put everything in the first bin
+	let bins = edges.len() - 1;
+	match edges[bins] > edges[0] {
+		true => edges[1..bins].partition_point(|edge| *edge <= x),
+		false => 0,
+	}
+}
+
+fn central_moment(slice: &[f64], k: i32) -> f64 {
+	let mean = mean::arithmetic(slice);
+	let powers: Vec<f64> = slice.iter().map(|x| (x - mean).powi(k)).collect();
+	mean::arithmetic(&powers)
+}
+
+fn compensated_add(sum: &mut f64, compensation: &mut f64, x: f64) {
+	// Neumaier's summation step as in mean::arithmetic
+	let t = *sum + x;
+	*compensation += match sum.abs() >= x.abs() {
+		true => (*sum - t) + x,
+		false => (x - t) + *sum,
+	};
+	*sum = t;
+}
+
+fn deviations(slice: &[f64]) -> Option<(Vec<f64>, f64)> {
+	// deviations from the mean and their sum of squares, None if empty or
+	// constant
+	if slice.is_empty() {
+		return None;
+	}
+	let mean = mean::arithmetic(slice);
+	let deviations: Vec<f64> = slice.iter().map(|x| x - mean).collect();
+	let squares = deviations.iter().fold(0., |a, d| a + d * d);
+	match squares == 0. {
+		true => None,
+		false => Some((deviations, squares)),
+	}
+}
+
+fn is_constant(slice: &[f64]) -> bool {
+	// the mean of constant data need not equal the constant, e.g. for 0.1,
+	// so the central moments are not exactly zero
+	slice.iter().all(|x| *x == slice[0])
+}
+
+fn sorted(slice: &[f64]) -> Vec<f64> {
+	let mut sorted = slice.to_vec();
+	sorted.sort_by(|a, b| a.total_cmp(b));
+	sorted
+}
+
+#[cfg(test)]
+mod tests {
+	use super::QuantileMethod;
+	use super::rand::{ Rng, SeedableRng, StdRng };
+	use interpolate;
+	use matrix::Matrix;
+	use mean;
+	use round;
+	use variance;
+
+	macro_rules! test_stat {
+		($func:path [ $($name:ident: $params:expr,)* ]) => {
+		$(
+			#[test]
+			fn $name() {
+				let (slice, expected): (&[f64], f64) = $params;
+				let result = $func(slice);
+				match result.is_nan() {
+					true => assert_eq!(expected.is_nan(), true),
+					false => assert_eq!(round::half_up(result, 6), expected),
+				}
+			}
+		)*
+		}
+	}
+
+	test_stat! { super::iqr [
+		iqr_1: (&[1., 2., 3., 4., 5.], 2.),
+		iqr_2: (&[7., 1., 5., 3.], 3.),
+		iqr_3: (&[4., 4., 4.], 0.),
+		iqr_4: (&[], f64::NAN),
+	]}
+
+	test_stat! { super::median [
+		median_1: (&[3., 1., 2.], 2.),
+		median_2: (&[4., 1., 3., 2.], 2.5),
+		median_3: (&[5.], 5.),
+		median_4: (&[], f64::NAN),
+	]}
+
+	#[test]
+	fn binned_statistic() {
+		let x = [0.5, 1.5, 2.5, 3.5, 4.];
+		let values = [1., 2., 3., 4., 5.];
+		let (means, edges) = super::binned_statistic(&x, &values, 2, mean::arithmetic).unwrap();
+		assert_eq!(means, vec![1.5, 4.]);
+		assert_eq!(edges, vec![0.5, 2.25, 4.]);
+		let (counts, _) = super::binned_statistic(&x, &values, 2, |bin| bin.len() as f64).unwrap();
+		assert_eq!(counts, vec![2., 3.]);
+	}
+
+	#[test]
+	fn binned_statistic_empty_bins() {
+		let (counts, edges) = super::binned_statistic(&[0., 0.1, 1.], &[1., 2., 3.], 4, |bin| bin.len() as f64).unwrap();
+		assert_eq!(counts[0], 2.);
+		assert!(counts[1].is_nan() && counts[2].is_nan());
+		assert_eq!(counts[3], 1.);
+		assert_eq!(edges, vec![0., 0.25, 0.5, 0.75, 1.]);
+	}
+
+	#[test]
+	fn binned_statistic_edges() {
+		let x = [3., -1., 7., 2., 5., 0.];
+		let (_, edges) = super::binned_statistic(&x, &x, 4, mean::arithmetic).unwrap();
+		assert_eq!(edges.len(), 5);
+		assert_eq!((edges[0], edges[4]), (-1., 7.));
+		for pair in edges.windows(2) {
+			assert_eq!(pair[1] - pair[0], 2.);
+		}
+	}
+
+	#[test]
+	fn binned_statistic_on_edge() {
+		let (counts, edges) = super::binned_statistic(&[2.9, 2.36, 2., 2.15], &[1.; 4], 5, |bin| bin.len() as f64).unwrap();
+		assert_eq!(edges[2], 2.36);
+		assert_eq!(counts[2], 1.);
+		assert!(counts[1].is_nan());
+	}
+
+	#[test]
+	fn binned_statistic_not_finite() {
+		let (means, edges) = super::binned_statistic(&[1., f64::NAN, 3.], &[1., 2., 3.], 2, mean::arithmetic).unwrap();
+		assert_eq!(means, vec![1., 3.]);
+		assert_eq!(edges, vec![1., 2., 3.]);
+		let (means, _) = super::binned_statistic(&[1., f64::INFINITY, 3.], &[1., 2., 3.], 2, mean::arithmetic).unwrap();
+		assert_eq!(means, vec![1., 3.]);
+		assert_eq!(super::binned_statistic(&[f64::NAN], &[1.], 2, mean::arithmetic), None);
+	}
+
+	#[test]
+	fn binned_statistic_wide_range() {
+		let x = [-f64::MAX, 1., f64::MAX];
+		let (means, edges) = super::binned_statistic(&x, &[1., 2., 3.], 2, mean::arithmetic).unwrap();
+		assert_eq!(edges, vec![-f64::MAX, 0., f64::MAX]);
+		assert_eq!(means, vec![1., 2.5]);
+	}
+
+	#[test]
+	fn binned_statistic_invalid() {
+		assert_eq!(super::binned_statistic(&[1., 2.], &[1.], 2, mean::arithmetic), None);
+		assert_eq!(super::binned_statistic(&[1., 2.], &[1., 2.], 0, mean::arithmetic), None);
+		assert_eq!(super::binned_statistic(&[], &[], 2, mean::arithmetic), None);
+	}
+
+	#[test]
+	fn silverman_bandwidth() {
+		let slice = [2.1, 3.4, 1.9, 5.6, 4.4, 3.3, 2.8, 4.9, 3.7, 3.1];
+		assert_eq!(round::half_up(super::silverman_bandwidth(&slice), 6), 0.572099);
+		let slice = [1., 1., 1., 1., 1., 2.];
+		assert_eq!(round::half_up(super::silverman_bandwidth(&slice), 6), 0.256765);
+	}
+
+	#[test]
+	fn silverman_bandwidth_shrinks() {
+		let slice = [2.1, 3.4, 1.9, 5.6, 4.4, 3.3, 2.8, 4.9, 3.7, 3.1];
+		let mut previous = super::silverman_bandwidth(&slice);
+		for n in 2..6 {
+			let repeated: Vec<f64> = slice.iter().cycle().take(slice.len() * n).cloned().collect();
+			let bandwidth = super::silverman_bandwidth(&repeated);
+			assert!(bandwidth < previous);
+			previous = bandwidth;
+		}
+	}
+
+	#[test]
+	fn silverman_bandwidth_small() {
+		assert!(super::silverman_bandwidth(&[]).is_nan());
+		assert!(super::silverman_bandwidth(&[1.]).is_nan());
+	}
+
+	fn sample_matrix() -> Matrix {
+		Matrix::from_rows(&[vec![1., 10., -2.], vec![4., 20., -2.], vec![7., 60., -2.], vec![2., 30., -2.]]).unwrap()
+	}
+
+	#[test]
+	fn column_means() {
+		assert_eq!(super::column_means(&sample_matrix()), vec![3.5, 30., -2.]);
+	}
+
+	#[test]
+	fn column_medians() {
+		assert_eq!(super::column_medians(&sample_matrix()), vec![3., 25., -2.]);
+	}
+
+	#[test]
+	fn column_variances() {
+		let m = sample_matrix();
+		let variances = super::column_variances(&m);
+		assert_eq!(variances, vec![7., 1400. / 3., 0.]);
+		let rows: Vec<Vec<f64>> = (0..m.rows()).map(|i| m.row(i).to_vec()).collect();
+		let cov = super::covariance_matrix(&rows).unwrap();
+		for (j, variance) in variances.iter().enumerate() {
+			assert_eq!(*variance, cov[(j, j)]);
+		}
+	}
+
+	#[test]
+	fn row_means() {
+		assert_eq!(super::row_means(&sample_matrix()), vec![3., 22. / 3., 65. / 3., 10.]);
+	}
+
+	#[test]
+	fn matrix_statistics_single_row() {
+		let m = Matrix::from_rows(&[vec![1., 2., 3.]]).unwrap();
+		assert_eq!(super::column_means(&m), vec![1., 2., 3.]);
+		assert_eq!(super::column_medians(&m), vec![1., 2., 3.]);
+		assert!(super::column_variances(&m).iter().all(|v| v.is_nan()));
+		assert_eq!(super::row_means(&m), vec![2.]);
+	}
+
+	#[test]
+	fn matrix_statistics_single_column() {
+		let m = Matrix::from_rows(&[vec![1.], vec![2.], vec![6.]]).unwrap();
+		assert_eq!(super::column_means(&m), vec![3.]);
+		assert_eq!(super::column_medians(&m), vec![2.]);
+		assert_eq!(super::column_variances(&m), vec![7.]);
+		assert_eq!(super::row_means(&m), vec![1., 2., 6.]);
+	}
+
+	#[test]
+	fn correlation() {
+		assert_eq!(super::correlation(&[1., 2., 3.], &[2., 4., 6.]), 1.);
+		assert_eq!(super::correlation(&[1., 2., 3.], &[6., 4., 2.]), -1.);
+		assert_eq!(super::correlation(&[-3., -1., 1., 3.], &[1., -1., -1., 1.]), 0.);
+		assert!(super::correlation(&[1., 2.], &[1., 2., 3.]).is_nan());
+		assert!(super::correlation(&[], &[]).is_nan());
+		assert!(super::correlation(&[1., 2., 3.], &[2., 2., 2.]).is_nan());
+	}
+
+	#[test]
+	fn partial_correlation_uncorrelated_control() {
+		let xs = [1., 2., 3., 4.];
+		let ys = [2., 1., 4., 3.];
+		let zs = [1., -1., -1., 1.];
+		let partial = super::partial_correlation(&xs, &ys, &zs);
+		assert_eq!(round::half_up(partial, 12), round::half_up(super::correlation(&xs, &ys), 12));
+	}
+
+	#[test]
+	fn partial_correlation_explained() {
+		let zs = [-3., -1., 1., 3.];
+		let xs: Vec<f64> = zs.iter().zip([1., -1., -1., 1.].iter()).map(|(z, e)| z + e).collect();
+		let ys: Vec<f64> = zs.iter().zip([-1., 3., -3., 1.].iter()).map(|(z, e)| z + e).collect();
+		assert!(super::correlation(&xs, &ys) > 0.5);
+		assert!(super::partial_correlation(&xs, &ys, &zs).abs() < 1e-12);
+	}
+
+	#[test]
+	fn partial_correlation_invalid() {
+		assert!(super::partial_correlation(&[1., 2., 3.], &[1., 2.], &[1., 2., 3.]).is_nan());
+		assert!(super::partial_correlation(&[], &[], &[]).is_nan());
+		assert!(super::partial_correlation(&[1., 2., 3.], &[3., 1., 2.], &[2., 4., 6.]).is_nan());
+	}
+
+	fn condition_number(m: &Matrix) -> f64 {
+		let mean = (m[(0, 0)] + m[(1, 1)]) / 2.;
+		let radius = (((m[(0, 0)] - m[(1, 1)]) / 2.).powi(2) + m[(0, 1)].powi(2)).sqrt();
+		(mean + radius) / (mean - radius)
+	}
+
+	const OBSERVATIONS: [[f64; 2]; 4] = [[1., 2.], [2., 4.1], [3., 5.9], [4., 8.2]];
+
+	fn observations() -> Vec<Vec<f64>> {
+		OBSERVATIONS.iter().map(|row| row.to_vec()).collect()
+	}
+
+	#[test]
+	fn covariance_matrix() {
+		let cov = super::covariance_matrix(&observations()).unwrap();
+		assert_eq!(round::half_up(cov[(0, 0)], 6), 1.666667);
+		assert_eq!(round::half_up(cov[(0, 1)], 6), 3.4);
+		assert_eq!(cov[(0, 1)], cov[(1, 0)]);
+		assert_eq!(super::covariance_matrix(&[vec![1., 2.]]), None);
+		assert_eq!(super::covariance_matrix(&[vec![1., 2.], vec![1.]]), None);
+	}
+
+	#[test]
+	fn covariance_shrinkage_none() {
+		let cov = super::covariance_shrinkage(&observations(), 0.);
+		assert_eq!(cov, super::covariance_matrix(&observations()));
+	}
+
+	#[test]
+	fn covariance_shrinkage_full() {
+		let cov = super::covariance_matrix(&observations()).unwrap();
+		let target = (cov[(0, 0)] + cov[(1, 1)]) / 2.;
+		let shrunk = super::covariance_shrinkage(&observations(), 1.).unwrap();
+		assert_eq!(shrunk[(0, 0)], target);
+		assert_eq!(shrunk[(1, 1)], target);
+		assert_eq!(shrunk[(0, 1)], 0.);
+		assert_eq!(shrunk[(1, 0)], 0.);
+	}
+
+	#[test]
+	fn covariance_shrinkage_conditioning() {
+		let cov = super::covariance_matrix(&observations()).unwrap();
+		let shrunk = super::covariance_shrinkage(&observations(), 0.3).unwrap();
+		assert!(condition_number(&shrunk) < condition_number(&cov) / 10.);
+	}
+
+	#[test]
+	fn covariance_shrinkage_invalid() {
+		assert_eq!(super::covariance_shrinkage(&observations(), -0.1), None);
+		assert_eq!(super::covariance_shrinkage(&observations(), 1.1), None);
+		assert_eq!(super::covariance_shrinkage(&observations(), f64::NAN), None);
+		assert_eq!(super::covariance_shrinkage(&[vec![1., 2.], vec![3.]], 0.5), None);
+	}
+
+	#[test]
+	fn permutation_test_different() {
+		let a = [1.2, 2.3, 1.9, 2.8, 1.5, 2.1];
+		let b = [5.1, 4.8, 6.2, 5.5, 4.9, 5.8];
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let p = super::permutation_test(&a, &b, 2000, &mut rng).unwrap();
+		assert!(p < 0.01);
+	}
+
+	#[test]
+	fn permutation_test_similar() {
+		let a = [1., 3., 5., 7., 9.];
+		let b = [2., 4., 6., 8., 10.];
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let p = super::permutation_test(&a, &b, 2000, &mut rng).unwrap();
+		assert!(p > 0.5);
+	}
+
+	#[test]
+	fn permutation_test_deterministic() {
+		let a = [1., 4., 2., 8.];
+		let b = [3., 9., 7.];
+		let mut x = StdRng::from_seed(&[42][..]);
+		let mut y = StdRng::from_seed(&[42][..]);
+		assert_eq!(super::permutation_test(&a, &b, 500, &mut x), super::permutation_test(&a, &b, 500, &mut y));
+	}
+
+	#[test]
+	fn permutation_test_empty() {
+		let mut rng = StdRng::from_seed(&[1][..]);
+		assert_eq!(super::permutation_test(&[], &[1.], 10, &mut rng), None);
+		assert_eq!(super::permutation_test(&[1.], &[], 10, &mut rng), None);
+		assert_eq!(super::permutation_test(&[1.], &[2.], 0, &mut rng), None);
+	}
+
+	#[test]
+	fn quantile_bounds() {
+		let slice = [3., 1., 4., 1., 5.];
+		assert_eq!(super::quantile(&slice, 0.), 1.);
+		assert_eq!(super::quantile(&slice, 1.), 5.);
+		assert!(super::quantile(&slice, -0.1).is_nan());
+		assert!(super::quantile(&slice, 1.1).is_nan());
+	}
+
+	#[test]
+	fn robust_scale_median_is_zero() {
+		let slice = [9., 1., 5., 3., 7.];
+		assert_eq!(super::robust_scale(&slice)[2], 0.);
+	}
+
+	#[test]
+	fn robust_scale_outlier() {
+		let scaled = super::robust_scale(&[1., 2., 3., 4., 5.]);
+		let outlier = super::robust_scale(&[1., 2., 3., 4., 5., 1000.]);
+		for (a, b) in scaled.iter().zip(outlier.iter()) {
+			assert!((a - b).abs() <= 0.5);
+		}
+	}
+
+	#[test]
+	fn robust_scale_zero_iqr() {
+		assert_eq!(super::robust_scale(&[2., 2., 2., 2.]), vec![0., 0., 0., 0.]);
+	}
+
+	#[test]
+	fn robust_scale_single() {
+		assert_eq!(super::robust_scale(&[7.]), vec![0.]);
+	}
+
+	#[test]
+	fn robust_scale_empty() {
+		assert_eq!(super::robust_scale(&[]), Vec::<f64>::new());
+	}
+
+	#[test]
+	fn wasserstein_1d_identical() {
+		let a = [3., 1., 4., 1., 5.];
+		assert_eq!(super::wasserstein_1d(&a, &a), 0.);
+	}
+
+	#[test]
+	fn wasserstein_1d_shift() {
+		let a = [3., 1., 4., 1., 5., 9., 2., 6.];
+		for c in [-2.5, 0.75, 10.].iter() {
+			let b: Vec<f64> = a.iter().map(|x| x + c).collect();
+			assert!((super::wasserstein_1d(&a, &b) - c.abs()).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn wasserstein_1d_asymmetric() {
+		assert_eq!(super::wasserstein_1d(&[0.], &[1., 2.]), 1.5);
+		assert_eq!(super::wasserstein_1d(&[1., 2.], &[0.]), 1.5);
+		assert_eq!(super::wasserstein_1d(&[0., 1.], &[0., 3.]), 1.);
+	}
+
+	#[test]
+	fn wasserstein_1d_empty() {
+		assert!(super::wasserstein_1d(&[], &[1.]).is_nan());
+		assert!(super::wasserstein_1d(&[1.], &[]).is_nan());
+	}
+
+	#[test]
+	fn pearson_test_strong() {
+		let xs: Vec<f64> = (0..20).map(|i| i as f64).collect();
+		let ys: Vec<f64> = xs.iter().map(|x| 2. * x + (x * 1.7).sin()).collect();
+		let (r, p) = super::pearson_test(&xs, &ys).unwrap();
+		assert_eq!(r, super::correlation(&xs, &ys));
+		assert!(p < 1e-10);
+	}
+
+	#[test]
+	fn pearson_test_weak() {
+		let xs: Vec<f64> = (0..50).map(|i| i as f64).collect();
+		let ys: Vec<f64> = xs.iter().map(|x| (x * 2.3).sin()).collect();
+		let (r, p) = super::pearson_test(&xs, &ys).unwrap();
+		assert!(r.abs() < 0.1);
+		assert!(p > 0.5);
+	}
+
+	#[test]
+	fn pearson_test_reference() {
+		// r = 149 / 165 with n = 10 gives t = 5.945747 and p = 0.000344
+		let xs = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+		let ys = [2., 1., 4., 3., 7., 5., 6., 9., 10., 8.];
+		let (r, p) = super::pearson_test(&xs, &ys).unwrap();
+		assert!((r - 149. / 165.).abs() < 1e-12);
+		assert!((p - 0.000343612).abs() < 1e-8);
+	}
+
+	#[test]
+	fn pearson_test_invalid() {
+		assert_eq!(super::pearson_test(&[1., 2.], &[2., 1.]), None);
+		assert_eq!(super::pearson_test(&[1., 2., 3.], &[2., 1.]), None);
+		assert_eq!(super::pearson_test(&[1., 2., 3.], &[2., 4., 6.]), None);
+		assert_eq!(super::pearson_test(&[1., 2., 3.], &[5., 5., 5.]), None);
+	}
+
+	#[test]
+	fn cov_to_corr() {
+		let data = [vec![1., 2., 9.], vec![2., 4.1, 7.], vec![3., 5.9, 8.], vec![4., 8.2, 3.], vec![5., 9.7, 4.]];
+		let corr = super::cov_to_corr(&super::covariance_matrix(&data).unwrap()).unwrap();
+		for i in 0..3 {
+			assert_eq!(corr[(i, i)], 1.);
+			for j in 0..3 {
+				assert_eq!(corr[(i, j)], corr[(j, i)]);
+				assert!(corr[(i, j)].abs() <= 1.);
+				let xs: Vec<f64> = data.iter().map(|row| row[i]).collect();
+				let ys: Vec<f64> = data.iter().map(|row| row[j]).collect();
+				assert!((corr[(i, j)] - super::correlation(&xs, &ys)).abs() < 1e-12);
+			}
+		}
+	}
+
+	#[test]
+	fn cov_to_corr_diagonal() {
+		let cov = Matrix::from_rows(&[vec![4., 0., 0.], vec![0., 0.25, 0.], vec![0., 0., 9.]]).unwrap();
+		assert_eq!(super::cov_to_corr(&cov), Some(Matrix::identity(3)));
+	}
+
+	#[test]
+	fn cov_to_corr_invalid() {
+		let zero = Matrix::from_rows(&[vec![1., 0.], vec![0., 0.]]).unwrap();
+		let negative = Matrix::from_rows(&[vec![-1., 0.], vec![0., 1.]]).unwrap();
+		let nan = Matrix::from_rows(&[vec![f64::NAN, 0.], vec![0., 1.]]).unwrap();
+		assert_eq!(super::cov_to_corr(&zero), None);
+		assert_eq!(super::cov_to_corr(&negative), None);
+		assert_eq!(super::cov_to_corr(&nan), None);
+		assert_eq!(super::cov_to_corr(&Matrix::zeros(2, 3)), None);
+	}
+
+	#[test]
+	fn durbin_watson_independent() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let residuals: Vec<f64> = (0..2000).map(|_| rng.gen_range(-1., 1.)).collect();
+		assert!((super::durbin_watson(&residuals) - 2.).abs() < 0.15);
+	}
+
+	#[test]
+	fn durbin_watson_autocorrelated() {
+		let positive: Vec<f64> = (0..100).map(|i| (i as f64 / 10.).sin()).collect();
+		assert!(super::durbin_watson(&positive) < 0.1);
+		let negative: Vec<f64> = (0..100).map(|i| (-1f64).powi(i) * (1. + (i as f64 / 10.).sin() / 2.)).collect();
+		assert!(super::durbin_watson(&negative) > 3.);
+	}
+
+	#[test]
+	fn durbin_watson_invalid() {
+		assert!(super::durbin_watson(&[]).is_nan());
+		assert!(super::durbin_watson(&[1.5]).is_nan());
+		assert!(super::durbin_watson(&[0., 0., 0.]).is_nan());
+	}
+
+	#[test]
+	fn acf() {
+		let acf = super::acf(&[1., 3., 2., 5., 4.], 10);
+		assert_eq!(acf.len(), 5);
+		assert_eq!(acf[0], 1.);
+		assert!(acf[1].abs() < 1e-12);
+		assert!((acf[2] - 0.1).abs() < 1e-12);
+		assert_eq!(super::acf(&[1., 3., 2.], 0), vec![1.]);
+		assert_eq!(super::acf(&[], 3), vec![]);
+		assert_eq!(super::acf(&[2., 2., 2.], 3), vec![]);
+	}
+
+	#[test]
+	fn effective_sample_size_independent() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let slice: Vec<f64> = (0..5000).map(|_| rng.gen_range(-1., 1.)).collect();
+		let ess = super::effective_sample_size(&slice);
+		assert!(ess > 4000. && ess <= 5000. * 1.2);
+	}
+
+	#[test]
+	fn effective_sample_size_autocorrelated() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let mut x = 0.;
+		let slice: Vec<f64> = (0..5000).map(|_| {
+			x = 0.95 * x + rng.gen_range(-1., 1.);
+			x
+		}).collect();
+		// AR(1) with coefficient 0.95 has n * 0.05 / 1.95 effective samples
+		let ess = super::effective_sample_size(&slice);
+		assert!(ess > 50. && ess < 250.);
+	}
+
+	#[test]
+	fn effective_sample_size_periodic() {
+		let slice: Vec<f64> = (0..1000).map(|i| (i as f64 * std::f64::consts::PI / 10.).sin()).collect();
+		assert!(super::effective_sample_size(&slice) < 1000. / 4.);
+		let alternating: Vec<f64> = (0..1000).map(|i| (-1f64).powi(i)).collect();
+		assert_eq!(super::effective_sample_size(&alternating), 1000.);
+	}
+
+	#[test]
+	fn effective_sample_size_invalid() {
+		assert!(super::effective_sample_size(&[]).is_nan());
+		assert!(super::effective_sample_size(&[1.]).is_nan());
+		assert!(super::effective_sample_size(&[3., 3., 3.]).is_nan());
+	}
+
+	#[test]
+	fn quantile_with() {
+		let slice = [6., 1., 4., 2., 5., 3.];
+		let cases = [
+			(QuantileMethod::Linear, 2.25, 4.75),
+			(QuantileMethod::Lower, 2., 4.),
+			(QuantileMethod::Higher, 3., 5.),
+			(QuantileMethod::Nearest, 2., 5.),
+			(QuantileMethod::Midpoint, 2.5, 4.5),
+		];
+		for &(method, q1, q3) in cases.iter() {
+			assert_eq!(super::quantile_with(&slice, 0.25, method), q1);
+			assert_eq!(super::quantile_with(&slice, 0.75, method), q3);
+		}
+	}
+
+	#[test]
+	fn quantile_with_exact() {
+		let slice = [4., 1., 3., 2., 5.];
+		for &method in [QuantileMethod::Linear, QuantileMethod::Lower, QuantileMethod::Higher, QuantileMethod::Nearest, QuantileMethod::Midpoint].iter() {
+			assert_eq!(super::quantile_with(&slice, 0., method), 1.);
+			assert_eq!(super::quantile_with(&slice, 0.5, method), 3.);
+			assert_eq!(super::quantile_with(&slice, 1., method), 5.);
+			assert!(super::quantile_with(&[], 0.5, method).is_nan());
+			assert!(super::quantile_with(&slice, 1.5, method).is_nan());
+		}
+	}
+
+	#[test]
+	fn quantile_with_median_min_max() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		for n in 1..12 {
+			let slice: Vec<f64> = (0..n).map(|_| rng.gen_range(-50., 50.)).collect();
+			let min = slice.iter().cloned().fold(f64::INFINITY, f64::min);
+			let max = slice.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+			assert_eq!(super::quantile_with(&slice, 0.5, QuantileMethod::Linear), super::median(&slice));
+			assert_eq!(super::quantile_with(&slice, 0.5, QuantileMethod::Linear), mean::median(&slice));
+			for &method in [QuantileMethod::Linear, QuantileMethod::Lower, QuantileMethod::Higher, QuantileMethod::Nearest, QuantileMethod::Midpoint].iter() {
+				assert_eq!(super::quantile_with(&slice, 0., method), min);
+				assert_eq!(super::quantile_with(&slice, 1., method), max);
+				assert!(super::quantile_with(&slice, f64::NAN, method).is_nan());
+			}
+		}
+	}
+
+	#[test]
+	fn quantile_with_nearest_tie() {
+		let slice = [1., 2., 3., 4., 5., 6.];
+		assert_eq!(super::quantile_with(&slice, 0.5, QuantileMethod::Nearest), 3.);
+		assert_eq!(super::quantile_with(&slice, 0.7, QuantileMethod::Nearest), 5.);
+		assert_eq!(super::quantile_with(&slice, 0.3, QuantileMethod::Nearest), 3.);
+	}
+
+	#[test]
+	fn quantile_default_linear() {
+		let slice = [0.5, 7., 2.25, 9., 3.];
+		for i in 0..=20 {
+			let q = i as f64 / 20.;
+			assert_eq!(super::quantile(&slice, q), super::quantile_with(&slice, q, QuantileMethod::Linear));
+		}
+	}
+
+	#[test]
+	fn gaussian_kde_integral() {
+		let kde = super::GaussianKde::new(&[1.2, 1.9, 2.4, 3.1, 5.5, 5.8, 9.], None).unwrap();
+		let xs = interpolate::linspace(-20., 30., 5001);
+		let integral = xs.windows(2).fold(0., |a, w| a + (kde.pdf(w[0]) + kde.pdf(w[1])) / 2. * (w[1] - w[0]));
+		assert!((integral - 1.).abs() < 1e-9);
+		assert!(kde.cdf(-20.) < 1e-12);
+		assert!((kde.cdf(30.) - 1.).abs() < 1e-12);
+		assert!((kde.cdf(4.) - xs[..2401].windows(2).fold(0., |a, w| a + (kde.pdf(w[0]) + kde.pdf(w[1])) / 2. * (w[1] - w[0]))).abs() < 1e-6);
+	}
+
+	#[test]
+	fn gaussian_kde_dense() {
+		let kde = super::GaussianKde::new(&[0., 0.1, 0.2, -0.1, 0.05, 5.], Some(0.5)).unwrap();
+		assert_eq!(kde.bandwidth(), 0.5);
+		assert!(kde.pdf(0.05) > kde.pdf(5.));
+		assert!(kde.pdf(5.) > kde.pdf(2.5));
+		assert!(kde.cdf(1.) > 0.8);
+	}
+
+	#[test]
+	fn gaussian_kde_sample() {
+		let kde = super::GaussianKde::new(&[1., 2., 3.], Some(0.1)).unwrap();
+		let mut a = StdRng::from_seed(&[1, 2, 3][..]);
+		let mut b = StdRng::from_seed(&[1, 2, 3][..]);
+		let samples: Vec<f64> = (0..100).map(|_| kde.sample(&mut a)).collect();
+		let repeated: Vec<f64> = (0..100).map(|_| kde.sample(&mut b)).collect();
+		assert_eq!(samples, repeated);
+		assert!(samples.iter().all(|x| *x > 0. && *x < 4.));
+		assert!((mean::arithmetic(&samples) - 2.).abs() < 0.3);
+	}
+
+	#[test]
+	fn gaussian_kde_invalid() {
+		assert_eq!(super::GaussianKde::new(&[], Some(1.)), None);
+		assert_eq!(super::GaussianKde::new(&[], None), None);
+		assert_eq!(super::GaussianKde::new(&[1.], None), None);
+		assert_eq!(super::GaussianKde::new(&[2., 2.], None), None);
+		assert_eq!(super::GaussianKde::new(&[1., 2.], Some(0.)), None);
+		assert_eq!(super::GaussianKde::new(&[1., 2.], Some(f64::NAN)), None);
+		assert!(super::GaussianKde::new(&[1.], Some(1.)).is_some());
+	}
+
+	#[test]
+	fn shrunk_mean() {
+		let slice: Vec<f64> = (0..10_000).map(|i| (i % 10) as f64).collect();
+		let raw = mean::arithmetic(&slice);
+		assert!((super::shrunk_mean(&slice, 100., 5.) - raw).abs() < 0.1);
+		assert_eq!(super::shrunk_mean(&slice, 100., 0.), raw);
+		assert_eq!(super::shrunk_mean(&[], 3.5, 10.), 3.5);
+		assert_eq!(super::shrunk_mean(&[], 3.5, 0.), 3.5);
+		assert!((super::shrunk_mean(&[10.], 2., 1000.) - 2.).abs() < 0.01);
+		assert!(super::shrunk_mean(&[1.], 2., -1.).is_nan());
+	}
+
+	#[test]
+	fn shrunk_mean_transition() {
+		let values = [9., 8., 10., 9., 9., 8., 10., 9., 9., 9.];
+		let mut previous = super::shrunk_mean(&[], 5., 3.);
+		for n in 1..=values.len() {
+			let current = super::shrunk_mean(&values[..n], 5., 3.);
+			assert!(current > previous);
+			assert!(current < 9.);
+			previous = current;
+		}
+	}
+
+	#[test]
+	fn seasonal_decompose() {
+		let pattern = [2., -1., -3., 2.];
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let slice: Vec<f64> = (0..40)
+			.map(|i| 10. + 0.5 * i as f64 + pattern[i % 4] + rng.gen_range(-0.05, 0.05))
+			.collect();
+		let (trend, seasonal, residual) = super::seasonal_decompose(&slice, 4).unwrap();
+		for (i, s) in seasonal.iter().enumerate() {
+			assert!((s - pattern[i % 4]).abs() < 0.05);
+		}
+		for i in 0..40 {
+			match (2..38).contains(&i) {
+				true => assert!((trend[i] + seasonal[i] + residual[i] - slice[i]).abs() < 1e-12),
+				false => assert!(trend[i].is_nan() && residual[i].is_nan()),
+			}
+		}
+	}
+
+	#[test]
+	fn seasonal_decompose_odd_period() {
+		let pattern = [1., -2., 1.];
+		let slice: Vec<f64> = (0..9).map(|i| 3. + 0.2 * i as f64 + pattern[i % 3]).collect();
+		let (trend, seasonal, residual) = super::seasonal_decompose(&slice, 3).unwrap();
+		assert!(trend[0].is_nan() && trend[8].is_nan());
+		for i in 1..8 {
+			assert!((trend[i] - (3. + 0.2 * i as f64)).abs() < 1e-12);
+			assert!(residual[i].abs() < 1e-12);
+		}
+		for (i, s) in seasonal.iter().enumerate() {
+			assert!((s - pattern[i % 3]).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn seasonal_decompose_invalid() {
+		assert_eq!(super::seasonal_decompose(&[1., 2., 3., 4.], 1), None);
+		assert_eq!(super::seasonal_decompose(&[1., 2., 3., 4., 5.], 3), None);
+		assert!(super::seasonal_decompose(&[1., 2., 3., 4., 5., 6.], 3).is_some());
+	}
+
+	#[test]
+	fn iqr_too_few_values() {
+		assert!(super::iqr(&[]).is_nan());
+		assert!(super::iqr(&[5.]).is_nan());
+		assert_eq!(super::iqr(&[5., 9.]), 2.);
+	}
+
+	#[test]
+	fn iqr_known_quartiles() {
+		let slice = [0., 25., 50., 75., 100.];
+		assert_eq!(super::quantile(&slice, 0.25), 25.);
+		assert_eq!(super::quantile(&slice, 0.75), 75.);
+		assert_eq!(super::iqr(&slice), 50.);
+	}
+
+	#[test]
+	fn mad() {
+		assert_eq!(super::mad(&[1., 1., 2., 2., 4., 6., 9.]), 1.);
+		assert_eq!(super::mad(&[3., 8., 1., 5.]), 2.);
+		assert_eq!(super::mad(&[4., 4., 4.]), 0.);
+		assert!(super::mad(&[]).is_nan());
+		assert!(super::mad_normalized(&[]).is_nan());
+	}
+
+	#[test]
+	fn mad_outlier() {
+		let clean = [1., 2., 3., 4., 5.];
+		let outlier = [1., 2., 3., 4., 1000.];
+		assert_eq!(super::mad(&clean), 1.);
+		assert_eq!(super::mad(&outlier), 1.);
+		assert_eq!(super::mad_normalized(&outlier), super::mad_normalized(&clean));
+		assert!(variance::std_dev_sample(&outlier) > 100. * variance::std_dev_sample(&clean));
+	}
+
+	#[test]
+	fn skewness_symmetric() {
+		assert_eq!(super::skewness(&[1., 2., 3., 4., 5.]), 0.);
+		assert!(super::skewness(&[-7., -1., 0., 1., 7.]).abs() < 1e-15);
+	}
+
+	#[test]
+	fn skewness_skewed() {
+		assert_eq!(round::half_up(super::skewness(&[1., 2., 3., 10.]), 6), 1.018234);
+		assert!(super::skewness(&[10., 9., 8., 1.]) < -1.);
+	}
+
+	#[test]
+	fn kurtosis() {
+		assert_eq!(super::kurtosis(&[1., 2., 3., 10.]), 2.2304);
+		assert_eq!(super::excess_kurtosis(&[1., 2., 3., 10.]), 2.2304 - 3.);
+		assert_eq!(super::kurtosis(&[-1., 1., -1., 1.]), 1.);
+	}
+
+	#[test]
+	fn moments_invalid() {
+		assert!(super::skewness(&[1., 2.]).is_nan());
+		assert!(super::skewness(&[3., 3., 3.]).is_nan());
+		assert!(super::kurtosis(&[1., 2., 3.]).is_nan());
+		assert!(super::excess_kurtosis(&[1., 2., 3.]).is_nan());
+		assert!(super::kurtosis(&[2., 2., 2., 2.]).is_nan());
+	}
+
+	#[test]
+	fn moments_constant() {
+		assert!(super::skewness(&[0.1; 3]).is_nan());
+		assert!(super::skewness(&[0.7; 5]).is_nan());
+		assert!(super::kurtosis(&[0.1; 6]).is_nan());
+		assert!(super::kurtosis(&[0.7; 4]).is_nan());
+		assert!(super::excess_kurtosis(&[0.1; 6]).is_nan());
+	}
+
+	#[test]
+	fn covariance() {
+		let xs = [1., 2., 3., 4.];
+		assert_eq!(super::covariance(&xs, &[2., 4., 6., 8.], false), 2.5);
+		assert_eq!(super::covariance(&xs, &[8., 6., 4., 2.], false), -2.5);
+		assert_eq!(super::covariance(&[-3., -1., 1., 3.], &[1., -1., -1., 1.], true), 0.);
+		assert_eq!(super::covariance(&xs, &xs, false), variance::population(&xs));
+		assert_eq!(super::covariance(&xs, &xs, true), variance::sample(&xs));
+		assert_eq!(super::covariance(&xs, &[5., 5., 5., 5.], true), 0.);
+	}
+
+	#[test]
+	fn covariance_invalid() {
+		assert!(super::covariance(&[1., 2.], &[1., 2., 3.], false).is_nan());
+		assert!(super::covariance(&[], &[], false).is_nan());
+		assert!(super::covariance(&[1.], &[2.], true).is_nan());
+		assert_eq!(super::covariance(&[1.], &[2.], false), 0.);
+	}
+
+	#[test]
+	fn correlation_from_covariance() {
+		let xs = [2.1, 3.4, 1.9, 5.6, 4.4];
+		let ys = [1.2, 2.9, 2.4, 4.1, 3.3];
+		let r = super::covariance(&xs, &ys, true) / (variance::std_dev_sample(&xs) * variance::std_dev_sample(&ys));
+		assert!((super::correlation(&xs, &ys) - r).abs() < 1e-12);
+	}
+
+	#[test]
+	fn linear_fit_exact() {
+		assert_eq!(super::linear_fit(&[0., 1., 2., 3.], &[-2., 1., 4., 7.]), Some((3., -2.)));
+		assert_eq!(super::linear_fit(&[1., 2., 3.], &[4., 4., 4.]), Some((0., 4.)));
+		assert_eq!(super::linear_fit(&[-1., 1.], &[3., -1.]), Some((-2., 1.)));
+		assert_eq!(super::linear_fit(&[2., 4., 6., 8.], &[1.5, 2.5, 3.5, 4.5]), Some((0.5, 0.5)));
+	}
+
+	#[test]
+	fn linear_fit_noisy() {
+		let xs = [1., 2., 3., 4., 5.];
+		let ys = [2.2, 4.1, 6.3, 7.9, 10.2];
+		let (slope, intercept) = super::linear_fit(&xs, &ys).unwrap();
+		assert_eq!(round::half_up(slope, 12), 1.98);
+		assert_eq!(round::half_up(intercept, 12), 0.2);
+	}
+
+	#[test]
+	fn linear_fit_invalid() {
+		assert_eq!(super::linear_fit(&[1., 2.], &[1., 2., 3.]), None);
+		assert_eq!(super::linear_fit(&[1.], &[1.]), None);
+		assert_eq!(super::linear_fit(&[], &[]), None);
+		assert_eq!(super::linear_fit(&[2., 2., 2.], &[1., 2., 3.]), None);
+		assert_eq!(super::linear_fit(&[0.1, 0.1, 0.1], &[1., 2., 3.]), None);
+		assert_eq!(super::linear_fit(&[0.7; 4], &[1., 2., 3., 4.]), None);
+	}
+
+	#[test]
+	fn mode_binned_clustered() {
+		let slice = [0.31, 4.72, 4.98, 5.13, 5.27, 5.49, 9.64, 12.2, 12.4];
+		assert_eq!(super::mode_binned(&slice, 1.), 5.5);
+		assert_eq!(super::mode_binned(&slice, 0.5), 5.25);
+		assert_eq!(super::mode_binned(&[-2.3, -2.1, -2.6, 3.4], 1.), -2.5);
+		assert_eq!(super::mode_binned(&[7.3], 2.), 7.);
+	}
+
+	#[test]
+	fn mode_binned_tie() {
+		assert_eq!(super::mode_binned(&[8.1, 8.2, 1.1, 1.3, 4.4], 1.), 1.5);
+	}
+
+	#[test]
+	fn mode_binned_invalid() {
+		assert!(super::mode_binned(&[], 1.).is_nan());
+		assert!(super::mode_binned(&[1., 2.], 0.).is_nan());
+		assert!(super::mode_binned(&[1., 2.], -1.).is_nan());
+		assert!(super::mode_binned(&[1., 2.], f64::NAN).is_nan());
+		assert!(super::mode_binned(&[f64::NAN, f64::INFINITY], 1.).is_nan());
+		assert_eq!(super::mode_binned(&[f64::NAN, 3.2, 3.4, 0.1], 1.), 3.5);
+	}
+
+	#[test]
+	fn mode_binned_narrow() {
+		assert!(super::mode_binned(&[1., 2., 2., 3.], 1e-300).is_nan());
+		assert!(super::mode_binned(&[1e300, 2.], 1.).is_nan());
+		assert!(super::mode_binned(&[-1e20, 2.], 1e3).is_nan());
+		assert_eq!(super::mode_binned(&[1e15, 1e15, 2.], 1.), 1e15 + 0.5);
+	}
+
+	#[test]
+	fn histogram_counts() {
+		let slice = [2.3, 7.1, 0.4, 9.9, 5.5, 3.2, 8.8, 1.1, 6.4, 4.6, f64::NAN, f64::INFINITY];
+		let histogram = super::histogram(&slice, 4);
+		assert_eq!(histogram.len(), 4);
+		assert_eq!(histogram.iter().map(|bin| bin.1).sum::<usize>(), 10);
+		assert_eq!(histogram.iter().map(|bin| bin.1).collect::<Vec<usize>>(), vec![3, 2, 3, 2]);
+		let edges: Vec<f64> = histogram.iter().map(|bin| bin.0).collect();
+		assert_eq!(edges[0], 0.4);
+		for pair in edges.windows(2) {
+			assert!((pair[1] - pair[0] - 2.375).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn histogram_edges() {
+		// 2.36 is on the third edge although (2.36 - 2) / 0.9 * 5 < 2
+		assert_eq!(super::histogram(&[2.9, 2.36, 2., 2.15], 5)[2], (2.36, 1));
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		for _ in 0..200 {
+			let slice: Vec<f64> = (0..6).map(|_| rng.gen_range(-300, 300) as f64 / 100.).collect();
+			for bins in 1..8 {
+				let histogram = super::histogram(&slice, bins);
+				for (i, &(edge, count)) in histogram.iter().enumerate() {
+					let next = histogram.get(i + 1).map_or(f64::INFINITY, |bin| bin.0);
+					assert_eq!(slice.iter().filter(|x| **x >= edge && **x < next).count(), count);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn histogram_wide_range() {
+		assert_eq!(super::histogram(&[-1e308, 0., 1e308], 2), vec![(-1e308, 1), (0., 2)]);
+		assert_eq!(super::histogram(&[-f64::MAX, f64::MAX], 1), vec![(-f64::MAX, 2)]);
+		let histogram = super::histogram(&[-f64::MAX, 1., f64::MAX], 4);
+		assert_eq!(histogram.iter().map(|bin| bin.1).collect::<Vec<usize>>(), vec![1, 0, 1, 1]);
+		assert!(histogram.iter().all(|bin| bin.0.is_finite()));
+	}
+
+	#[test]
+	fn histogram_equal_values() {
+		assert_eq!(super::histogram(&[3., 3., 3.], 3), vec![(3., 3), (3., 0), (3., 0)]);
+		assert_eq!(super::histogram(&[3.], 1), vec![(3., 1)]);
+	}
+
+	#[test]
+	fn histogram_empty() {
+		assert_eq!(super::histogram(&[], 3), vec![]);
+		assert_eq!(super::histogram(&[1., 2.], 0), vec![]);
+		assert_eq!(super::histogram(&[f64::NAN], 2), vec![]);
+	}
+
+	#[test]
+	fn moving_average() {
+		let slice = [2., 4., 6., 8., 4., 0., 10., 2.];
+		let averages = super::moving_average(&slice, 4);
+		assert_eq!(averages.len(), 5);
+		assert_eq!(averages, vec![5., 5.5, 4.5, 5.5, 4.]);
+		assert_eq!(super::moving_average(&slice, 1), slice.to_vec());
+		assert_eq!(super::moving_average(&slice, 8), vec![4.5]);
+		for (i, average) in super::moving_average(&slice, 3).iter().enumerate() {
+			assert_eq!(*average, mean::arithmetic(&slice[i..i + 3]));
+		}
+	}
+
+	#[test]
+	fn moving_average_fractional() {
+		let mut rng = StdRng::from_seed(&[1, 2, 3][..]);
+		let slice: Vec<f64> = (0..500).map(|_| rng.gen_range(-1e3, 1e3) / 7.).collect();
+		for (i, average) in super::moving_average(&slice, 7).iter().enumerate() {
+			assert!((average - mean::arithmetic(&slice[i..i + 7])).abs() < 1e-13);
+		}
+	}
+
+	#[test]
+	fn moving_average_recovers() {
+		assert_eq!(super::moving_average(&[1., 1e17, 1., 1., 1.], 2), vec![5e16, 5e16, 1., 1.]);
+		assert_eq!(super::moving_average(&[1e17, 1., 1., 1., 1.], 2), vec![5e16, 1., 1., 1.]);
+		let slice = [1., f64::INFINITY, 1., 1., 1.];
+		assert_eq!(super::moving_average(&slice, 2), vec![f64::INFINITY, f64::INFINITY, 1., 1.]);
+		let averages = super::moving_average(&[2., f64::NAN, 4., 6., 8.], 2);
+		assert!(averages[..2].iter().all(|x| x.is_nan()));
+		assert_eq!(averages[2..].to_vec(), vec![5., 7.]);
+		let averages = super::moving_average(&[f64::INFINITY, f64::NEG_INFINITY, 3., 5.], 2);
+		assert!(averages[0].is_nan());
+		assert_eq!(averages[1..].to_vec(), vec![f64::NEG_INFINITY, 4.]);
+	}
+
+	#[test]
+	fn moving_average_invalid() {
+		assert_eq!(super::moving_average(&[1., 2.], 0), vec![]);
+		assert_eq!(super::moving_average(&[1., 2.], 3), vec![]);
+		assert_eq!(super::moving_average(&[], 1), vec![]);
+	}
+
+	#[test]
+	fn ema_recurrence() {
+		let slice = [10., 20., 30., 10.];
+		assert_eq!(super::ema(&slice, 0.25), vec![10., 12.5, 16.875, 15.15625]);
+		assert_eq!(super::ema(&slice, 1.), slice.to_vec());
+	}
+
+	#[test]
+	fn ema_ewma() {
+		let slice = [2.1, 3.4, 1.9, 5.6, 4.4, 3.3];
+		let mut ewma = mean::Ewma::new(0.3);
+		for (x, average) in slice.iter().zip(super::ema(&slice, 0.3)) {
+			ewma.push(*x);
+			assert_eq!(ewma.value(), average);
+		}
+	}
+
+	#[test]
+	fn ema_invalid() {
+		assert_eq!(super::ema(&[], 0.5), vec![]);
+		assert_eq!(super::ema(&[1., 2.], 0.), vec![]);
+		assert_eq!(super::ema(&[1., 2.], 1.5), vec![]);
+		assert_eq!(super::ema(&[1., 2.], f64::NAN), vec![]);
+		assert_eq!(super::ema(&[3.], 0.5), vec![3.]);
+	}
+}
@@ -0,0 +1,153 @@
+//! Functions for calculating variance and standard deviation
+use mean;
+
+/// Calculate population variance of data set `slice`.
+///
+/// Returns the mean squared deviation from the arithmetic mean.
+/// Returns `NAN` if `slice` is empty.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::variance;
+///
+/// let slice = [2., 4., 4., 4., 5., 5., 7., 9.];
+/// assert_eq!(variance::population(&slice), 4.);
+/// ```
+pub fn population(slice: &[f64]) -> f64 {
+	mean::arithmetic(&squared_deviations(slice))
+}
+
+/// Calculate sample variance of data set `slice`.
+///
+/// Returns the sum of squared deviations from the arithmetic mean divided
+/// by `n - 1` (Bessel's correction), which estimates the variance of the
+/// population `slice` was drawn from.
+/// Returns `NAN` if `slice` has fewer than two values.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::variance;
+///
+/// let slice = [1., 2., 3., 4.];
+/// assert_eq!(variance::sample(&slice), 5. / 3.);
+/// ```
+pub fn sample(slice: &[f64]) -> f64 {
+	let n = slice.len() as f64;
+	match slice.len() < 2 {
+		true => f64::NAN,
+		false => mean::arithmetic(&squared_deviations(slice)) * n / (n - 1.),
+	}
+}
+
+/// Calculate population standard deviation of data set `slice`.
+///
+/// Square root of `population`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::variance;
+///
+/// let slice = [2., 4., 4., 4., 5., 5., 7., 9.];
+/// assert_eq!(variance::std_dev_population(&slice), 2.);
+/// ```
+pub fn std_dev_population(slice: &[f64]) -> f64 {
+	population(slice).sqrt()
+}
+
+/// Calculate sample standard deviation of data set `slice`.
+///
+/// Square root of `sample`.
+///
+/// # Arguments
+///
+/// * `slice` - collection of values
+///
+/// # Example
+///
+/// ```
+/// use math::variance;
+///
+/// let slice = [1., 3.];
+/// assert_eq!(variance::std_dev_sample(&slice), 2f64.sqrt());
+/// ```
+pub fn std_dev_sample(slice: &[f64]) -> f64 {
+	sample(slice).sqrt()
+}
+
+fn squared_deviations(slice: &[f64]) -> Vec<f64> {
+	let mean = mean::arithmetic(slice);
+	slice.iter().map(|x| (x - mean) * (x - mean)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use round;
+
+	macro_rules! test_variance {
+		($func:path [ $($name:ident: $params:expr,)* ]) => {
+		$(
+			#[test]
+			fn $name() {
+				let (slice, expected): (&[f64], f64) = $params;
+				let result = $func(slice);
+				match result.is_nan() {
+					true => assert!(expected.is_nan()),
+					false => assert_eq!(round::half_up(result, 6), expected),
+				}
+			}
+		)*
+		}
+	}
+
+	test_variance! { super::population [
+		population_1: (&[-7., -4., 1., 3., 8.], 27.76),
+		population_2: (&[0., 0., 0., 0., 0.], 0.),
+		population_3: (&[1., 2., 6., 4., 13.], 18.16),
+		population_4: (&[2., 3., 5., 7., 11.], 10.24),
+		population_5: (&[5.], 0.),
+		population_6: (&[], f64::NAN),
+	]}
+
+	test_variance! { super::sample [
+		sample_1: (&[-7., -4., 1., 3., 8.], 34.7),
+		sample_2: (&[0., 0., 0., 0., 0.], 0.),
+		sample_3: (&[1., 2., 6., 4., 13.], 22.7),
+		sample_4: (&[2., 3., 5., 7., 11.], 12.8),
+		sample_5: (&[5.], f64::NAN),
+		sample_6: (&[], f64::NAN),
+	]}
+
+	test_variance! { super::std_dev_population [
+		std_dev_population_1: (&[-7., -4., 1., 3., 8.], 5.268776),
+		std_dev_population_2: (&[2., 4., 4., 4., 5., 5., 7., 9.], 2.),
+		std_dev_population_3: (&[], f64::NAN),
+	]}
+
+	test_variance! { super::std_dev_sample [
+		std_dev_sample_1: (&[-7., -4., 1., 3., 8.], 5.890671),
+		std_dev_sample_2: (&[2., 4., 4., 4., 5., 5., 7., 9.], 2.13809),
+		std_dev_sample_3: (&[5.], f64::NAN),
+	]}
+
+	#[test]
+	fn large_offset() {
+		let slice: Vec<f64> = [4., 7., 13., 16.].iter().map(|x| x + 1e9).collect();
+		assert_eq!(super::population(&slice), 22.5);
+		assert_eq!(super::sample(&slice), 30.);
+	}
+}
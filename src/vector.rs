@@ -0,0 +1,678 @@
+//! Vector functions
+use special;
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+/// Convolution mode.
+///
+/// Selects which part of the full discrete convolution is returned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConvMode {
+	/// Every point of overlap, of length `n + m - 1`.
+	Full,
+	/// Centre of the full output, of length `max(n, m)`.
+	Same,
+	/// Only points of complete overlap, of length `max(n, m) - min(n, m) + 1`.
+	Valid,
+}
+
+/// Find index of the largest element of vector `v`.
+///
+/// `NAN` elements are ignored and the first index is returned on ties.
+/// Returns `None` if `v` is empty or contains only `NAN`.
+///
+/// # Arguments
+///
+/// * `v` - vector
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// assert_eq!(vector::argmax(&[3., 7., 1., 7.]), Some(1));
+/// ```
+pub fn argmax(v: &[f64]) -> Option<usize> {
+	position(v, |x, best| x > best)
+}
+
+/// Find index of the smallest element of vector `v`.
+///
+/// `NAN` elements are ignored and the first index is returned on ties.
+/// Returns `None` if `v` is empty or contains only `NAN`.
+///
+/// # Arguments
+///
+/// * `v` - vector
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// assert_eq!(vector::argmin(&[3., 1., 7., 1.]), Some(1));
+/// ```
+pub fn argmin(v: &[f64]) -> Option<usize> {
+	position(v, |x, best| x < best)
+}
+
+/// Find indices that sort vector `v` in ascending order.
+///
+/// The sort is stable and puts `NAN` elements at the end.
+///
+/// # Arguments
+///
+/// * `v` - vector
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// assert_eq!(vector::argsort(&[3., f64::NAN, 1., 2.]), vec![2, 3, 0, 1]);
+/// ```
+pub fn argsort(v: &[f64]) -> Vec<usize> {
+	let mut indices: Vec<usize> = (0..v.len()).collect();
+	indices.sort_by(|&i, &j| match (v[i].is_nan(), v[j].is_nan()) {
+		(true, true) => Ordering::Equal,
+		(true, false) => Ordering::Greater,
+		(false, true) => Ordering::Less,
+		(false, false) => v[i].partial_cmp(&v[j]).unwrap(),
+	});
+	indices
+}
+
+/// Calculate discrete convolution of `signal` and `kernel`.
+///
+/// Element `k` of the full convolution is `Σ signal[i] * kernel[k - i]`.
+/// The part of it that is returned is selected by `mode`.
+/// Returns an empty vector if either input is empty.
+///
+/// # Arguments
+///
+/// * `signal` - vector to convolve
+/// * `kernel` - vector to convolve with
+/// * `mode` - part of the convolution to return
+///
+/// # Example
+///
+/// ```
+/// use math::vector::{ self, ConvMode };
+///
+/// let full = vector::convolve(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Full);
+/// assert_eq!(full, vec![0., 1., 2.5, 4., 1.5]);
+/// ```
+pub fn convolve(signal: &[f64], kernel: &[f64], mode: ConvMode) -> Vec<f64> {
+	let (n, m) = (signal.len(), kernel.len());
+	if n == 0 || m == 0 {
+		return vec![];
+	}
+	let mut full = vec![0.; n + m - 1];
+	for (i, s) in signal.iter().enumerate() {
+		for (j, k) in kernel.iter().enumerate() {
+			full[i + j] += s * k;
+		}
+	}
+	let (long, short) = (n.max(m), n.min(m));
+	match mode {
+		ConvMode::Full => full,
+		ConvMode::Same => full[(short - 1) / 2..][..long].to_vec(),
+		ConvMode::Valid => full[short - 1..long].to_vec(),
+	}
+}
+
+/// Calculate discrete cross-correlation of `signal` and `kernel`.
+///
+/// Equal to the convolution with the reversed kernel, so element `k` of
+/// the full output is `Σ signal[i + k - m + 1] * kernel[i]` where `m` is the
+/// length of the kernel. The part of it that is returned is selected by
+/// `mode`. Returns an empty vector if either input is empty.
+///
+/// # Arguments
+///
+/// * `signal` - vector to correlate
+/// * `kernel` - vector to correlate with
+/// * `mode` - part of the correlation to return
+///
+/// # Example
+///
+/// ```
+/// use math::vector::{ self, ConvMode };
+///
+/// let full = vector::correlate(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Full);
+/// assert_eq!(full, vec![0.5, 2., 3.5, 3., 0.]);
+/// ```
+pub fn correlate(signal: &[f64], kernel: &[f64], mode: ConvMode) -> Vec<f64> {
+	let reversed: Vec<f64> = kernel.iter().rev().cloned().collect();
+	convolve(signal, &reversed, mode)
+}
+
+/// Calculate dot product of vectors `a` and `b`.
+///
+/// Returns `NAN` if the vectors differ in length.
+///
+/// # Arguments
+///
+/// * `a` - first vector
+/// * `b` - second vector
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// assert_eq!(vector::dot(&[1., 2., 3.], &[4., -5., 6.]), 12.);
+/// ```
+pub fn dot(a: &[f64], b: &[f64]) -> f64 {
+	match a.len() == b.len() {
+		true => a.iter().zip(b).fold(0., |sum, (x, y)| sum + x * y),
+		false => f64::NAN,
+	}
+}
+
+/// Calculate discrete Fourier transform of real vector `input`.
+///
+/// Uses the iterative radix-2 Cooley-Tukey algorithm, so the length of
+/// `input` must be a power of two. Input of any other length, including
+/// empty input, is not padded and gives an empty result. Element `k` is
+/// the complex coefficient `Σ input[j] * exp(-2πijk / n)` as a
+/// `(re, im)` pair.
+///
+/// # Arguments
+///
+/// * `input` - vector of power of two length
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// assert_eq!(vector::fft(&[1., 1., 1., 1.]), vec![(4., 0.), (0., 0.), (0., 0.), (0., 0.)]);
+/// ```
+pub fn fft(input: &[f64]) -> Vec<(f64, f64)> {
+	let mut data: Vec<(f64, f64)> = input.iter().map(|&x| (x, 0.)).collect();
+	match input.len().is_power_of_two() {
+		true => {
+			transform(&mut data, false);
+			data
+		},
+		false => vec![],
+	}
+}
+
+/// Calculate inverse discrete Fourier transform of `input`.
+///
+/// The inverse of `fft`, returning only the real part of the result.
+/// The length of `input` must be a power of two, input of any other length
+/// gives an empty result.
+///
+/// # Arguments
+///
+/// * `input` - complex `(re, im)` coefficients of power of two length
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// assert_eq!(vector::ifft(&[(4., 0.), (0., 0.), (0., 0.), (0., 0.)]), vec![1., 1., 1., 1.]);
+/// ```
+pub fn ifft(input: &[(f64, f64)]) -> Vec<f64> {
+	let mut data = input.to_vec();
+	match input.len().is_power_of_two() {
+		true => {
+			transform(&mut data, true);
+			data.iter().map(|&(re, _)| re / input.len() as f64).collect()
+		},
+		false => vec![],
+	}
+}
+
+/// Calculate logarithm of softmax of vector `v`.
+///
+/// Computed with the log-sum-exp trick, so it stays finite where taking
+/// the logarithm of `softmax` would underflow to negative infinity.
+///
+/// # Arguments
+///
+/// * `v` - vector
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// let v = [0., 0.];
+/// assert_eq!(vector::log_softmax(&v), vec![-(2f64.ln()), -(2f64.ln())]);
+/// ```
+pub fn log_softmax(v: &[f64]) -> Vec<f64> {
+	let lse = special::logsumexp(v);
+	v.iter().map(|x| x - lse).collect()
+}
+
+/// Scale vector `v` to range `[new_min, new_max]`.
+///
+/// Maps the minimum of `v` to `new_min`, the maximum to `new_max` and
+/// everything in between linearly, preserving the order of the values.
+/// If all values are equal, every value is mapped to `new_min`.
+///
+/// # Arguments
+///
+/// * `v` - vector
+/// * `new_min` - lower bound of the range
+/// * `new_max` - upper bound of the range
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// let v = [2., 4., 10.];
+/// assert_eq!(vector::min_max_scale(&v, 0., 1.), vec![0., 0.25, 1.]);
+/// ```
+pub fn min_max_scale(v: &[f64], new_min: f64, new_max: f64) -> Vec<f64> {
+	let min = v.iter().cloned().fold(f64::INFINITY, f64::min);
+	let max = v.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+	v.iter().map(|x| match min == max {
+		true => new_min,
+		false => map_range(*x, (min, max), (new_min, new_max)),
+	}).collect()
+}
+
+/// Calculate softmax of vector `v`.
+///
+/// The maximum of `v` is subtracted before exponentiation to avoid
+/// overflow. The result sums up to 1.
+///
+/// # Arguments
+///
+/// * `v` - vector
+///
+/// # Example
+///
+/// ```
+/// use math::vector;
+///
+/// let v = [0., 0., 0., 0.];
+/// assert_eq!(vector::softmax(&v), vec![0.25, 0.25, 0.25, 0.25]);
+/// ```
+pub fn softmax(v: &[f64]) -> Vec<f64> {
+	let max = v.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+	let exp: Vec<f64> = v.iter().map(|x| (x - max).exp()).collect();
+	let sum = exp.iter().fold(0., |a, x| a + x);
+	exp.iter().map(|x| x / sum).collect()
+}
+
+/// Incremental dot product.
+///
+/// Accumulates the dot product of a stream of value pairs without storing
+/// the vectors. Rounding error of every product and of the running sum is
+/// compensated for, so long streams stay accurate to about machine
+/// precision.
+///
+/// # Example
+///
+/// ```
+/// use math::vector::DotAccumulator;
+///
+/// let mut dot = DotAccumulator::new();
+/// dot.push(1., 4.);
+/// dot.push(2., -5.);
+/// dot.push(3., 6.);
+/// assert_eq!(dot.value(), 12.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DotAccumulator {
+	sum: f64,
+	compensation: f64,
+}
+
+impl DotAccumulator {
+	/// Create accumulator with value 0.
+	pub fn new() -> DotAccumulator {
+		DotAccumulator { sum: 0., compensation: 0. }
+	}
+
+	/// Add product of `a` and `b` to the dot product.
+	///
+	/// # Arguments
+	///
+	/// * `a` - element of first vector
+	/// * `b` - element of second vector
+	pub fn push(&mut self, a: f64, b: f64) {
+		let product = a * b;
+		self.add(product);
+		self.compensation += a.mul_add(b, -product);
+	}
+
+	/// Current dot product, 0 if no values have been pushed.
+	pub fn value(&self) -> f64 {
+		self.sum + self.compensation
+	}
+
+	fn add(&mut self, value: f64) {
+		// Neumaier's variant of Kahan summation
+		let sum = self.sum + value;
+		self.compensation += match self.sum.abs() >= value.abs() {
+			true => (self.sum - sum) + value,
+			false => (value - sum) + self.sum,
+		};
+		self.sum = sum;
+	}
+}
+
+fn map_range(value: f64, from: (f64, f64), to: (f64, f64)) -> f64 {
+	to.0 + (value - from.0) * (to.1 - to.0) / (from.1 - from.0)
+}
+
+fn position<F: Fn(f64, f64) -> bool>(v: &[f64], better: F) -> Option<usize> {
+	v.iter().enumerate()
+		.filter(|&(_, x)| !x.is_nan())
+		.fold(None, |best: Option<(usize, f64)>, (i, &x)| match best {
+			Some((_, b)) if !better(x, b) => best,
+			_ => Some((i, x)),
+		})
+		.map(|(i, _)| i)
+}
+
+fn transform(data: &mut [(f64, f64)], inverse: bool) {
+	let n = data.len();
+	let bits = n.trailing_zeros();
+	for i in 0..n {
+		let j = i.reverse_bits().checked_shr(usize::BITS - bits).unwrap_or(0);
+		if i < j {
+			data.swap(i, j);
+		}
+	}
+	let sign = match inverse {
+		true => 1.,
+		false => -1.,
+	};
+	let mut len = 2;
+	while len <= n {
+		let angle = sign * 2. * PI / len as f64;
+		for start in (0..n).step_by(len) {
+			for k in 0..len / 2 {
+				let (sin, cos) = (angle * k as f64).sin_cos();
+				let (re, im) = data[start + k + len / 2];
+				let odd = (re * cos - im * sin, re * sin + im * cos);
+				let even = data[start + k];
+				data[start + k] = (even.0 + odd.0, even.1 + odd.1);
+				data[start + k + len / 2] = (even.0 - odd.0, even.1 - odd.1);
+			}
+		}
+		len *= 2;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ConvMode;
+	use std::f64::consts::PI;
+
+	fn assert_close(a: &[f64], b: &[f64]) {
+		assert_eq!(a.len(), b.len());
+		for (x, y) in a.iter().zip(b) {
+			assert!((x - y).abs() < 1e-12, "{} != {}", x, y);
+		}
+	}
+
+	#[test]
+	fn argmax() {
+		assert_eq!(super::argmax(&[3., 7., 1.]), Some(1));
+		assert_eq!(super::argmax(&[3., 7., 1., 7.]), Some(1));
+		assert_eq!(super::argmax(&[f64::NAN, 2., f64::NAN, 5.]), Some(3));
+		assert_eq!(super::argmax(&[f64::NAN, f64::NAN]), None);
+		assert_eq!(super::argmax(&[]), None);
+	}
+
+	#[test]
+	fn argmin() {
+		assert_eq!(super::argmin(&[3., 7., 1.]), Some(2));
+		assert_eq!(super::argmin(&[3., 1., 7., 1.]), Some(1));
+		assert_eq!(super::argmin(&[f64::NAN, 2., f64::NAN, 5.]), Some(1));
+		assert_eq!(super::argmin(&[f64::NAN]), None);
+		assert_eq!(super::argmin(&[]), None);
+	}
+
+	#[test]
+	fn argsort() {
+		let v = [0.5, -3., 8., 0.5, f64::NAN, 2., f64::NAN, -1.];
+		let indices = super::argsort(&v);
+		let mut sorted = indices.clone();
+		sorted.sort();
+		assert_eq!(sorted, (0..v.len()).collect::<Vec<usize>>());
+		assert_eq!(indices, vec![1, 7, 0, 3, 5, 2, 4, 6]);
+		assert_eq!(super::argsort(&[]), Vec::<usize>::new());
+	}
+
+	#[test]
+	fn convolve() {
+		let full = super::convolve(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Full);
+		assert_eq!(full, vec![0., 1., 2.5, 4., 1.5]);
+		assert_eq!(super::convolve(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Same), vec![1., 2.5, 4.]);
+		assert_eq!(super::convolve(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Valid), vec![2.5]);
+		assert_eq!(super::convolve(&[0., 1., 0.5], &[1., 2., 3.], ConvMode::Full), full);
+	}
+
+	#[test]
+	fn convolve_identity() {
+		let signal = [3., -1., 4., 1., -5., 9.];
+		for &mode in [ConvMode::Full, ConvMode::Same, ConvMode::Valid].iter() {
+			assert_eq!(super::convolve(&signal, &[1.], mode), signal.to_vec());
+		}
+	}
+
+	#[test]
+	fn convolve_box() {
+		let signal = [1., 2., 3., 4., 5., 6.];
+		let sums = super::convolve(&signal, &[1., 1., 1.], ConvMode::Valid);
+		let expected: Vec<f64> = signal.windows(3).map(|w| w.iter().sum()).collect();
+		assert_eq!(sums, expected);
+	}
+
+	#[test]
+	fn convolve_lengths() {
+		let signal = [1.; 7];
+		for m in 1..10 {
+			let kernel = vec![1.; m];
+			assert_eq!(super::convolve(&signal, &kernel, ConvMode::Full).len(), 7 + m - 1);
+			assert_eq!(super::convolve(&signal, &kernel, ConvMode::Same).len(), 7.max(m));
+			assert_eq!(super::convolve(&signal, &kernel, ConvMode::Valid).len(), 7.max(m) - 7.min(m) + 1);
+		}
+	}
+
+	#[test]
+	fn convolve_empty() {
+		assert_eq!(super::convolve(&[], &[1., 2.], ConvMode::Full), vec![]);
+		assert_eq!(super::convolve(&[1., 2.], &[], ConvMode::Same), vec![]);
+		assert_eq!(super::correlate(&[], &[], ConvMode::Valid), vec![]);
+	}
+
+	#[test]
+	fn correlate() {
+		let full = super::correlate(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Full);
+		assert_eq!(full, vec![0.5, 2., 3.5, 3., 0.]);
+		assert_ne!(full, super::convolve(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Full));
+		assert_eq!(super::correlate(&[1., 2., 3.], &[0., 1., 0.5], ConvMode::Same), vec![2., 3.5, 3.]);
+		// symmetric kernels make both operations agree
+		let signal = [2., 7., 1., 8.];
+		let kernel = [1., 2., 1.];
+		assert_eq!(super::correlate(&signal, &kernel, ConvMode::Full), super::convolve(&signal, &kernel, ConvMode::Full));
+	}
+
+	#[test]
+	fn dot() {
+		assert_eq!(super::dot(&[1., 2., 3.], &[4., -5., 6.]), 12.);
+		assert_eq!(super::dot(&[], &[]), 0.);
+		assert!(super::dot(&[1., 2.], &[1.]).is_nan());
+	}
+
+	#[test]
+	fn dot_accumulator() {
+		let a = [0.5, -1.25, 3., 2.75, -0.125];
+		let b = [4., 2., -1.5, 0.25, 8.];
+		let mut dot = super::DotAccumulator::new();
+		for (x, y) in a.iter().zip(b.iter()) {
+			dot.push(*x, *y);
+		}
+		assert_eq!(dot.value(), super::dot(&a, &b));
+	}
+
+	#[test]
+	fn dot_accumulator_drift() {
+		let mut dot = super::DotAccumulator::new();
+		let mut naive = 0.;
+		for _ in 0..1_000_000 {
+			dot.push(0.1, 1.);
+			naive += 0.1;
+		}
+		assert_eq!(dot.value(), 100000.);
+		assert!((naive - 100000f64).abs() > 1e-6);
+	}
+
+	#[test]
+	fn dot_accumulator_cancellation() {
+		let mut dot = super::DotAccumulator::new();
+		dot.push(1e16, 1.);
+		dot.push(1., 1.);
+		dot.push(-1e16, 1.);
+		assert_eq!(dot.value(), 1.);
+		assert_eq!(super::dot(&[1e16, 1., -1e16], &[1., 1., 1.]), 0.);
+	}
+
+	#[test]
+	fn dot_accumulator_empty() {
+		assert_eq!(super::DotAccumulator::new().value(), 0.);
+		assert_eq!(super::DotAccumulator::default().value(), 0.);
+	}
+
+	#[test]
+	fn fft_constant() {
+		let spectrum = super::fft(&[2.5; 8]);
+		assert_eq!(spectrum[0], (20., 0.));
+		for bin in spectrum[1..].iter() {
+			assert!(bin.0.abs() < 1e-12 && bin.1.abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn fft_sinusoid() {
+		let n = 64;
+		let signal: Vec<f64> = (0..n).map(|j| (2. * PI * 5. * j as f64 / n as f64).cos()).collect();
+		let spectrum = super::fft(&signal);
+		let magnitudes: Vec<f64> = spectrum.iter().map(|&(re, im)| (re * re + im * im).sqrt()).collect();
+		for (k, magnitude) in magnitudes.iter().enumerate() {
+			match k == 5 || k == n - 5 {
+				true => assert!((magnitude - 32.).abs() < 1e-9),
+				false => assert!(magnitude.abs() < 1e-9),
+			}
+		}
+	}
+
+	#[test]
+	fn fft_naive() {
+		let signal = [0.5, -1., 3., 2.25, 0., 4., -2., 1.];
+		let n = signal.len();
+		for (k, &(re, im)) in super::fft(&signal).iter().enumerate() {
+			let expected = signal.iter().enumerate().fold((0., 0.), |a, (j, x)| {
+				let angle = -2. * PI * (j * k) as f64 / n as f64;
+				(a.0 + x * angle.cos(), a.1 + x * angle.sin())
+			});
+			assert!((re - expected.0).abs() < 1e-12 && (im - expected.1).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn fft_round_trip() {
+		let signal: Vec<f64> = (0..256).map(|j| (j as f64 * 0.37).sin() * 3. + (j % 7) as f64).collect();
+		let reconstructed = super::ifft(&super::fft(&signal));
+		assert_eq!(reconstructed.len(), signal.len());
+		for (a, b) in reconstructed.iter().zip(signal.iter()) {
+			assert!((a - b).abs() < 1e-12);
+		}
+		assert_eq!(super::fft(&[7.]), vec![(7., 0.)]);
+		assert_eq!(super::ifft(&[(7., 0.)]), vec![7.]);
+	}
+
+	#[test]
+	fn fft_not_power_of_two() {
+		assert_eq!(super::fft(&[]), vec![]);
+		assert_eq!(super::fft(&[1., 2., 3.]), vec![]);
+		assert_eq!(super::ifft(&[]), Vec::<f64>::new());
+		assert_eq!(super::ifft(&[(1., 0.); 6]), Vec::<f64>::new());
+	}
+
+	#[test]
+	fn min_max_scale_unit() {
+		let result = super::min_max_scale(&[3., -1., 7., 5.], 0., 1.);
+		assert_eq!(result, vec![0.5, 0., 1., 0.75]);
+	}
+
+	#[test]
+	fn min_max_scale_range() {
+		let result = super::min_max_scale(&[3., -1., 7., 5.], -10., 10.);
+		assert_eq!(result, vec![0., -10., 10., 5.]);
+		let result = super::min_max_scale(&[3., -1., 7., 5.], 1., 0.);
+		assert_eq!(result, vec![0.5, 1., 0., 0.25]);
+	}
+
+	#[test]
+	fn min_max_scale_equal() {
+		assert_eq!(super::min_max_scale(&[4., 4., 4.], 2., 5.), vec![2., 2., 2.]);
+		assert_eq!(super::min_max_scale(&[], 0., 1.), Vec::<f64>::new());
+	}
+
+	#[test]
+	fn min_max_scale_order() {
+		let v = [0.3, 12., -4.5, 8.25, 1e-3, 6.];
+		let result = super::min_max_scale(&v, 0., 100.);
+		for i in 0..v.len() {
+			for j in 0..v.len() {
+				assert_eq!(v[i] < v[j], result[i] < result[j]);
+			}
+		}
+	}
+
+	#[test]
+	fn softmax_distribution() {
+		let result = super::softmax(&[1., 2., 3., -4.]);
+		assert!((result.iter().sum::<f64>() - 1.).abs() < 1e-12);
+		assert!(result.iter().all(|x| *x > 0. && *x < 1.));
+		assert!(result[2] > result[1] && result[1] > result[0] && result[0] > result[3]);
+	}
+
+	#[test]
+	fn softmax_uniform() {
+		assert_eq!(super::softmax(&[7., 7., 7., 7.]), vec![0.25, 0.25, 0.25, 0.25]);
+	}
+
+	#[test]
+	fn softmax_shift() {
+		let v = [1., 2., 3.];
+		let shifted: Vec<f64> = v.iter().map(|x| x + 100.).collect();
+		assert_close(&super::softmax(&v), &super::softmax(&shifted));
+	}
+
+	#[test]
+	fn softmax_large() {
+		let result = super::softmax(&[1000., 999., -1000.]);
+		assert!(result.iter().all(|x| !x.is_nan()));
+		assert_close(&result[..2], &super::softmax(&[1., 0.])[..]);
+	}
+
+	#[test]
+	fn softmax_empty() {
+		assert_eq!(super::softmax(&[]), Vec::<f64>::new());
+		assert_eq!(super::log_softmax(&[]), Vec::<f64>::new());
+	}
+
+	#[test]
+	fn log_softmax() {
+		let v = [1., 2., 3.];
+		let expected: Vec<f64> = super::softmax(&v).iter().map(|x| x.ln()).collect();
+		assert_close(&super::log_softmax(&v), &expected);
+		let result = super::log_softmax(&[1000., 0.]);
+		assert_close(&result, &[0., -1000.]);
+	}
+}
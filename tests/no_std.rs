@@ -0,0 +1,56 @@
+//! Core rounding functions, which are also built without the standard
+//! library. Run `cargo test --no-default-features --features libm --test
+//! no_std` to check them in the `no_std` configuration.
+extern crate math;
+
+use math::round::{ self, ErrorFeedbackRounder, Round, RoundIterExt, RoundingMode };
+
+#[test]
+fn free_functions() {
+	assert_eq!(round::ceil(2.46159, 3), 2.462);
+	assert_eq!(round::floor(2.46159, 3), 2.461);
+	assert_eq!(round::half_up(2.5, 0), 3.);
+	assert_eq!(round::half_down(2.5, 0), 2.);
+	assert_eq!(round::half_away_from_zero(-2.5, 0), -3.);
+	assert_eq!(round::half_towards_zero(-2.5, 0), -2.);
+	assert_eq!(round::half_to_even(3450., -2), 3400.);
+	assert_eq!(round::half_to_odd(0.25, 1), 0.3);
+	assert_eq!(round::truncate(-1.999, 2), -1.99);
+	assert_eq!(round::floor_to_bucket(1000, 60), 960);
+	assert_eq!(round::ceil_to_bucket(1000, 60), 1020);
+	assert_eq!(round::snap_to_grid(7.3, 4.9, 2.5, 2.), (7.5, 4.));
+	assert_eq!(round::to_place(12345., -2, RoundingMode::HalfUp), 12300.);
+	assert_eq!(round::round_bits(0.1, 4, RoundingMode::HalfUp), 0.125);
+	assert_eq!(round::truncate_bits(-0.3, 2), -0.25);
+}
+
+#[test]
+fn rounders() {
+	let mut rounder = ErrorFeedbackRounder::new(0);
+	let rounded: Vec<f64> = [0.4, 0.4, 0.4].iter().map(|x| rounder.round(*x)).collect();
+	assert_eq!(rounded, vec![0., 1., 0.]);
+	let rounded: Vec<f64> = vec![1.25, 2.35].into_iter().rounded(1, RoundingMode::HalfToEven).collect();
+	assert_eq!(rounded, vec![1.2, 2.4]);
+}
+
+#[test]
+fn modes() {
+	assert_eq!(round::round_with(2.5, 0, RoundingMode::HalfToEven), 2.);
+	assert_eq!(round::round_to_multiple(7., 5., RoundingMode::HalfUp), 5.);
+	assert_eq!(round::fraction(1.3, 4, RoundingMode::HalfUp), 1.25);
+	assert_eq!(round::compare_modes(2.5, 0, RoundingMode::Ceil, RoundingMode::Floor), (3., 2., true));
+	let rounded = round::round_with(2.5, 0, RoundingMode::Stochastic);
+	assert!(rounded == 2. || rounded == 3.);
+	assert_eq!(round::round_with(2.7, 0, RoundingMode::Stochastic), 3.);
+	assert_eq!("stochastic".parse(), Ok(RoundingMode::Stochastic));
+	assert_eq!("half-even".parse(), Ok(RoundingMode::HalfToEven));
+	assert!("half-evn".parse::<RoundingMode>().is_err());
+}
+
+#[test]
+fn trait_methods() {
+	assert_eq!(2.46159f32.half_up(3), 2.462);
+	assert_eq!(Round::ceil(3456f32, -2), 3500.);
+	assert_eq!(2.675f64.truncate(2), 2.67);
+	assert!(f64::NAN.half_up(2).is_nan());
+}
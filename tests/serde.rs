@@ -0,0 +1,34 @@
+//! Serialization of `RoundingMode`, run with `cargo test --features serde`.
+#![cfg(feature = "serde")]
+
+extern crate math;
+extern crate serde_json;
+
+use math::round::RoundingMode;
+
+#[test]
+fn rounding_mode_round_trip() {
+	let modes = [
+		(RoundingMode::Ceil, "\"ceil\""),
+		(RoundingMode::Floor, "\"floor\""),
+		(RoundingMode::HalfUp, "\"half_up\""),
+		(RoundingMode::HalfDown, "\"half_down\""),
+		(RoundingMode::HalfAwayFromZero, "\"half_away_from_zero\""),
+		(RoundingMode::HalfTowardsZero, "\"half_towards_zero\""),
+		(RoundingMode::HalfToEven, "\"half_to_even\""),
+		(RoundingMode::HalfToOdd, "\"half_to_odd\""),
+		(RoundingMode::Stochastic, "\"stochastic\""),
+	];
+	for &(mode, json) in modes.iter() {
+		assert_eq!(serde_json::to_string(&mode).unwrap(), json);
+		assert_eq!(serde_json::from_str::<RoundingMode>(json).unwrap(), mode);
+	}
+}
+
+#[test]
+fn rounding_mode_invalid() {
+	let error = serde_json::from_str::<RoundingMode>("\"half_sideways\"").unwrap_err();
+	assert!(error.to_string().starts_with("unknown variant `half_sideways`"));
+	assert!(serde_json::from_str::<RoundingMode>("\"HalfUp\"").is_err());
+	assert!(serde_json::from_str::<RoundingMode>("3").is_err());
+}